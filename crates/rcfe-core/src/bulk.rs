@@ -0,0 +1,295 @@
+use crate::{
+    ByteSequence,
+    error::Error,
+    etcdserverpb::{TxnResponse, response_op::Response as ResponseUnion},
+    kv::KVClient,
+    options::{Namespaceable, bulk::BulkOptions, get::GetOptions, txn::op::RequestOp},
+    response::{KeyValue, ResponseHeader},
+    txn::Txn,
+};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use prost::Message;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+/// Bytes framing an operation within a transaction, its field tag and length.
+const OP_FRAMING_BYTES: usize = 6;
+
+/// Bytes of the revision field pinning reads to the revision of the first transaction.
+const REVISION_BYTES: usize = 11;
+
+/// Result of a bulk put or delete, one entry per transaction.
+/// Batches fail independently, a failed batch applied none of its operations while the
+/// other batches may have been applied.
+/// # Examples
+/// ```rust,no_run
+/// use rcfe_core::{BulkOptions, Error, KVClient};
+/// async fn example<K: KVClient + Clone>(mut client: K) -> Result<(), Error> {
+///     let kvs = (0..1000).map(|i| (format!("key/{i}"), format!("value-{i}")));
+///     let result = client.put_many(kvs, BulkOptions::default()).await;
+///     for batch in result.failed() {
+///         eprintln!("keys {:?} failed: {:?}", batch.range(), batch.error());
+///     }
+///     result.into_result()?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct BulkResult {
+    batches: Vec<BatchResult>,
+}
+
+/// Result of a single transaction of a bulk operation.
+#[derive(Debug)]
+pub struct BatchResult {
+    range: Range<usize>,
+    result: Result<TxnResponse, Error>,
+}
+
+impl BulkResult {
+    /// Returns the results of the batches, in the order of the operations.
+    pub fn batches(&self) -> &[BatchResult] {
+        &self.batches
+    }
+
+    /// Consumes the result, returning the results of the batches.
+    pub fn into_batches(self) -> Vec<BatchResult> {
+        self.batches
+    }
+
+    /// Returns true if every batch succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.batches.iter().all(BatchResult::is_ok)
+    }
+
+    /// Returns the number of operations applied by the successful batches.
+    pub fn succeeded(&self) -> usize {
+        self.batches
+            .iter()
+            .filter(|batch| batch.is_ok())
+            .map(BatchResult::len)
+            .sum()
+    }
+
+    /// Returns the batches that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &BatchResult> {
+        self.batches.iter().filter(|batch| !batch.is_ok())
+    }
+
+    /// Returns the number of keys deleted by the successful batches.
+    pub fn deleted(&self) -> i64 {
+        self.batches.iter().map(BatchResult::deleted).sum()
+    }
+
+    /// Returns the error of the first failed batch, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.batches.iter().find_map(BatchResult::error)
+    }
+
+    /// Converts the result into an error if any batch failed, returning the error of the
+    /// first failed batch.
+    pub fn into_result(self) -> Result<Self, Error> {
+        match self.batches.iter().position(|batch| !batch.is_ok()) {
+            Some(index) => Err(self
+                .batches
+                .into_iter()
+                .nth(index)
+                .and_then(|batch| batch.result.err())
+                .expect("failed batch")),
+            None => Ok(self),
+        }
+    }
+}
+
+impl BatchResult {
+    /// Returns the indices of the operations of the batch, in the order they were given.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the number of operations of the batch.
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns true if the batch has no operations.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns true if the transaction of the batch succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Returns the header of the transaction, `None` if it failed.
+    pub fn header(&self) -> Option<ResponseHeader> {
+        let response = self.result.as_ref().ok()?;
+        Some(response.header.map(Into::into).unwrap_or_default())
+    }
+
+    /// Returns the number of keys deleted by the batch, `0` if it failed.
+    pub fn deleted(&self) -> i64 {
+        let Ok(response) = &self.result else {
+            return 0;
+        };
+        response
+            .responses
+            .iter()
+            .filter_map(|op| match &op.response {
+                Some(ResponseUnion::ResponseDeleteRange(delete)) => Some(delete.deleted),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Returns the error of the transaction, `None` if it succeeded.
+    pub fn error(&self) -> Option<&Error> {
+        self.result.as_ref().err()
+    }
+
+    /// Consumes the batch, returning the response of the transaction.
+    pub fn into_result(self) -> Result<TxnResponse, Error> {
+        self.result
+    }
+}
+
+/// Commits the operations in batches, see [`KVClient::put_many`].
+pub(crate) async fn run<K>(client: &K, ops: Vec<RequestOp>, options: BulkOptions) -> BulkResult
+where
+    K: KVClient + Clone,
+{
+    let batches = split(ops, &options, namespace_overhead(client));
+    let batches = stream::iter(batches)
+        .map(|(range, ops)| {
+            let mut client = client.clone();
+            async move {
+                let result = commit(&mut client, ops).await;
+                BatchResult { range, result }
+            }
+        })
+        .buffered(options.concurrency())
+        .collect()
+        .await;
+    BulkResult { batches }
+}
+
+/// Reads the keys in as few transactions as the options allow, see [`KVClient::get_many`].
+pub(crate) async fn get<K>(
+    client: &K,
+    keys: Vec<ByteSequence>,
+    options: BulkOptions,
+) -> Result<HashMap<ByteSequence, Option<KeyValue>>, Error>
+where
+    K: KVClient + Clone,
+{
+    let mut kvs: HashMap<ByteSequence, Option<KeyValue>> = HashMap::with_capacity(keys.len());
+    let ops = keys
+        .into_iter()
+        .filter(|key| kvs.insert(key.clone(), None).is_none())
+        .map(|key| RequestOp::Get { key, options: None })
+        .collect();
+    let overhead = namespace_overhead(client) + REVISION_BYTES;
+    let mut batches = split(ops, &options, overhead).into_iter();
+    let Some((_, first)) = batches.next() else {
+        return Ok(kvs);
+    };
+
+    // Later transactions read the revision of the first one.
+    let response = commit(&mut client.clone(), first).await?;
+    let revision = response.header.map_or(0, |header| header.revision);
+    let pinned = GetOptions::builder().revision(revision).build();
+    let responses: Vec<TxnResponse> = stream::iter(batches)
+        .map(|(_, ops)| {
+            let mut client = client.clone();
+            let ops: Vec<RequestOp> = ops
+                .into_iter()
+                .map(|op| match op {
+                    RequestOp::Get { key, .. } => RequestOp::Get {
+                        key,
+                        options: Some(pinned.clone()),
+                    },
+                    op => op,
+                })
+                .collect();
+            async move { commit(&mut client, ops).await }
+        })
+        .buffered(options.concurrency())
+        .try_collect()
+        .await?;
+
+    for op in std::iter::once(response)
+        .chain(responses)
+        .flat_map(|response| response.responses)
+    {
+        if let Some(ResponseUnion::ResponseRange(range)) = op.response {
+            for kv in range.kvs {
+                let kv = KeyValue::from(kv);
+                kvs.insert(kv.key.clone(), Some(kv));
+            }
+        }
+    }
+    Ok(kvs)
+}
+
+/// Returns the bytes the namespace adds to an operation, prefixing the key and the end of
+/// the range.
+fn namespace_overhead<K: KVClient>(client: &K) -> usize {
+    client
+        .options()
+        .namespace()
+        .map_or(0, |namespace| 2 * namespace.as_bytes().len())
+}
+
+async fn commit<K: KVClient>(client: &mut K, ops: Vec<RequestOp>) -> Result<TxnResponse, Error> {
+    let mut txn = client.txn();
+    txn.then(ops)?;
+    Ok(txn.commit().await?.into_inner())
+}
+
+/// Splits the operations into batches within the limits of the options, adding `overhead`
+/// bytes to the size of every operation. A key is only used once per batch, as etcd rejects transactions writing a key twice.
+fn split(
+    ops: Vec<RequestOp>,
+    options: &BulkOptions,
+    overhead: usize,
+) -> Vec<(Range<usize>, Vec<RequestOp>)> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut start = 0;
+    let mut bytes = 0;
+    let mut keys = HashSet::new();
+
+    for (index, op) in ops.into_iter().enumerate() {
+        let key = op_key(&op).to_vec();
+        let size = op.clone().into_pb().encoded_len() + OP_FRAMING_BYTES + overhead;
+        let full = batch.len() == options.max_txn_ops()
+            || bytes + size > options.max_txn_bytes()
+            || keys.contains(&key);
+        if full && !batch.is_empty() {
+            batches.push((start..index, std::mem::take(&mut batch)));
+            start = index;
+            bytes = 0;
+            keys.clear();
+        }
+        batch.push(op);
+        bytes += size;
+        keys.insert(key);
+    }
+
+    if !batch.is_empty() {
+        let end = start + batch.len();
+        batches.push((start..end, batch));
+    }
+    batches
+}
+
+fn op_key(op: &RequestOp) -> &[u8] {
+    match op {
+        RequestOp::Put { key, .. } | RequestOp::Get { key, .. } | RequestOp::Delete { key, .. } => {
+            key.as_bytes()
+        }
+    }
+}
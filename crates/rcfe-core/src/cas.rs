@@ -0,0 +1,213 @@
+use crate::{
+    ByteSequence, KeyRange,
+    error::Error,
+    etcdserverpb::response_op::Response as ResponseUnion,
+    kv::KVClient,
+    options::{
+        put::PutOptions,
+        retry::RetryPolicy,
+        txn::{compare::Compare, op::RequestOp},
+    },
+    response::KeyValue,
+    txn::Txn,
+};
+
+/// Outcome of a conditional write, see [`KVClient::compare_and_swap`].
+/// # Examples
+/// ```rust,no_run
+/// use rcfe_core::{CasOutcome, Error, KVClient};
+/// async fn example<K: KVClient>(mut client: K) -> Result<(), Error> {
+///     match client.create("leader", "node-1").await? {
+///         CasOutcome::Applied { revision } => println!("elected at revision {revision}"),
+///         CasOutcome::Conflict { current } => println!("leader is {:?}", current.map(|kv| kv.value)),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CasOutcome {
+    /// The condition held and the write was applied.
+    /// # Arguments
+    /// * `revision` - The revision of the write, the new `mod_revision` of the key
+    Applied { revision: i64 },
+
+    /// The condition failed and nothing was written.
+    /// # Arguments
+    /// * `current` - The key-value pair at the time of the conflict, `None` if the key does not exist
+    Conflict { current: Option<KeyValue> },
+}
+
+impl CasOutcome {
+    /// Returns true if the write was applied.
+    pub fn is_applied(&self) -> bool {
+        matches!(self, CasOutcome::Applied { .. })
+    }
+
+    /// Returns the revision of the write, `None` on conflict.
+    pub fn revision(&self) -> Option<i64> {
+        match self {
+            CasOutcome::Applied { revision } => Some(*revision),
+            CasOutcome::Conflict { .. } => None,
+        }
+    }
+
+    /// Returns the current key-value pair on conflict, `None` if the write was applied or the
+    /// key does not exist.
+    pub fn current(&self) -> Option<&KeyValue> {
+        match self {
+            CasOutcome::Applied { .. } => None,
+            CasOutcome::Conflict { current } => current.as_ref(),
+        }
+    }
+}
+
+/// Outcome of an update, see [`KVClient::update`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// The new value was committed.
+    /// # Arguments
+    /// * `KeyValue` - The committed key-value pair, its `mod_revision` is the revision of the write
+    Updated(KeyValue),
+
+    /// The update function returned `None`, so nothing was written.
+    /// # Arguments
+    /// * `Option<KeyValue>` - The key-value pair the function was given, `None` if the key does not exist
+    Unchanged(Option<KeyValue>),
+}
+
+impl UpdateOutcome {
+    /// Returns true if a new value was committed.
+    pub fn is_updated(&self) -> bool {
+        matches!(self, UpdateOutcome::Updated(_))
+    }
+
+    /// Returns the key-value pair after the update, `None` if the key does not exist.
+    pub fn kv(&self) -> Option<&KeyValue> {
+        match self {
+            UpdateOutcome::Updated(kv) => Some(kv),
+            UpdateOutcome::Unchanged(kv) => kv.as_ref(),
+        }
+    }
+
+    /// Consumes the outcome, returning the key-value pair after the update.
+    pub fn into_kv(self) -> Option<KeyValue> {
+        match self {
+            UpdateOutcome::Updated(kv) => Some(kv),
+            UpdateOutcome::Unchanged(kv) => kv,
+        }
+    }
+}
+
+/// Runs the read-modify-write loop of [`KVClient::update_with_policy`].
+pub(crate) async fn update<K, F, V>(
+    client: &mut K,
+    key: ByteSequence,
+    policy: RetryPolicy,
+    mut f: F,
+) -> Result<UpdateOutcome, Error>
+where
+    K: KVClient,
+    F: FnMut(Option<&KeyValue>) -> Option<V>,
+    V: Into<ByteSequence>,
+{
+    let mut current = client
+        .get(KeyRange::Key(key.clone()))
+        .await?
+        .into_iter()
+        .next();
+    let mut attempt = 1;
+    loop {
+        let Some(value) = f(current.as_ref()).map(Into::into) else {
+            return Ok(UpdateOutcome::Unchanged(current));
+        };
+        let expected = current.as_ref().map_or(0, |kv| kv.mod_revision);
+        // Keep the key attached to its lease, a put without one would detach it.
+        let lease = current.as_ref().map_or(0, |kv| kv.lease);
+        let options = PutOptions::builder().lease(lease).build();
+        let compare = Compare::mod_eq(key.clone(), expected);
+
+        match put(client, compare, key.clone(), value.clone(), options).await? {
+            CasOutcome::Applied { revision } => {
+                return Ok(UpdateOutcome::Updated(KeyValue {
+                    key,
+                    value,
+                    create_revision: current.as_ref().map_or(revision, |kv| kv.create_revision),
+                    mod_revision: revision,
+                    version: current.as_ref().map_or(1, |kv| kv.version + 1),
+                    lease,
+                }));
+            }
+            CasOutcome::Conflict { current: latest } if attempt < policy.max_attempts() => {
+                tokio::time::sleep(policy.backoff(attempt)).await;
+                attempt += 1;
+                current = latest;
+            }
+            CasOutcome::Conflict { .. } => {
+                return Err(Error::Conflict(format!(
+                    "key {:?} changed concurrently in {attempt} attempts",
+                    String::from_utf8_lossy(key.as_bytes())
+                )));
+            }
+        }
+    }
+}
+
+/// Puts the value if the compare holds.
+pub(crate) async fn put<K: KVClient>(
+    client: &mut K,
+    compare: Compare,
+    key: ByteSequence,
+    value: ByteSequence,
+    options: PutOptions,
+) -> Result<CasOutcome, Error> {
+    let op = RequestOp::Put {
+        key: key.clone(),
+        value,
+        options: Some(options),
+    };
+    commit(client, compare, op, key).await
+}
+
+/// Deletes the key if the compare holds.
+pub(crate) async fn delete<K: KVClient>(
+    client: &mut K,
+    compare: Compare,
+    key: ByteSequence,
+) -> Result<CasOutcome, Error> {
+    let op = RequestOp::Delete {
+        key: key.clone(),
+        options: None,
+    };
+    commit(client, compare, op, key).await
+}
+
+/// Applies the operation if the compare holds, reading the key otherwise.
+/// The txn is sent once: a retry after an applied attempt whose response was lost would
+/// fail its own compare and report the write as a conflict.
+async fn commit<K: KVClient>(
+    client: &mut K,
+    compare: Compare,
+    op: RequestOp,
+    key: ByteSequence,
+) -> Result<CasOutcome, Error> {
+    let mut txn = client.txn();
+    txn.when([compare])?
+        .then([op])?
+        .otherwise([RequestOp::Get { key, options: None }])?
+        .disable_retries();
+    let response = txn.commit().await?.into_inner();
+
+    if response.succeeded {
+        let revision = response.header.map_or(0, |header| header.revision);
+        return Ok(CasOutcome::Applied { revision });
+    }
+    let current = response
+        .responses
+        .into_iter()
+        .find_map(|op| match op.response {
+            Some(ResponseUnion::ResponseRange(range)) => range.kvs.into_iter().next(),
+            _ => None,
+        })
+        .map(KeyValue::from);
+    Ok(CasOutcome::Conflict { current })
+}
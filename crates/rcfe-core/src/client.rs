@@ -1,25 +1,67 @@
-use crate::{kv::KVClient, lease::LeaseClient, options::client::ClientOptions, watch::WatchClient};
+use crate::{
+    ByteSequence,
+    endpoint::{EndpointStatus, HealthReport},
+    kv::KVClient,
+    lease::LeaseClient,
+    options::{client::ClientOptions, shutdown::ShutdownOptions},
+    watch::WatchClient,
+};
 
 /// Client trait defining the interface for a client.
 /// Implementors must provide methods to retrieve client options and a key-value client.
 #[tonic::async_trait]
 pub trait Client {
-    /// Ping the server to check connectivity.
+    /// Check that the cluster can serve requests, see [`Client::health`].
+    /// # Errors
+    /// Returns an `Error::Unhealthy` with the errors of the endpoints if none is healthy.
     async fn ping(&self) -> Result<(), crate::Error> {
-        let mut kv_client = self.get_kv_client();
-        kv_client.get("__ping__").await.map(|_| ())?;
-        Ok(())
+        let report = self.health().await?;
+        match report.is_healthy() {
+            true => Ok(()),
+            false => Err(crate::Error::Unhealthy(report.errors().join(", "))),
+        }
     }
 
+    /// Query the Maintenance `Status` of every endpoint concurrently, bounded by
+    /// [`ClientOptions::health_probe_timeout`]. Needs no permission on any key and ignores the namespace.
+    /// The endpoint statuses are updated with the result like a periodic health check.
+    async fn health(&self) -> Result<HealthReport, crate::Error>;
+
     /// Get a reference to the client options.
     fn get_options(&self) -> &ClientOptions;
 
-    /// Get the key-value client.
-    fn get_kv_client(&self) -> impl KVClient;
+    /// Get the key-value client, cheap to clone as clones share the channel.
+    fn get_kv_client(&self) -> impl KVClient + Clone;
 
     /// Get the lease client.
     fn get_lease_client(&self) -> impl LeaseClient;
 
     /// Get the watch client.
     fn get_watch_client(&self) -> impl WatchClient;
+
+    /// Derive a client scoped to a namespace nested under the namespace of this client.
+    /// The derived client shares the connections and the lifecycle of this client,
+    /// so shutting down either of them shuts down both.
+    /// # Arguments
+    /// * `namespace` - The prefix appended to the namespace of this client.
+    fn with_namespace<N>(&self, namespace: N) -> Self
+    where
+        Self: Sized,
+        N: Into<ByteSequence>;
+
+    /// Get the current health of every endpoint the client knows about.
+    fn endpoint_statuses(&self) -> Vec<EndpointStatus>;
+
+    /// Shut down the client with the default options, see [`Client::shutdown_with_options`].
+    async fn shutdown(&self) -> Result<(), crate::Error> {
+        self.shutdown_with_options(ShutdownOptions::default()).await
+    }
+
+    /// Shut down the client and every client obtained from it.
+    /// New requests fail with `Error::ClientClosed`, active watchers are cancelled and
+    /// keep-alives stop. In-flight requests are awaited until the deadline of the options,
+    /// after which `Error::Timeout` is returned. Shutting down twice has no effect.
+    /// # Arguments
+    /// * `options` - Whether to revoke the leases granted through the client and the deadline.
+    async fn shutdown_with_options(&self, options: ShutdownOptions) -> Result<(), crate::Error>;
 }
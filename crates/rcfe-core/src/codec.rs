@@ -0,0 +1,87 @@
+use crate::options::middleware::BoxError;
+
+/// Converts values of type `T` to and from the bytes stored in etcd.
+/// Used by [`TypedKv`](crate::TypedKv) to store typed values. Errors are reported as
+/// `Error::Encode` and `Error::Decode` along with the key of the value.
+/// # Examples
+/// ```rust
+/// use rcfe_core::{BoxError, Codec};
+/// struct Utf8Codec;
+/// impl Codec<String> for Utf8Codec {
+///     fn encode(&self, value: &String) -> Result<Vec<u8>, BoxError> {
+///         Ok(value.as_bytes().to_vec())
+///     }
+///
+///     fn decode(&self, bytes: &[u8]) -> Result<String, BoxError> {
+///         Ok(String::from_utf8(bytes.to_vec())?)
+///     }
+/// }
+/// assert_eq!(Utf8Codec.decode(b"value").unwrap(), "value");
+/// ```
+pub trait Codec<T>: Send + Sync {
+    /// Encodes the value to bytes.
+    fn encode(&self, value: &T) -> Result<Vec<u8>, BoxError>;
+
+    /// Decodes a value from bytes.
+    fn decode(&self, bytes: &[u8]) -> Result<T, BoxError>;
+}
+
+/// JSON codec, backed by `serde_json`.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+#[cfg(feature = "json")]
+impl<T> Codec<T> for JsonCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, BoxError> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, BoxError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// MessagePack codec, backed by `rmp-serde`.
+/// Structs are encoded as maps, so fields can be added and reordered like with JSON.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackCodec;
+
+#[cfg(feature = "msgpack")]
+impl<T> Codec<T> for MessagePackCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, BoxError> {
+        Ok(rmp_serde::to_vec_named(value)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, BoxError> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+}
+
+/// Bincode codec, backed by `bincode`.
+/// The most compact of the built-in codecs, but values only decode into the exact type they
+/// were encoded from.
+#[cfg(feature = "bincode")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "bincode")]
+impl<T> Codec<T> for BincodeCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, BoxError> {
+        Ok(bincode::serialize(value)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, BoxError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
@@ -0,0 +1,494 @@
+//! Object-safe counterparts of the client traits.
+//!
+//! The client traits have generic methods and return `impl Trait`, so they cannot be used
+//! as trait objects. Every implementation of them also implements the `Dyn*` traits
+//! below, which can be boxed to store a client in a non-generic struct or to swap in a
+//! mock for tests.
+//! # Examples
+//! ```rust
+//! use rcfe_core::{DynClient, Error};
+//! use std::sync::Arc;
+//!
+//! struct AppState {
+//!     client: Arc<dyn DynClient>,
+//! }
+//!
+//! async fn handler(state: &AppState) -> Result<(), Error> {
+//!     let mut kv_client = state.client.kv_client();
+//!     kv_client.put("key", "value").await?;
+//!     Ok(())
+//! }
+//! ```
+use crate::{
+    ByteSequence, KeyRange,
+    client::Client,
+    endpoint::{EndpointStatus, HealthReport},
+    error::Error,
+    etcdserverpb::{
+        CompactionResponse, LeaseGrantResponse, LeaseKeepAliveResponse, LeaseRevokeResponse,
+        LeaseTimeToLiveResponse, TxnResponse, WatchResponse,
+    },
+    kv::KVClient,
+    lease::{KeepAliveHandler, LeaseClient},
+    options::{
+        client::ClientOptions,
+        compact::CompactOptions,
+        delete::DeleteOptions,
+        get::GetOptions,
+        kv::KVOptions,
+        lease::{TimeToLiveOptions, grant::GrantOptions},
+        put::PutOptions,
+        shutdown::ShutdownOptions,
+        txn::{compare::Compare, op::RequestOp},
+        watch::{WatchClientOptions, WatchRequestType},
+    },
+    response::{DeleteResult, GetResult, PutResult},
+    txn::Txn,
+    watch::{WatchClient, Watcher},
+};
+use std::time::Duration;
+use tonic::{Response, Streaming, async_trait};
+
+/// Object-safe counterpart of [`Client`].
+#[async_trait]
+pub trait DynClient: Send + Sync {
+    /// Get a reference to the client options.
+    fn options(&self) -> &ClientOptions;
+
+    /// Get the key-value client.
+    fn kv_client(&self) -> Box<dyn DynKVClient + '_>;
+
+    /// Get the lease client.
+    fn lease_client(&self) -> Box<dyn DynLeaseClient + '_>;
+
+    /// Get the watch client.
+    fn watch_client(&self) -> Box<dyn DynWatchClient + '_>;
+
+    /// Derive a client scoped to a nested namespace, see [`Client::with_namespace`].
+    fn with_namespace(&self, namespace: ByteSequence) -> Box<dyn DynClient + '_>;
+
+    /// Get the current health of every endpoint the client knows about.
+    fn endpoint_statuses(&self) -> Vec<EndpointStatus>;
+
+    /// Check that the cluster can serve requests, see [`Client::ping`].
+    async fn ping(&self) -> Result<(), Error>;
+
+    /// Query the health of every endpoint, see [`Client::health`].
+    async fn health(&self) -> Result<HealthReport, Error>;
+
+    /// Shut down the client, see [`Client::shutdown_with_options`].
+    async fn shutdown_with_options(&self, options: ShutdownOptions) -> Result<(), Error>;
+}
+
+#[async_trait]
+impl<C> DynClient for C
+where
+    C: Client + Send + Sync,
+{
+    fn options(&self) -> &ClientOptions {
+        self.get_options()
+    }
+
+    fn kv_client(&self) -> Box<dyn DynKVClient + '_> {
+        Box::new(self.get_kv_client())
+    }
+
+    fn lease_client(&self) -> Box<dyn DynLeaseClient + '_> {
+        Box::new(self.get_lease_client())
+    }
+
+    fn watch_client(&self) -> Box<dyn DynWatchClient + '_> {
+        Box::new(self.get_watch_client())
+    }
+
+    fn with_namespace(&self, namespace: ByteSequence) -> Box<dyn DynClient + '_> {
+        Box::new(Client::with_namespace(self, namespace))
+    }
+
+    fn endpoint_statuses(&self) -> Vec<EndpointStatus> {
+        Client::endpoint_statuses(self)
+    }
+
+    async fn ping(&self) -> Result<(), Error> {
+        Client::ping(self).await
+    }
+
+    async fn health(&self) -> Result<HealthReport, Error> {
+        Client::health(self).await
+    }
+
+    async fn shutdown_with_options(&self, options: ShutdownOptions) -> Result<(), Error> {
+        Client::shutdown_with_options(self, options).await
+    }
+}
+
+impl dyn DynClient + '_ {
+    /// Shut down the client with the default options, see [`Client::shutdown`].
+    pub async fn shutdown(&self) -> Result<(), Error> {
+        self.shutdown_with_options(ShutdownOptions::default()).await
+    }
+}
+
+/// Object-safe counterpart of [`KVClient`].
+#[async_trait]
+pub trait DynKVClient: Send + Sync {
+    /// Compacts the key-value store up to the specified revision with the given options.
+    async fn compact_with_options(
+        &mut self,
+        revision: i64,
+        options: CompactOptions,
+    ) -> Result<Response<CompactionResponse>, Error>;
+
+    /// Creates a new transaction associated with this KV client.
+    fn txn(&mut self) -> Box<dyn DynTxn + '_>;
+
+    /// Deletes the keys of the range from the store with the specified options.
+    async fn delete_with_options(
+        &mut self,
+        key: KeyRange,
+        options: DeleteOptions,
+    ) -> Result<DeleteResult, Error>;
+
+    /// Puts a key-value pair into the store with the specified options.
+    async fn put_with_options(
+        &mut self,
+        key: ByteSequence,
+        value: ByteSequence,
+        options: PutOptions,
+    ) -> Result<PutResult, Error>;
+
+    /// Performs a range query with the specified key or range and options.
+    async fn get_with_options(
+        &mut self,
+        key: KeyRange,
+        options: GetOptions,
+    ) -> Result<GetResult, Error>;
+
+    /// Retrieves the KV options associated with this client.
+    fn options(&self) -> &KVOptions;
+}
+
+#[async_trait]
+impl<T> DynKVClient for T
+where
+    T: KVClient,
+{
+    async fn compact_with_options(
+        &mut self,
+        revision: i64,
+        options: CompactOptions,
+    ) -> Result<Response<CompactionResponse>, Error> {
+        KVClient::compact_with_options(self, revision, options).await
+    }
+
+    fn txn(&mut self) -> Box<dyn DynTxn + '_> {
+        Box::new(KVClient::txn(self))
+    }
+
+    async fn delete_with_options(
+        &mut self,
+        key: KeyRange,
+        options: DeleteOptions,
+    ) -> Result<DeleteResult, Error> {
+        KVClient::delete_with_options(self, key, options).await
+    }
+
+    async fn put_with_options(
+        &mut self,
+        key: ByteSequence,
+        value: ByteSequence,
+        options: PutOptions,
+    ) -> Result<PutResult, Error> {
+        KVClient::put_with_options(self, key, value, options).await
+    }
+
+    async fn get_with_options(
+        &mut self,
+        key: KeyRange,
+        options: GetOptions,
+    ) -> Result<GetResult, Error> {
+        KVClient::get_with_options(self, key, options).await
+    }
+
+    fn options(&self) -> &KVOptions {
+        KVClient::options(self)
+    }
+}
+
+impl dyn DynKVClient + '_ {
+    /// Compacts the key-value store up to the specified revision.
+    pub async fn compact(&mut self, revision: i64) -> Result<Response<CompactionResponse>, Error> {
+        self.compact_with_options(revision, CompactOptions::default())
+            .await
+    }
+
+    /// Deletes the keys of the range from the store.
+    pub async fn delete<K>(&mut self, key: K) -> Result<DeleteResult, Error>
+    where
+        K: Into<KeyRange>,
+    {
+        self.delete_with_options(key.into(), DeleteOptions::default())
+            .await
+    }
+
+    /// Puts a key-value pair into the store.
+    pub async fn put<K, V>(&mut self, key: K, value: V) -> Result<PutResult, Error>
+    where
+        K: Into<ByteSequence>,
+        V: Into<ByteSequence>,
+    {
+        self.put_with_options(key.into(), value.into(), PutOptions::default())
+            .await
+    }
+
+    /// Performs a range query with the specified key or range.
+    pub async fn get<K>(&mut self, key: K) -> Result<GetResult, Error>
+    where
+        K: Into<KeyRange>,
+    {
+        self.get_with_options(key.into(), GetOptions::default())
+            .await
+    }
+}
+
+/// Object-safe counterpart of [`Txn`].
+/// Conditions and operations are passed as vectors, each call returns the transaction
+/// so that calls can be chained like on [`Txn`].
+#[async_trait]
+pub trait DynTxn: Send {
+    /// Adds comparison conditions to the transaction.
+    fn when(&mut self, compares: Vec<Compare>) -> Result<&mut dyn DynTxn, Error>;
+
+    /// Adds operations to be executed if the comparison conditions are met.
+    fn then(&mut self, ops: Vec<RequestOp>) -> Result<&mut dyn DynTxn, Error>;
+
+    /// Adds operations to be executed if the comparison conditions are not met.
+    fn otherwise(&mut self, ops: Vec<RequestOp>) -> Result<&mut dyn DynTxn, Error>;
+
+    /// Sends the transaction at most once.
+    fn disable_retries(&mut self) -> &mut dyn DynTxn;
+
+    /// Commits the transaction and executes the operations.
+    async fn commit(&mut self) -> Result<Response<TxnResponse>, Error>;
+}
+
+#[async_trait]
+impl<T> DynTxn for T
+where
+    T: Txn,
+{
+    fn when(&mut self, compares: Vec<Compare>) -> Result<&mut dyn DynTxn, Error> {
+        Txn::when(self, compares)?;
+        Ok(self)
+    }
+
+    fn then(&mut self, ops: Vec<RequestOp>) -> Result<&mut dyn DynTxn, Error> {
+        Txn::then(self, ops)?;
+        Ok(self)
+    }
+
+    fn otherwise(&mut self, ops: Vec<RequestOp>) -> Result<&mut dyn DynTxn, Error> {
+        Txn::otherwise(self, ops)?;
+        Ok(self)
+    }
+
+    fn disable_retries(&mut self) -> &mut dyn DynTxn {
+        Txn::disable_retries(self);
+        self
+    }
+
+    async fn commit(&mut self) -> Result<Response<TxnResponse>, Error> {
+        Txn::commit(self).await
+    }
+}
+
+/// Object-safe counterpart of [`LeaseClient`].
+#[async_trait]
+pub trait DynLeaseClient: Send + Sync {
+    /// Grants a lease with the specified time-to-live (TTL) and options.
+    async fn grant_with_options(
+        &mut self,
+        ttl: Duration,
+        options: GrantOptions,
+    ) -> Result<Response<LeaseGrantResponse>, Error>;
+
+    /// Revokes a lease with the specified lease ID.
+    async fn revoke(&self, lease_id: i64) -> Result<Response<LeaseRevokeResponse>, Error>;
+
+    /// Keeps the lease alive for the specified lease ID.
+    async fn keep_alive<'a>(
+        &'a mut self,
+        lease_id: i64,
+    ) -> Result<Box<dyn DynKeepAliveHandler + 'a>, Error>;
+
+    /// Retrieves the time-to-live (TTL) information for the specified lease ID with options.
+    async fn time_to_live_with_options(
+        &mut self,
+        lease_id: i64,
+        options: TimeToLiveOptions,
+    ) -> Result<Response<LeaseTimeToLiveResponse>, Error>;
+}
+
+#[async_trait]
+impl<T> DynLeaseClient for T
+where
+    T: LeaseClient,
+{
+    async fn grant_with_options(
+        &mut self,
+        ttl: Duration,
+        options: GrantOptions,
+    ) -> Result<Response<LeaseGrantResponse>, Error> {
+        LeaseClient::grant_with_options(self, ttl, options).await
+    }
+
+    async fn revoke(&self, lease_id: i64) -> Result<Response<LeaseRevokeResponse>, Error> {
+        LeaseClient::revoke(self, lease_id).await
+    }
+
+    async fn keep_alive<'a>(
+        &'a mut self,
+        lease_id: i64,
+    ) -> Result<Box<dyn DynKeepAliveHandler + 'a>, Error> {
+        let handler = LeaseClient::keep_alive(self, lease_id).await?;
+        Ok(Box::new(handler))
+    }
+
+    async fn time_to_live_with_options(
+        &mut self,
+        lease_id: i64,
+        options: TimeToLiveOptions,
+    ) -> Result<Response<LeaseTimeToLiveResponse>, Error> {
+        LeaseClient::time_to_live_with_options(self, lease_id, options).await
+    }
+}
+
+impl dyn DynLeaseClient + '_ {
+    /// Grants a lease with the specified time-to-live (TTL).
+    pub async fn grant(&mut self, ttl: Duration) -> Result<Response<LeaseGrantResponse>, Error> {
+        self.grant_with_options(ttl, GrantOptions::default()).await
+    }
+
+    /// Retrieves the time-to-live (TTL) information for the specified lease ID.
+    pub async fn time_to_live(
+        &mut self,
+        lease_id: i64,
+    ) -> Result<Response<LeaseTimeToLiveResponse>, Error> {
+        self.time_to_live_with_options(lease_id, TimeToLiveOptions::default())
+            .await
+    }
+}
+
+/// Object-safe counterpart of [`KeepAliveHandler`].
+#[async_trait]
+pub trait DynKeepAliveHandler: Send {
+    /// Retrieves the lease ID associated with the keep-alive handler.
+    fn lease_id(&self) -> i64;
+
+    /// Converts the keep-alive handler into a response containing the streaming lease keep-alive responses.
+    fn into_response(self: Box<Self>) -> Response<Streaming<LeaseKeepAliveResponse>>;
+
+    /// Sends a keep-alive request to renew the lease.
+    async fn keep_alive(&mut self) -> Result<(), Error>;
+}
+
+#[async_trait]
+impl<T> DynKeepAliveHandler for T
+where
+    T: KeepAliveHandler,
+{
+    fn lease_id(&self) -> i64 {
+        KeepAliveHandler::lease_id(self)
+    }
+
+    fn into_response(self: Box<Self>) -> Response<Streaming<LeaseKeepAliveResponse>> {
+        KeepAliveHandler::into_response(*self)
+    }
+
+    async fn keep_alive(&mut self) -> Result<(), Error> {
+        KeepAliveHandler::keep_alive(self).await
+    }
+}
+
+/// Object-safe counterpart of [`WatchClient`].
+#[async_trait]
+pub trait DynWatchClient: Send + Sync {
+    /// Watches a key or range of keys for changes.
+    async fn watch<'a>(
+        &'a mut self,
+        request: WatchRequestType,
+    ) -> Result<Box<dyn DynWatcher + 'a>, Error>;
+
+    /// Retrieves the options associated with the WatchClient.
+    fn options(&self) -> &WatchClientOptions;
+}
+
+#[async_trait]
+impl<T> DynWatchClient for T
+where
+    T: WatchClient,
+{
+    async fn watch<'a>(
+        &'a mut self,
+        request: WatchRequestType,
+    ) -> Result<Box<dyn DynWatcher + 'a>, Error> {
+        let watcher = WatchClient::watch(self, request).await?;
+        Ok(Box::new(watcher))
+    }
+
+    fn options(&self) -> &WatchClientOptions {
+        WatchClient::options(self)
+    }
+}
+
+/// Object-safe counterpart of [`Watcher`].
+#[async_trait]
+pub trait DynWatcher: Send {
+    /// Retrieves the ID of the watcher.
+    fn id(&self) -> i64;
+
+    /// Starts watching for changes.
+    async fn watch(&mut self) -> Result<(), Error>;
+
+    /// Converts the watcher into a response stream of `WatchResponse`.
+    fn into_response(self: Box<Self>) -> Response<Streaming<WatchResponse>>;
+
+    /// Retrieves the original watch request type.
+    fn request(&self) -> &WatchRequestType;
+
+    /// Progresses the watcher to fetch the next set of events.
+    async fn progress(&mut self) -> Result<(), Error>;
+
+    /// Cancels the watcher.
+    async fn cancel(&mut self) -> Result<(), Error>;
+}
+
+#[async_trait]
+impl<T> DynWatcher for T
+where
+    T: Watcher,
+{
+    fn id(&self) -> i64 {
+        Watcher::id(self)
+    }
+
+    async fn watch(&mut self) -> Result<(), Error> {
+        Watcher::watch(self).await
+    }
+
+    fn into_response(self: Box<Self>) -> Response<Streaming<WatchResponse>> {
+        Watcher::into_response(*self)
+    }
+
+    fn request(&self) -> &WatchRequestType {
+        Watcher::request(self)
+    }
+
+    async fn progress(&mut self) -> Result<(), Error> {
+        Watcher::progress(self).await
+    }
+
+    async fn cancel(&mut self) -> Result<(), Error> {
+        Watcher::cancel(self).await
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::{Duration, Instant};
+
+/// Health of a single endpoint as seen by the client.
+/// # Fields
+/// * `endpoint` - The endpoint URI
+/// * `healthy` - Whether the last health check succeeded
+/// * `in_rotation` - Whether requests are currently routed to the endpoint
+/// * `last_checked` - When the endpoint was last health checked, `None` before the first check
+/// * `last_error` - Why the last health check failed, if it did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    pub endpoint: String,
+    pub healthy: bool,
+    pub in_rotation: bool,
+    pub last_checked: Option<Instant>,
+    pub last_error: Option<String>,
+}
+
+impl EndpointStatus {
+    /// Creates the status of an endpoint that was not checked yet.
+    /// Endpoints are assumed healthy until a health check fails.
+    pub fn new<S: Into<String>>(endpoint: S) -> Self {
+        EndpointStatus {
+            endpoint: endpoint.into(),
+            healthy: true,
+            in_rotation: true,
+            last_checked: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Health of a single endpoint as reported by the Maintenance `Status` RPC.
+/// The member fields are zero or empty if the endpoint could not be reached.
+/// # Fields
+/// * `endpoint` - The endpoint URI
+/// * `member_id` - The ID of the member serving the endpoint
+/// * `leader` - The member ID of the leader, `0` if the member has no leader
+/// * `raft_term` - The current raft term of the member
+/// * `raft_index` - The current raft committed index of the member
+/// * `raft_applied_index` - The current raft applied index of the member
+/// * `db_size` - The physically allocated size of the backend database in bytes
+/// * `db_size_in_use` - The logically used size of the backend database in bytes
+/// * `version` - The etcd version of the member
+/// * `is_learner` - Whether the member is a raft learner
+/// * `alarms` - The alarms raised on the member, e.g. `NOSPACE`
+/// * `latency` - The round trip time of the `Status` request
+/// * `error` - Why the endpoint is unhealthy, if it is
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub endpoint: String,
+    pub member_id: u64,
+    pub leader: u64,
+    pub raft_term: u64,
+    pub raft_index: u64,
+    pub raft_applied_index: u64,
+    pub db_size: i64,
+    pub db_size_in_use: i64,
+    pub version: String,
+    pub is_learner: bool,
+    pub alarms: Vec<String>,
+    pub latency: Duration,
+    pub error: Option<String>,
+}
+
+impl EndpointHealth {
+    /// Returns true if the endpoint answered and its member has a leader.
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Health of the cluster as seen through every endpoint of a client.
+/// # Fields
+/// * `endpoints` - The health of each endpoint, in the order of the client endpoints
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealthReport {
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+impl HealthReport {
+    /// Returns true if at least one endpoint is healthy, so that requests can be served.
+    pub fn is_healthy(&self) -> bool {
+        self.endpoints.iter().any(EndpointHealth::is_healthy)
+    }
+
+    /// Returns the leader reported by the healthy endpoints in the highest raft term.
+    pub fn leader(&self) -> Option<u64> {
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.is_healthy())
+            .max_by_key(|endpoint| endpoint.raft_term)
+            .map(|endpoint| endpoint.leader)
+    }
+
+    /// Returns the errors of the unhealthy endpoints, prefixed with their URI.
+    pub fn errors(&self) -> Vec<String> {
+        self.endpoints
+            .iter()
+            .filter_map(|endpoint| {
+                let error = endpoint.error.as_ref()?;
+                Some(format!("{}: {}", endpoint.endpoint, error))
+            })
+            .collect()
+    }
+}
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use thiserror::Error;
 use tonic::codegen::http::uri::InvalidUri;
 
@@ -6,6 +7,15 @@ use tonic::codegen::http::uri::InvalidUri;
 /// * `InvalidUri` - Indicates that the provided URI is invalid
 /// * `TonicStatus` - Wraps errors from tonic status
 /// * `IllegalArgument` - Indicates that an illegal argument was provided
+/// * `Transport` - Wraps errors from the tonic transport, e.g. an invalid TLS configuration
+/// * `Io` - Wraps I/O errors, e.g. when reading certificate files
+/// * `Timeout` - Indicates that a request did not complete within its deadline
+/// * `NoLeader` - Indicates that the member serving a leader-requiring request has no leader
+/// * `ClientClosed` - Indicates that the client was shut down
+/// * `Unhealthy` - Indicates that no endpoint of the cluster is healthy
+/// * `Encode` - Indicates that a value could not be encoded by the codec of a typed client
+/// * `Decode` - Indicates that a stored value could not be decoded by the codec of a typed client
+/// * `Conflict` - Indicates that a read-modify-write kept conflicting with concurrent writes
 #[derive(Error, Debug)]
 pub enum Error {
     /// URI is invalid
@@ -14,7 +24,65 @@ pub enum Error {
 
     /// Tonic status error
     #[error("Tonic status error: {0}")]
-    TonicStatus(#[from] tonic::Status),
+    TonicStatus(#[source] tonic::Status),
+
+    /// Tonic transport error
+    #[error("Transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Timeout error
+    /// Indicates that a request did not complete within its deadline
+    /// # Arguments
+    /// * `Duration` - The deadline that was exceeded
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// No leader error
+    /// Indicates that the member is partitioned from the leader, so a request or watch
+    /// requiring a leader failed. Retrying against another member may succeed.
+    /// # Arguments
+    /// * `tonic::Status` - The status returned by the member
+    #[error("Member has no leader: {0}")]
+    NoLeader(tonic::Status),
+
+    /// Client closed error
+    /// Indicates that the request was made after the client was shut down
+    #[error("Client is shut down")]
+    ClientClosed,
+
+    /// Unhealthy error
+    /// Indicates that no endpoint answered the health check with a leader
+    /// # Arguments
+    /// * `String` - The errors of the endpoints
+    #[error("Cluster is unhealthy: {0}")]
+    Unhealthy(String),
+
+    /// Encode error
+    /// Indicates that a value could not be encoded by the codec of a typed client
+    /// # Arguments
+    /// * `String` - Description of the error
+    #[error("Encode error: {0}")]
+    Encode(String),
+
+    /// Decode error
+    /// Indicates that a stored value could not be decoded by the codec of a typed client,
+    /// e.g. because it was written with another codec or an incompatible type
+    /// # Arguments
+    /// * `String` - Description of the error, including the key of the value
+    #[error("Decode error: {0}")]
+    Decode(String),
+
+    /// Conflict error
+    /// Indicates that the key kept changing between the read and the write of an update,
+    /// until the attempts of its retry policy ran out
+    /// # Arguments
+    /// * `String` - Description of the error, including the key
+    #[error("Conflict: {0}")]
+    Conflict(String),
 
     /// Illegal argument error
     #[error("Illegal argument: {0}")]
@@ -38,6 +106,13 @@ pub enum Error {
     #[error("Lease keep-alive error: {0}")]
     KeepAliveError(String),
 
+    /// Authentication error
+    /// Indicates that authenticating with the cluster failed
+    /// # Arguments
+    /// * `String` - Description of the error
+    #[error("Authentication error: {0}")]
+    AuthError(String),
+
     /// Watch error
     /// Indicates an error occurred during watch operation
     /// # Arguments
@@ -49,3 +124,16 @@ pub enum Error {
     #[error("Other error: {0}")]
     Other(String),
 }
+
+/// Message of the status etcd returns when a member has no leader.
+const NO_LEADER_MESSAGE: &str = "etcdserver: no leader";
+
+impl From<tonic::Status> for Error {
+    /// Converts a status, mapping the `no leader` status of etcd to `Error::NoLeader`.
+    fn from(status: tonic::Status) -> Self {
+        match status.code() == tonic::Code::Unavailable && status.message() == NO_LEADER_MESSAGE {
+            true => Error::NoLeader(status),
+            false => Error::TonicStatus(status),
+        }
+    }
+}
@@ -1,10 +1,26 @@
 use crate::{
-    ByteSequence, NamespaceBuilder, Namespaceable,
+    ByteSequence, KeyRange,
+    bulk::{self, BulkResult},
+    cas::{self, CasOutcome, UpdateOutcome},
+    codec::Codec,
     error::Error,
-    etcdserverpb::{CompactionResponse, DeleteRangeResponse, PutResponse, RangeResponse},
-    options::{delete::DeleteOptions, get::GetOptions, kv::KVOptions, put::PutOptions},
+    etcdserverpb::CompactionResponse,
+    options::{
+        bulk::BulkOptions,
+        delete::DeleteOptions,
+        get::GetOptions,
+        kv::KVOptions,
+        put::PutOptions,
+        retry::RetryPolicy,
+        txn::{compare::Compare, op::RequestOp},
+    },
+    response::{DeleteResult, GetResult, KeyValue, PutResult},
+    scan,
     txn::Txn,
+    typed::TypedKv,
 };
+use futures_util::stream::Stream;
+use std::{collections::HashMap, future::Future};
 use tonic::Response;
 
 /// KVClient defines the interface for interacting with the key-value store.
@@ -27,21 +43,26 @@ pub trait KVClient: Send + Sync {
     /// Creates a new transaction associated with this KV client.
     fn txn(&mut self) -> impl Txn;
 
-    /// Deletes a key-value pair from the store.
-    async fn delete(&mut self, key: ByteSequence) -> Result<Response<DeleteRangeResponse>, Error> {
+    /// Deletes the keys of the range from the store.
+    async fn delete<K>(&mut self, key: K) -> Result<DeleteResult, Error>
+    where
+        K: Into<KeyRange> + Send,
+    {
         self.delete_with_options(key, DeleteOptions::default())
             .await
     }
 
-    /// Deletes a key-value pair from the store with the specified options.
-    async fn delete_with_options(
+    /// Deletes the keys of the range from the store with the specified options.
+    async fn delete_with_options<K>(
         &mut self,
-        key: ByteSequence,
+        key: K,
         options: DeleteOptions,
-    ) -> Result<Response<DeleteRangeResponse>, Error>;
+    ) -> Result<DeleteResult, Error>
+    where
+        K: Into<KeyRange> + Send;
 
     /// Puts a key-value pair into the store.
-    async fn put<K, V>(&mut self, key: K, value: V) -> Result<Response<PutResponse>, Error>
+    async fn put<K, V>(&mut self, key: K, value: V) -> Result<PutResult, Error>
     where
         K: Into<ByteSequence> + Send,
         V: Into<ByteSequence> + Send,
@@ -56,50 +77,322 @@ pub trait KVClient: Send + Sync {
         key: K,
         value: V,
         options: PutOptions,
-    ) -> Result<Response<PutResponse>, Error>
+    ) -> Result<PutResult, Error>
     where
         K: Into<ByteSequence> + Send,
         V: Into<ByteSequence> + Send;
 
-    /// Performs a range query with the specified key.
-    async fn get<K>(&mut self, key: K) -> Result<Response<RangeResponse>, Error>
+    /// Creates a key-value pair if the key does not exist.
+    /// On conflict, the outcome holds the existing key-value pair.
+    async fn create<K, V>(&mut self, key: K, value: V) -> Result<CasOutcome, Error>
+    where
+        Self: Sized,
+        K: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        self.create_with_options(key, value, PutOptions::default())
+            .await
+    }
+
+    /// Creates a key-value pair if the key does not exist, with the specified options,
+    /// e.g. attaching a lease.
+    async fn create_with_options<K, V>(
+        &mut self,
+        key: K,
+        value: V,
+        options: PutOptions,
+    ) -> Result<CasOutcome, Error>
+    where
+        Self: Sized,
+        K: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        let key = key.into();
+        let compare = Compare::create_eq(key.clone(), 0);
+        cas::put(self, compare, key, value.into(), options).await
+    }
+
+    /// Puts the value if the key was last modified at the expected revision, typically the
+    /// `mod_revision` of a previous read. An expected revision of `0` requires the key to
+    /// not exist. On conflict, the outcome holds the current key-value pair.
+    async fn compare_and_swap<K, V>(
+        &mut self,
+        key: K,
+        expected_mod_revision: i64,
+        value: V,
+    ) -> Result<CasOutcome, Error>
+    where
+        Self: Sized,
+        K: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        let options = PutOptions::default();
+        self.compare_and_swap_with_options(key, expected_mod_revision, value, options)
+            .await
+    }
+
+    /// Puts the value if the key was last modified at the expected revision, with the
+    /// specified options.
+    async fn compare_and_swap_with_options<K, V>(
+        &mut self,
+        key: K,
+        expected_mod_revision: i64,
+        value: V,
+        options: PutOptions,
+    ) -> Result<CasOutcome, Error>
+    where
+        Self: Sized,
+        K: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        let key = key.into();
+        let compare = Compare::mod_eq(key.clone(), expected_mod_revision);
+        cas::put(self, compare, key, value.into(), options).await
+    }
+
+    /// Deletes the key if it was last modified at the expected revision.
+    /// On conflict, the outcome holds the current key-value pair.
+    async fn compare_and_delete<K>(
+        &mut self,
+        key: K,
+        expected_mod_revision: i64,
+    ) -> Result<CasOutcome, Error>
+    where
+        Self: Sized,
+        K: Into<ByteSequence> + Send,
+    {
+        let key = key.into();
+        let compare = Compare::mod_eq(key.clone(), expected_mod_revision);
+        cas::delete(self, compare, key).await
+    }
+
+    /// Updates the value of a key with a read-modify-write loop.
+    /// The function is given the current key-value pair, `None` if the key does not exist, and
+    /// returns the new value, or `None` to leave the key unchanged. The new value is only put if
+    /// the key was not modified in the meantime; otherwise the function is called again with
+    /// the latest key-value pair, as retried by the default [`RetryPolicy`].
+    /// The lease of the key, if any, is kept.
+    /// # Errors
+    /// Returns an `Error::Conflict` if the key changed concurrently on every attempt.
+    /// A write that fails is returned as is and not retried, as it may have been applied.
+    /// # Examples
+    /// ```rust,no_run
+    /// use rcfe_core::{Error, KVClient};
+    /// async fn example<K: KVClient>(mut client: K) -> Result<(), Error> {
+    ///     let outcome = client
+    ///         .update("counter", |current| {
+    ///             let count: u64 = current
+    ///                 .and_then(|kv| std::str::from_utf8(kv.value.as_bytes()).ok()?.parse().ok())
+    ///                 .unwrap_or(0);
+    ///             Some((count + 1).to_string())
+    ///         })
+    ///         .await?;
+    ///     println!("{:?}", outcome.kv().map(|kv| kv.mod_revision));
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn update<K, F, V>(&mut self, key: K, f: F) -> Result<UpdateOutcome, Error>
     where
+        Self: Sized,
         K: Into<ByteSequence> + Send,
+        F: FnMut(Option<&KeyValue>) -> Option<V> + Send,
+        V: Into<ByteSequence>,
     {
-        self.get_with_options(
-            key.into(),
-            GetOptions::builder()
-                .namespace(self.options().namespace())
-                .build(),
-        )
-        .await
+        self.update_with_policy(key, RetryPolicy::default(), f)
+            .await
+    }
+
+    /// Updates the value of a key like [`KVClient::update`], retrying conflicts with the
+    /// attempts and backoff of the policy. Its retryable codes do not apply, failed reads
+    /// are retried by the policy of the client.
+    async fn update_with_policy<K, F, V>(
+        &mut self,
+        key: K,
+        policy: RetryPolicy,
+        f: F,
+    ) -> Result<UpdateOutcome, Error>
+    where
+        Self: Sized,
+        K: Into<ByteSequence> + Send,
+        F: FnMut(Option<&KeyValue>) -> Option<V> + Send,
+        V: Into<ByteSequence>,
+    {
+        cas::update(self, key.into(), policy, f).await
+    }
+
+    /// Puts many key-value pairs, batching them into transactions.
+    /// Batches respect the operation and size limits of the options, and up to `concurrency`
+    /// of them are in flight at once. Each batch is applied atomically, but batches may be
+    /// applied in any order, and a failed batch does not stop the others: the result reports
+    /// the outcome of every batch.
+    fn put_many<I, K, V>(
+        &mut self,
+        kvs: I,
+        options: BulkOptions,
+    ) -> impl Future<Output = BulkResult> + Send + '_
+    where
+        Self: Sized + Clone,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<ByteSequence>,
+        V: Into<ByteSequence>,
+    {
+        let ops = kvs
+            .into_iter()
+            .map(|(key, value)| RequestOp::Put {
+                key: key.into(),
+                value: value.into(),
+                options: None,
+            })
+            .collect();
+        bulk::run(self, ops, options)
+    }
+
+    /// Deletes many keys, batching them into transactions like [`KVClient::put_many`].
+    fn delete_many<I, K>(
+        &mut self,
+        keys: I,
+        options: BulkOptions,
+    ) -> impl Future<Output = BulkResult> + Send + '_
+    where
+        Self: Sized + Clone,
+        I: IntoIterator<Item = K>,
+        K: Into<ByteSequence>,
+    {
+        let ops = keys
+            .into_iter()
+            .map(|key| RequestOp::Delete {
+                key: key.into(),
+                options: None,
+            })
+            .collect();
+        bulk::run(self, ops, options)
+    }
+
+    /// Performs a range query with the specified key or range.
+    async fn get<K>(&mut self, key: K) -> Result<GetResult, Error>
+    where
+        K: Into<KeyRange> + Send,
+    {
+        self.get_with_options(key, GetOptions::default())
+            .await
+    }
+
+    /// Retrieves many discrete keys, mapping every key to its key-value pair or `None` if it
+    /// does not exist. The keys are read in a single transaction, split only when exceeding
+    /// the default limits of [`BulkOptions`], and every transaction reads the same revision.
+    /// # Examples
+    /// ```rust,no_run
+    /// use rcfe_core::{ByteSequence, Error, KVClient};
+    /// async fn example<K: KVClient + Clone>(mut client: K) -> Result<(), Error> {
+    ///     let kvs = client.get_many(["config/a", "config/b"]).await?;
+    ///     if let Some(kv) = &kvs[&ByteSequence::from("config/a")] {
+    ///         println!("config/a = {:?}", kv.value);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn get_many<I, K>(
+        &mut self,
+        keys: I,
+    ) -> impl Future<Output = Result<HashMap<ByteSequence, Option<KeyValue>>, Error>> + Send + '_
+    where
+        Self: Sized + Clone,
+        I: IntoIterator<Item = K>,
+        K: Into<ByteSequence>,
+    {
+        self.get_many_with_options(keys, BulkOptions::default())
+    }
+
+    /// Retrieves many discrete keys like [`KVClient::get_many`], within the limits of the
+    /// options. Transactions after the first one run with up to `concurrency` in flight.
+    fn get_many_with_options<I, K>(
+        &mut self,
+        keys: I,
+        options: BulkOptions,
+    ) -> impl Future<Output = Result<HashMap<ByteSequence, Option<KeyValue>>, Error>> + Send + '_
+    where
+        Self: Sized + Clone,
+        I: IntoIterator<Item = K>,
+        K: Into<ByteSequence>,
+    {
+        let keys = keys.into_iter().map(Into::into).collect();
+        bulk::get(self, keys, options)
     }
 
     /// Performs a range query to retrieve all key-value pairs in the store.
     async fn get_all(
         &mut self,
         options: Option<GetOptions>,
-    ) -> Result<Response<RangeResponse>, Error> {
-        let options = if let Some(mut options) = options {
-            options.end_key = Some(ByteSequence::from("\0"));
-            options
-        } else {
-            GetOptions::builder()
-                .end_key(ByteSequence::from("\0"))
-                .build()
-        };
-        self.get_with_options(ByteSequence::from("\0"), options)
+    ) -> Result<GetResult, Error> {
+        self.get_with_options(KeyRange::All, options.unwrap_or_else(GetOptions::default))
             .await
     }
 
-    /// Performs a range query with the specified key and options.
+    /// Performs a range query with the specified key or range and options.
     async fn get_with_options<K>(
         &mut self,
         key: K,
         options: GetOptions,
-    ) -> Result<Response<RangeResponse>, Error>
+    ) -> Result<GetResult, Error>
+    where
+        K: Into<KeyRange> + Send;
+
+    /// Scans the range page by page, returning a stream of its key-value pairs.
+    /// Each page requests at most `page_size` keys, starting after the last key of the previous
+    /// page. Every page reads the revision of the first one, so the scan is a consistent snapshot
+    /// even while the range is modified.
+    /// # Examples
+    /// ```rust,no_run
+    /// use futures_util::TryStreamExt;
+    /// use rcfe_core::{Error, KVClient, KeyRange};
+    /// async fn example<K: KVClient>(mut client: K) -> Result<(), Error> {
+    ///     let keys: Vec<_> = client
+    ///         .scan(KeyRange::prefix("services/"), 100)
+    ///         .map_ok(|kv| kv.key)
+    ///         .try_collect()
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    fn scan<K>(
+        &mut self,
+        range: K,
+        page_size: i64,
+    ) -> impl Stream<Item = Result<KeyValue, Error>> + Send + '_
+    where
+        Self: Sized,
+        K: Into<KeyRange>,
+    {
+        self.scan_with_options(range, page_size, GetOptions::default())
+    }
+
+    /// Scans the range page by page with the specified options.
+    /// A `revision` pins the scan to that revision, a `limit` caps the total number of keys, and
+    /// a descending sort order scans the range backwards. `keys_only` and the revision filters
+    /// apply to every page.
+    /// The stream fails with `Error::IllegalArgument` if `page_size` is not positive, the options
+    /// sort by anything but the key, or are `count_only`.
+    fn scan_with_options<K>(
+        &mut self,
+        range: K,
+        page_size: i64,
+        options: GetOptions,
+    ) -> impl Stream<Item = Result<KeyValue, Error>> + Send + '_
     where
-        K: Into<ByteSequence> + Send;
+        Self: Sized,
+        K: Into<KeyRange>,
+    {
+        scan::scan(self, range.into(), page_size, options)
+    }
+
+    /// Wraps the client into a [`TypedKv`] storing values of type `T` encoded by the codec.
+    fn typed<T, C>(self, codec: C) -> TypedKv<T, C, Self>
+    where
+        Self: Sized,
+        C: Codec<T>,
+    {
+        TypedKv::new(self, codec)
+    }
 
     /// Retrieves the KV options associated with this client.
     /// # Returns
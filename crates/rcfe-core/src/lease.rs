@@ -10,7 +10,7 @@ use tonic::{Response, Streaming, async_trait};
 
 /// Handler for managing lease keep-alive responses.
 #[async_trait]
-pub trait KeepAliveHandler {
+pub trait KeepAliveHandler: Send {
     /// Retrieves the lease ID associated with the keep-alive handler.
     fn lease_id(&self) -> i64;
 
@@ -22,7 +22,7 @@ pub trait KeepAliveHandler {
 }
 
 #[async_trait]
-pub trait LeaseClient {
+pub trait LeaseClient: Send + Sync {
     /// Grants a lease with the specified time-to-live (TTL).
     async fn grant(&mut self, ttl: Duration) -> Result<Response<LeaseGrantResponse>, Error> {
         self.grant_with_options(ttl, GrantOptions::default()).await
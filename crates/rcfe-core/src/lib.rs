@@ -1,9 +1,19 @@
+pub(crate) mod bulk;
+pub(crate) mod cas;
 pub(crate) mod client;
+pub(crate) mod codec;
+pub(crate) mod dynamic;
+pub(crate) mod endpoint;
 pub(crate) mod error;
 pub(crate) mod factory;
 pub(crate) mod kv;
+pub(crate) mod namespace;
 pub(crate) mod options;
+pub(crate) mod range;
+pub(crate) mod response;
+pub(crate) mod scan;
 pub(crate) mod txn;
+pub(crate) mod typed;
 pub(crate) mod lease;
 pub(crate) mod watch;
 pub(crate) mod prelude;
@@ -43,7 +53,7 @@ const INCREMENT_BYTE: u8 = 0x01; // Increment by 1
 /// let next_seq = seq.next();
 /// assert_eq!(next_seq.as_bytes(), b"abd");
 /// ```
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
 pub struct ByteSequence {
     inner: Vec<u8>, // A vector to hold the byte sequence
 }
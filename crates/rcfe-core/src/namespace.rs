@@ -0,0 +1,251 @@
+use crate::{
+    ByteSequence, KeyRange,
+    etcdserverpb::{
+        Compare, DeleteRangeRequest, DeleteRangeResponse, LeaseTimeToLiveResponse, PutRequest,
+        PutResponse, RangeRequest, RangeResponse, RequestOp, ResponseOp, TxnRequest, TxnResponse,
+        WatchCreateRequest, WatchRequest, WatchResponse, request_op::Request,
+        response_op::Response, watch_request::RequestUnion,
+    },
+    mvccpb::{Event, KeyValue},
+};
+
+/// Key prefix isolating the keyspace of a client.
+/// Requests are prefixed before they are sent and the prefix is stripped from the keys
+/// of the responses, so a namespaced client only sees the keys relative to its namespace.
+/// An empty namespace leaves requests and responses unchanged.
+/// # Examples
+/// ```rust
+/// use rcfe_core::{Namespace, etcdserverpb::PutRequest};
+/// let namespace = Namespace::new("tenant/");
+/// let request = namespace.prefix(PutRequest {
+///     key: b"key".to_vec(),
+///     ..Default::default()
+/// });
+/// assert_eq!(request.key, b"tenant/key");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Namespace {
+    prefix: Vec<u8>,
+}
+
+impl Namespace {
+    /// Creates a namespace from the key prefix.
+    pub fn new(prefix: impl Into<ByteSequence>) -> Self {
+        Namespace {
+            prefix: prefix.into().to_vec(),
+        }
+    }
+
+    /// Returns the key prefix of the namespace.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Returns true if the namespace has no prefix.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_empty()
+    }
+
+    /// Prefixes the keys of a request with the namespace.
+    pub fn prefix<T: PrefixNamespace>(&self, mut request: T) -> T {
+        if !self.is_empty() {
+            request.prefix_namespace(self);
+        }
+        request
+    }
+
+    /// Strips the namespace from the keys of a response.
+    pub fn strip<T: StripNamespace>(&self, mut response: T) -> T {
+        if !self.is_empty() {
+            response.strip_namespace(self);
+        }
+        response
+    }
+
+    /// Prefixes a key with the namespace.
+    pub fn prefix_key(&self, key: &[u8]) -> Vec<u8> {
+        [self.prefix.as_slice(), key].concat()
+    }
+
+    /// Prefixes the end of a range with the namespace.
+    /// An empty end selects a single key and stays empty. The `"\0"` end, which selects
+    /// every key from the start of the range, is bounded by the end of the namespace.
+    pub fn prefix_range_end(&self, range_end: &[u8]) -> Vec<u8> {
+        match range_end {
+            [] => Vec::new(),
+            [0] => KeyRange::prefix_end(&self.prefix),
+            end => self.prefix_key(end),
+        }
+    }
+
+    /// Strips the namespace from a key, keys outside of the namespace are left unchanged.
+    pub fn strip_key(&self, key: &mut Vec<u8>) {
+        if key.starts_with(&self.prefix) {
+            key.drain(..self.prefix.len());
+        }
+    }
+}
+
+impl From<Option<ByteSequence>> for Namespace {
+    fn from(prefix: Option<ByteSequence>) -> Self {
+        prefix.map(Namespace::new).unwrap_or_default()
+    }
+}
+
+/// A request whose keys are prefixed by a [`Namespace`].
+pub trait PrefixNamespace {
+    /// Prefixes the keys of the request with the namespace.
+    fn prefix_namespace(&mut self, namespace: &Namespace);
+}
+
+/// A response whose keys are stripped of a [`Namespace`].
+pub trait StripNamespace {
+    /// Strips the namespace from the keys of the response.
+    fn strip_namespace(&mut self, namespace: &Namespace);
+}
+
+impl PrefixNamespace for RangeRequest {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        self.key = namespace.prefix_key(&self.key);
+        self.range_end = namespace.prefix_range_end(&self.range_end);
+    }
+}
+
+impl PrefixNamespace for PutRequest {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        self.key = namespace.prefix_key(&self.key);
+    }
+}
+
+impl PrefixNamespace for DeleteRangeRequest {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        self.key = namespace.prefix_key(&self.key);
+        self.range_end = namespace.prefix_range_end(&self.range_end);
+    }
+}
+
+impl PrefixNamespace for Compare {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        self.key = namespace.prefix_key(&self.key);
+        self.range_end = namespace.prefix_range_end(&self.range_end);
+    }
+}
+
+impl PrefixNamespace for RequestOp {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        match &mut self.request {
+            Some(Request::RequestRange(request)) => request.prefix_namespace(namespace),
+            Some(Request::RequestPut(request)) => request.prefix_namespace(namespace),
+            Some(Request::RequestDeleteRange(request)) => request.prefix_namespace(namespace),
+            Some(Request::RequestTxn(request)) => request.prefix_namespace(namespace),
+            None => {}
+        }
+    }
+}
+
+impl PrefixNamespace for TxnRequest {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        self.compare
+            .iter_mut()
+            .for_each(|compare| compare.prefix_namespace(namespace));
+        self.success
+            .iter_mut()
+            .chain(self.failure.iter_mut())
+            .for_each(|op| op.prefix_namespace(namespace));
+    }
+}
+
+impl PrefixNamespace for WatchCreateRequest {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        self.key = namespace.prefix_key(&self.key);
+        self.range_end = namespace.prefix_range_end(&self.range_end);
+    }
+}
+
+impl PrefixNamespace for WatchRequest {
+    fn prefix_namespace(&mut self, namespace: &Namespace) {
+        if let Some(RequestUnion::CreateRequest(request)) = &mut self.request_union {
+            request.prefix_namespace(namespace);
+        }
+    }
+}
+
+impl StripNamespace for KeyValue {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        namespace.strip_key(&mut self.key);
+    }
+}
+
+impl StripNamespace for RangeResponse {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        self.kvs
+            .iter_mut()
+            .for_each(|kv| kv.strip_namespace(namespace));
+    }
+}
+
+impl StripNamespace for PutResponse {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        if let Some(kv) = &mut self.prev_kv {
+            kv.strip_namespace(namespace);
+        }
+    }
+}
+
+impl StripNamespace for DeleteRangeResponse {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        self.prev_kvs
+            .iter_mut()
+            .for_each(|kv| kv.strip_namespace(namespace));
+    }
+}
+
+impl StripNamespace for ResponseOp {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        match &mut self.response {
+            Some(Response::ResponseRange(response)) => response.strip_namespace(namespace),
+            Some(Response::ResponsePut(response)) => response.strip_namespace(namespace),
+            Some(Response::ResponseDeleteRange(response)) => response.strip_namespace(namespace),
+            Some(Response::ResponseTxn(response)) => response.strip_namespace(namespace),
+            None => {}
+        }
+    }
+}
+
+impl StripNamespace for TxnResponse {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        self.responses
+            .iter_mut()
+            .for_each(|response| response.strip_namespace(namespace));
+    }
+}
+
+/// Keys attached to the lease outside of the namespace are removed.
+impl StripNamespace for LeaseTimeToLiveResponse {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        self.keys
+            .retain(|key| key.starts_with(namespace.as_bytes()));
+        self.keys
+            .iter_mut()
+            .for_each(|key| namespace.strip_key(key));
+    }
+}
+
+impl StripNamespace for Event {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        if let Some(kv) = &mut self.kv {
+            kv.strip_namespace(namespace);
+        }
+        if let Some(kv) = &mut self.prev_kv {
+            kv.strip_namespace(namespace);
+        }
+    }
+}
+
+impl StripNamespace for WatchResponse {
+    fn strip_namespace(&mut self, namespace: &Namespace) {
+        self.events
+            .iter_mut()
+            .for_each(|event| event.strip_namespace(namespace));
+    }
+}
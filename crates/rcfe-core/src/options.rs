@@ -1,13 +1,20 @@
 use crate::ByteSequence;
 
+pub mod auth;
+pub mod bulk;
 pub mod client;
+pub mod config;
 pub mod delete;
 pub mod get;
 pub mod kv;
+pub mod middleware;
 pub mod put;
+pub mod retry;
+pub mod shutdown;
 pub mod txn;
 pub mod compact;
 pub mod lease;
+pub mod tls;
 pub mod watch;
 
 /// A trait for types that can have an optional namespace.
@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Formatter};
+
+/// Username and password used to authenticate against an etcd cluster with auth enabled.
+/// The password is never printed by the `Debug` implementation.
+/// # Examples
+/// ```rust
+/// use rcfe_core::Credentials;
+/// let credentials = Credentials::new("root", "secret");
+/// assert_eq!(credentials.user(), "root");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    user: String,
+    password: String,
+}
+
+impl Credentials {
+    /// Creates new credentials from a username and password.
+    pub fn new<U, P>(user: U, password: P) -> Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        Credentials {
+            user: user.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Returns the username.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the password.
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user", &self.user)
+            .field("password", &"******")
+            .finish()
+    }
+}
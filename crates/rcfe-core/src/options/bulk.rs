@@ -0,0 +1,132 @@
+use crate::error::Error;
+
+/// Default limit of operations per transaction, the default `--max-txn-ops` of etcd.
+pub const DEFAULT_MAX_TXN_OPS: usize = 128;
+
+/// Default limit of the size of a transaction, the default `--max-request-bytes` of etcd.
+pub const DEFAULT_MAX_TXN_BYTES: usize = 1536 * 1024;
+
+/// Options for bulk puts and deletes, which split the operations into transactions.
+/// # Fields
+/// * `max_txn_ops` - Maximum number of operations per transaction
+/// * `max_txn_bytes` - Maximum encoded size of the operations of a transaction
+/// * `concurrency` - Maximum number of transactions in flight
+/// # Examples
+/// ```rust
+/// use rcfe_core::BulkOptions;
+/// let options = BulkOptions::builder()
+///     .max_txn_ops(64)
+///     .concurrency(8)
+///     .build()
+///     .unwrap();
+/// assert_eq!(options.max_txn_ops(), 64);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BulkOptions {
+    max_txn_ops: usize,
+    max_txn_bytes: usize,
+    concurrency: usize,
+}
+
+impl Default for BulkOptions {
+    /// The default limits of etcd, with up to four transactions in flight.
+    fn default() -> Self {
+        BulkOptions {
+            max_txn_ops: DEFAULT_MAX_TXN_OPS,
+            max_txn_bytes: DEFAULT_MAX_TXN_BYTES,
+            concurrency: 4,
+        }
+    }
+}
+
+impl BulkOptions {
+    /// Creates a builder for BulkOptions, starting from the defaults.
+    pub fn builder() -> BulkOptionsBuilder {
+        BulkOptionsBuilder::default()
+    }
+
+    /// Returns the maximum number of operations per transaction.
+    pub fn max_txn_ops(&self) -> usize {
+        self.max_txn_ops
+    }
+
+    /// Returns the maximum encoded size of the operations of a transaction.
+    pub fn max_txn_bytes(&self) -> usize {
+        self.max_txn_bytes
+    }
+
+    /// Returns the maximum number of transactions in flight.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+}
+
+/// Builder for BulkOptions
+#[derive(Debug, Clone, Default)]
+pub struct BulkOptionsBuilder {
+    max_txn_ops: Option<usize>,
+    max_txn_bytes: Option<usize>,
+    concurrency: Option<usize>,
+}
+
+impl BulkOptionsBuilder {
+    /// Sets the maximum number of operations per transaction, which must not exceed the
+    /// `--max-txn-ops` of the cluster.
+    pub fn max_txn_ops(mut self, max_txn_ops: usize) -> Self {
+        self.max_txn_ops = Some(max_txn_ops);
+        self
+    }
+
+    /// Sets the maximum encoded size of the operations of a transaction, which must not exceed
+    /// the `--max-request-bytes` of the cluster. An operation larger than the limit is sent
+    /// in a transaction of its own.
+    pub fn max_txn_bytes(mut self, max_txn_bytes: usize) -> Self {
+        self.max_txn_bytes = Some(max_txn_bytes);
+        self
+    }
+
+    /// Sets the maximum number of transactions in flight.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Builds the BulkOptions
+    /// # Errors
+    /// Returns an `Error::IllegalArgument` if any of the limits is zero.
+    pub fn build(self) -> Result<BulkOptions, Error> {
+        let mut options = BulkOptions::default();
+
+        if let Some(max_txn_ops) = self.max_txn_ops {
+            options.max_txn_ops = max_txn_ops;
+        }
+
+        if let Some(max_txn_bytes) = self.max_txn_bytes {
+            options.max_txn_bytes = max_txn_bytes;
+        }
+
+        if let Some(concurrency) = self.concurrency {
+            options.concurrency = concurrency;
+        }
+
+        if options.max_txn_ops == 0 {
+            return Err(Error::IllegalArgument(String::from(
+                "max_txn_ops must be at least 1",
+            )));
+        }
+
+        if options.max_txn_bytes == 0 {
+            return Err(Error::IllegalArgument(String::from(
+                "max_txn_bytes must be at least 1",
+            )));
+        }
+
+        if options.concurrency == 0 {
+            return Err(Error::IllegalArgument(String::from(
+                "concurrency must be at least 1",
+            )));
+        }
+
+        Ok(options)
+    }
+}
@@ -1,16 +1,86 @@
 use crate::{
     ByteSequence,
-    options::Namespaceable
+    options::{
+        Namespaceable,
+        auth::Credentials,
+        config::ClientConfig,
+        middleware::{BoxError, ClientMiddleware, GrpcService},
+        retry::RetryPolicy,
+        tls::TlsOptions,
+    },
 };
 use crate::options::NamespaceBuilder;
+use serde::Deserialize;
+use std::time::Duration;
+use tonic::{
+    body::Body,
+    codec::CompressionEncoding,
+    codegen::{Body as HttpBody, Bytes, Service, http},
+    service::Interceptor,
+};
+use tower::Layer;
+
+/// Default interval between two health checks of the endpoints.
+pub const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default time to wait for the `Status` of an endpoint during a health check,
+/// used if neither a health probe timeout nor a request timeout is set.
+pub const DEFAULT_HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Client options for configuring the RCFE client.
 /// # Fields
 /// * `endpoints` - A vector of endpoint strings for connecting to the RCFE server.
-#[derive(Debug, Clone)]
+/// * `tls` - TLS options applied to `https://` endpoints.
+/// * `credentials` - Username and password used to authenticate with the cluster.
+/// * `connect_timeout` - Timeout for establishing a connection to an endpoint.
+/// * `request_timeout` - Default deadline for unary requests, overridable per call.
+/// * `keep_alive_interval` - Interval of HTTP/2 keepalive pings sent on idle connections.
+/// * `keep_alive_timeout` - Time to wait for a keepalive acknowledgement before closing the connection.
+/// * `retry_policy` - Policy for retrying requests that are safe to send again.
+/// * `health_check_interval` - Interval between two endpoint health checks, `None` if disabled.
+/// * `health_probe_timeout` - Time to wait for the `Status` of an endpoint during a health check.
+/// * `auto_sync_interval` - Interval between two member discoveries through `MemberList`, `None` if disabled.
+/// * `max_decoding_message_size` - Maximum size of a decoded response message.
+/// * `max_encoding_message_size` - Maximum size of an encoded request message.
+/// * `compression` - Encoding used to compress requests and accepted for responses.
+/// * `require_leader` - Whether requests and watches fail fast on a member without a leader.
+/// * `middleware` - Tower layers and interceptors applied to every service client.
+///
+/// Client options can also be deserialized from a configuration section, with durations
+/// written like `5s`, and built from `etcdctl` environment variables with [`ClientOptions::from_env`].
+/// # Examples
+/// ```toml
+/// endpoints = ["etcd-0:2379", "etcd-1:2379"]
+/// namespace = "app/"
+/// user = "root"
+/// password = "secret"
+/// request_timeout = "5s"
+///
+/// [tls]
+/// ca_cert = "/etc/etcd/ca.pem"
+/// client_cert = "/etc/etcd/client.pem"
+/// client_key = "/etc/etcd/client-key.pem"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "ClientConfig")]
 pub struct ClientOptions {
     endpoints: Vec<String>,
     namespace: Option<ByteSequence>,
+    tls: Option<TlsOptions>,
+    credentials: Option<Credentials>,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    health_check_interval: Option<Duration>,
+    health_probe_timeout: Option<Duration>,
+    auto_sync_interval: Option<Duration>,
+    max_decoding_message_size: Option<usize>,
+    max_encoding_message_size: Option<usize>,
+    compression: Option<CompressionEncoding>,
+    require_leader: bool,
+    middleware: ClientMiddleware,
 }
 
 /// Builder for ClientOptions.
@@ -18,6 +88,22 @@ pub struct ClientOptions {
 pub struct ClientOptionsBuilder {
     endpoints: Vec<String>,
     namespace: Option<ByteSequence>,
+    tls: Option<TlsOptions>,
+    credentials: Option<Credentials>,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    health_check_interval: Option<Duration>,
+    health_check_disabled: bool,
+    health_probe_timeout: Option<Duration>,
+    auto_sync_interval: Option<Duration>,
+    max_decoding_message_size: Option<usize>,
+    max_encoding_message_size: Option<usize>,
+    compression: Option<CompressionEncoding>,
+    require_leader: bool,
+    middleware: ClientMiddleware,
 }
 
 impl Namespaceable for ClientOptions {
@@ -41,6 +127,130 @@ impl ClientOptions {
         &self.endpoints
     }
 
+    /// Returns the TLS options, if any.
+    /// # Returns
+    /// * `Option<&TlsOptions>` - The TLS options used for `https://` endpoints.
+    pub fn tls(&self) -> Option<&TlsOptions> {
+        self.tls.as_ref()
+    }
+
+    /// Returns the authentication credentials, if any.
+    /// # Returns
+    /// * `Option<&Credentials>` - The username and password used to authenticate.
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.credentials.as_ref()
+    }
+
+    /// Returns the connect timeout, if any.
+    /// # Returns
+    /// * `Option<Duration>` - The timeout for establishing a connection to an endpoint.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Returns the default request timeout, if any.
+    /// # Returns
+    /// * `Option<Duration>` - The deadline applied to requests without a per-call timeout.
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Returns the HTTP/2 keepalive interval, if any.
+    /// # Returns
+    /// * `Option<Duration>` - The interval of keepalive pings.
+    pub fn keep_alive_interval(&self) -> Option<Duration> {
+        self.keep_alive_interval
+    }
+
+    /// Returns the HTTP/2 keepalive timeout, if any.
+    /// # Returns
+    /// * `Option<Duration>` - The time to wait for a keepalive acknowledgement.
+    pub fn keep_alive_timeout(&self) -> Option<Duration> {
+        self.keep_alive_timeout
+    }
+
+    /// Returns the retry policy, if any.
+    /// # Returns
+    /// * `Option<&RetryPolicy>` - The policy applied to requests that are safe to retry.
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Returns the interval between two endpoint health checks.
+    /// # Returns
+    /// * `Option<Duration>` - The health check interval, `None` if health checking is disabled.
+    pub fn health_check_interval(&self) -> Option<Duration> {
+        self.health_check_interval
+    }
+
+    /// Returns the time to wait for the `Status` of an endpoint during a health check.
+    /// Probes never outlast the health check interval, so that checks do not overlap.
+    /// # Returns
+    /// * `Duration` - The health probe timeout, else the request timeout, else
+    ///   [`DEFAULT_HEALTH_PROBE_TIMEOUT`], at most the health check interval.
+    pub fn health_probe_timeout(&self) -> Duration {
+        let timeout = self
+            .health_probe_timeout
+            .or(self.request_timeout)
+            .unwrap_or(DEFAULT_HEALTH_PROBE_TIMEOUT);
+        match self.health_check_interval {
+            Some(interval) => timeout.min(interval),
+            None => timeout,
+        }
+    }
+
+    /// Returns the interval between two member discoveries, if enabled.
+    /// # Returns
+    /// * `Option<Duration>` - The auto sync interval, `None` if member discovery is disabled.
+    pub fn auto_sync_interval(&self) -> Option<Duration> {
+        self.auto_sync_interval
+    }
+
+    /// Returns the maximum size of a decoded response message, if any.
+    /// # Returns
+    /// * `Option<usize>` - The limit in bytes, `None` for tonic's default of 4 MiB.
+    pub fn max_decoding_message_size(&self) -> Option<usize> {
+        self.max_decoding_message_size
+    }
+
+    /// Returns the maximum size of an encoded request message, if any.
+    /// # Returns
+    /// * `Option<usize>` - The limit in bytes, `None` for no limit.
+    pub fn max_encoding_message_size(&self) -> Option<usize> {
+        self.max_encoding_message_size
+    }
+
+    /// Returns the compression encoding, if any.
+    /// # Returns
+    /// * `Option<CompressionEncoding>` - The encoding of requests and accepted responses.
+    pub fn compression(&self) -> Option<CompressionEncoding> {
+        self.compression
+    }
+
+    /// Returns whether requests and watches require the member to have a leader.
+    /// # Returns
+    /// * `bool` - True if the `hasleader` metadata is attached to every request.
+    pub fn require_leader(&self) -> bool {
+        self.require_leader
+    }
+
+    /// Returns the middleware applied to every service client.
+    /// # Returns
+    /// * `&ClientMiddleware` - The tower layers and interceptors, empty if none were added.
+    pub fn middleware(&self) -> &ClientMiddleware {
+        &self.middleware
+    }
+
+    /// Returns a copy of the options using the namespace.
+    /// # Arguments
+    /// * `namespace` - The namespace replacing the current one, `None` removes it.
+    pub fn with_namespace(&self, namespace: Option<ByteSequence>) -> ClientOptions {
+        ClientOptions {
+            namespace,
+            ..self.clone()
+        }
+    }
+
     /// Creates a new ClientOptionsBuilder.
     /// # Returns
     /// * `ClientOptionsBuilder` - A new instance of ClientOptionsBuilder.
@@ -67,6 +277,7 @@ impl NamespaceBuilder for ClientOptionsBuilder {
 
 impl ClientOptionsBuilder {
     /// Sets the endpoints for the client.
+    /// A `unix://` endpoint connects to a unix domain socket and must be the only endpoint.
     /// # Arguments
     /// * `endpoints` - An iterable collection of endpoint strings.
     /// # Returns
@@ -88,6 +299,309 @@ impl ClientOptionsBuilder {
         }
     }
 
+    /// Sets the TLS options used for `https://` endpoints.
+    /// # Arguments
+    /// * `tls` - The TLS options, see [`TlsOptions::builder`].
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["https://localhost:2379"])
+    ///     .tls(TlsOptions::builder().ca_cert_file("ca.pem").build()?);
+    /// ```
+    pub fn tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets the username and password used to authenticate with the cluster.
+    /// The client authenticates on connect and transparently re-authenticates
+    /// when the server reports the token as invalid or expired.
+    /// # Arguments
+    /// * `user` - The username.
+    /// * `password` - The password.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .credentials("root", "secret");
+    /// ```
+    pub fn credentials<U, P>(mut self, user: U, password: P) -> Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.credentials = Some(Credentials::new(user, password));
+        self
+    }
+
+    /// Sets the timeout for establishing a connection to an endpoint.
+    /// # Arguments
+    /// * `timeout` - The connect timeout.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .connect_timeout(Duration::from_secs(5));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the default deadline for unary requests.
+    /// Requests exceeding it fail with `Error::Timeout`. Individual calls can override it,
+    /// e.g. through [`GetOptions`](crate::GetOptions). Watch and keep-alive streams are only
+    /// bounded while they are being opened.
+    /// # Arguments
+    /// * `timeout` - The default request timeout.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .request_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the interval of HTTP/2 keepalive pings, which are also sent while the
+    /// connection is idle so that a dead member is detected under long-lived watches.
+    /// # Arguments
+    /// * `interval` - The keepalive interval.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .keep_alive_interval(Duration::from_secs(30));
+    /// ```
+    pub fn keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Sets how long to wait for a keepalive acknowledgement before closing the connection.
+    /// Only effective together with [`keep_alive_interval`](Self::keep_alive_interval).
+    /// # Arguments
+    /// * `timeout` - The keepalive timeout.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    pub fn keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the policy for retrying requests that are safe to send again.
+    /// Without a policy every request is attempted once.
+    /// # Arguments
+    /// * `retry_policy` - The retry policy, see [`RetryPolicy::builder`].
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .retry_policy(RetryPolicy::default());
+    /// ```
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Sets the interval between two endpoint health checks.
+    /// Endpoints failing the Maintenance `Status` check stop receiving requests until they
+    /// pass it again. Defaults to [`DEFAULT_HEALTH_CHECK_INTERVAL`].
+    /// # Arguments
+    /// * `interval` - The health check interval.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379", "http://localhost:22379"])
+    ///     .health_check_interval(Duration::from_secs(5));
+    /// ```
+    pub fn health_check_interval(mut self, interval: Duration) -> Self {
+        self.health_check_interval = Some(interval);
+        self
+    }
+
+    /// Sets the time to wait for the `Status` of an endpoint during a health check.
+    /// Defaults to the request timeout, or [`DEFAULT_HEALTH_PROBE_TIMEOUT`] if none is set,
+    /// and is bounded by the health check interval.
+    /// # Arguments
+    /// * `timeout` - The health probe timeout.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379", "http://localhost:22379"])
+    ///     .health_probe_timeout(Duration::from_secs(2));
+    /// ```
+    pub fn health_probe_timeout(mut self, timeout: Duration) -> Self {
+        self.health_probe_timeout = Some(timeout);
+        self
+    }
+
+    /// Disables endpoint health checking, requests are balanced over all endpoints.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    pub fn disable_health_check(mut self) -> Self {
+        self.health_check_disabled = true;
+        self
+    }
+
+    /// Enables member discovery through the Cluster `MemberList` RPC.
+    /// The client lists the members when it connects and then every `interval`, replacing
+    /// the configured endpoints with the client URLs of the voting members. This allows
+    /// configuring a single seed endpoint and keeps the client working when members are
+    /// added, removed or replaced.
+    /// # Arguments
+    /// * `interval` - The interval between two member discoveries.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://etcd-0.etcd:2379"])
+    ///     .auto_sync_interval(Duration::from_secs(60));
+    /// ```
+    pub fn auto_sync_interval(mut self, interval: Duration) -> Self {
+        self.auto_sync_interval = Some(interval);
+        self
+    }
+
+    /// Sets the maximum size of a decoded response message.
+    /// Raise it when ranges over large prefixes exceed tonic's default of 4 MiB.
+    /// # Arguments
+    /// * `limit` - The limit in bytes.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .max_decoding_message_size(64 * 1024 * 1024);
+    /// ```
+    pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+        self.max_decoding_message_size = Some(limit);
+        self
+    }
+
+    /// Sets the maximum size of an encoded request message.
+    /// Larger KV requests and transactions fail with `OutOfRange` on the client without being
+    /// sent. Other requests are checked by tonic while they are sent, which may make the
+    /// server close the connection.
+    /// # Arguments
+    /// * `limit` - The limit in bytes.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+        self.max_encoding_message_size = Some(limit);
+        self
+    }
+
+    /// Compresses requests with the given encoding and asks the server to compress
+    /// responses with it.
+    /// # Arguments
+    /// * `encoding` - The compression encoding.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .compression(CompressionEncoding::Gzip);
+    /// ```
+    pub fn compression(mut self, encoding: CompressionEncoding) -> Self {
+        self.compression = Some(encoding);
+        self
+    }
+
+    /// Requires the serving member to have a leader for every request and watch.
+    /// A member partitioned from the leader then fails them with `Error::NoLeader`
+    /// instead of serving stale data or leaving watches silently stalled. Watches can
+    /// override it through [`WatchCreateOptions`](crate::WatchCreateOptions).
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .require_leader();
+    /// ```
+    pub fn require_leader(mut self) -> Self {
+        self.require_leader = true;
+        self
+    }
+
+    /// Adds a tower layer wrapping the gRPC channel of the KV, Lease, Watch and Cluster
+    /// clients, of authentication and of the endpoint health checks, e.g. for logging or
+    /// metrics. Layers are applied in the order they are added, so the last added layer
+    /// sees requests first.
+    /// # Arguments
+    /// * `layer` - The layer, its service may return any response body tonic can read.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .layer(TraceLayer::new_for_grpc());
+    /// ```
+    pub fn layer<L, B>(mut self, layer: L) -> Self
+    where
+        L: Layer<GrpcService> + Send + Sync + 'static,
+        L::Service: Service<http::Request<Body>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        <L::Service as Service<http::Request<Body>>>::Error: Into<BoxError>,
+        <L::Service as Service<http::Request<Body>>>::Future: Send + 'static,
+        B: HttpBody<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        self.middleware.layer(layer);
+        self
+    }
+
+    /// Adds a tonic interceptor called for every request of the client, including
+    /// authentication and health checks, e.g. to attach request IDs or additional headers.
+    /// Interceptors are layers, see [`layer`](Self::layer) for their order. Metadata they
+    /// set takes precedence over the metadata set by the client, such as `hasleader`.
+    /// # Arguments
+    /// * `interceptor` - The interceptor, any `FnMut(Request<()>) -> Result<Request<()>, Status>`.
+    /// # Returns
+    /// * `Self` - The updated ClientOptionsBuilder.
+    /// # Example
+    /// ```rust
+    /// let builder = ClientOptions::builder()
+    ///     .endpoints(vec!["http://localhost:2379"])
+    ///     .interceptor(|mut request: Request<()>| {
+    ///         request.metadata_mut().insert("x-request-id", "42".parse().unwrap());
+    ///         Ok(request)
+    ///     });
+    /// ```
+    pub fn interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: Interceptor + Clone + Send + Sync + 'static,
+    {
+        self.middleware.interceptor(interceptor);
+        self
+    }
+
     /// Builds the ClientOptions.
     /// # Returns
     /// * `ClientOptions` - The constructed ClientOptions instance.
@@ -101,6 +615,27 @@ impl ClientOptionsBuilder {
         ClientOptions {
             endpoints: self.endpoints,
             namespace: self.namespace,
+            tls: self.tls,
+            credentials: self.credentials,
+            connect_timeout: self.connect_timeout,
+            request_timeout: self.request_timeout,
+            keep_alive_interval: self.keep_alive_interval,
+            keep_alive_timeout: self.keep_alive_timeout,
+            retry_policy: self.retry_policy,
+            health_check_interval: match self.health_check_disabled {
+                true => None,
+                false => Some(
+                    self.health_check_interval
+                        .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL),
+                ),
+            },
+            health_probe_timeout: self.health_probe_timeout,
+            auto_sync_interval: self.auto_sync_interval,
+            max_decoding_message_size: self.max_decoding_message_size,
+            max_encoding_message_size: self.max_encoding_message_size,
+            compression: self.compression,
+            require_leader: self.require_leader,
+            middleware: self.middleware,
         }
     }
 }
@@ -0,0 +1,286 @@
+use crate::{
+    error::Error,
+    options::{NamespaceBuilder, client::ClientOptions, tls::TlsOptions},
+};
+use serde::Deserialize;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+/// Endpoint used by `etcdctl` when `ETCDCTL_ENDPOINTS` is not set.
+const DEFAULT_ENDPOINT: &str = "127.0.0.1:2379";
+
+impl ClientOptions {
+    /// Builds client options from the environment variables understood by `etcdctl`.
+    ///
+    /// The following variables are read, all of them are optional:
+    /// * `ETCDCTL_ENDPOINTS` - Comma separated endpoints, defaults to `127.0.0.1:2379`.
+    ///   Endpoints without a scheme use `https://` if a certificate is configured.
+    /// * `ETCDCTL_CACERT` - CA bundle used to verify the server certificate.
+    /// * `ETCDCTL_CERT` and `ETCDCTL_KEY` - Client certificate and key for mutual TLS.
+    /// * `ETCDCTL_USER` - Username, optionally followed by `:password`.
+    /// * `ETCDCTL_PASSWORD` - Password, if not part of `ETCDCTL_USER`.
+    /// * `ETCDCTL_DIAL_TIMEOUT` - Connect timeout, e.g. `2s`.
+    /// * `ETCDCTL_COMMAND_TIMEOUT` - Default request timeout, e.g. `5s`.
+    /// * `ETCDCTL_KEEPALIVE_TIME` and `ETCDCTL_KEEPALIVE_TIMEOUT` - HTTP/2 keepalive settings.
+    /// * `ETCDCTL_NAMESPACE` - Namespace prefixed to every key.
+    /// # Returns
+    /// * `Result<ClientOptions, Error>` - The options, or an `Error::IllegalArgument` if a variable is invalid.
+    /// # Example
+    /// ```rust
+    /// let options = ClientOptions::from_env()?;
+    /// ```
+    pub fn from_env() -> Result<ClientOptions, Error> {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Builds client options from `etcdctl` style variables, e.g. parsed from a `.env` file.
+    /// See [`from_env`](Self::from_env) for the variables that are read.
+    /// # Arguments
+    /// * `vars` - The variables as name and value pairs.
+    /// # Returns
+    /// * `Result<ClientOptions, Error>` - The options, or an `Error::IllegalArgument` if a variable is invalid.
+    /// # Example
+    /// ```rust
+    /// let options = ClientOptions::from_vars([
+    ///     ("ETCDCTL_ENDPOINTS", "https://etcd-0:2379,https://etcd-1:2379"),
+    ///     ("ETCDCTL_CACERT", "/etc/etcd/ca.pem"),
+    /// ])?;
+    /// ```
+    pub fn from_vars<I, K, V>(vars: I) -> Result<ClientOptions, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .filter(|(name, value)| name.starts_with("ETCDCTL_") && !value.is_empty())
+            .collect();
+        let var = |name: &str| vars.get(name).map(String::as_str);
+
+        let (user, password) = match var("ETCDCTL_USER").map(|user| user.split_once(':')) {
+            Some(Some((user, password))) => (Some(user), Some(password)),
+            Some(None) => (var("ETCDCTL_USER"), var("ETCDCTL_PASSWORD")),
+            None => (None, None),
+        };
+
+        let config = ClientConfig {
+            endpoints: var("ETCDCTL_ENDPOINTS")
+                .unwrap_or(DEFAULT_ENDPOINT)
+                .split(',')
+                .map(str::trim)
+                .filter(|endpoint| !endpoint.is_empty())
+                .map(String::from)
+                .collect(),
+            namespace: var("ETCDCTL_NAMESPACE").map(String::from),
+            tls: TlsConfig {
+                ca_cert: var("ETCDCTL_CACERT").map(PathBuf::from),
+                client_cert: var("ETCDCTL_CERT").map(PathBuf::from),
+                client_key: var("ETCDCTL_KEY").map(PathBuf::from),
+                domain_name: None,
+            },
+            user: user.map(String::from),
+            password: password.map(String::from),
+            connect_timeout: var("ETCDCTL_DIAL_TIMEOUT").map(String::from),
+            request_timeout: var("ETCDCTL_COMMAND_TIMEOUT").map(String::from),
+            keep_alive_interval: var("ETCDCTL_KEEPALIVE_TIME").map(String::from),
+            keep_alive_timeout: var("ETCDCTL_KEEPALIVE_TIMEOUT").map(String::from),
+            ..Default::default()
+        };
+
+        config.try_into()
+    }
+}
+
+/// Client options as read from a configuration file, see the `Deserialize` implementation
+/// of [`ClientOptions`]. Durations are written like `500ms`, `5s` or `1m30s`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ClientConfig {
+    endpoints: Vec<String>,
+    namespace: Option<String>,
+    tls: TlsConfig,
+    user: Option<String>,
+    password: Option<String>,
+    connect_timeout: Option<String>,
+    request_timeout: Option<String>,
+    keep_alive_interval: Option<String>,
+    keep_alive_timeout: Option<String>,
+    health_check_interval: Option<String>,
+    health_probe_timeout: Option<String>,
+    disable_health_check: bool,
+    auto_sync_interval: Option<String>,
+    max_decoding_message_size: Option<usize>,
+    max_encoding_message_size: Option<usize>,
+    require_leader: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TlsConfig {
+    ca_cert: Option<PathBuf>,
+    client_cert: Option<PathBuf>,
+    client_key: Option<PathBuf>,
+    domain_name: Option<String>,
+}
+
+impl TlsConfig {
+    fn is_empty(&self) -> bool {
+        self.ca_cert.is_none()
+            && self.client_cert.is_none()
+            && self.client_key.is_none()
+            && self.domain_name.is_none()
+    }
+}
+
+impl TryFrom<ClientConfig> for ClientOptions {
+    type Error = Error;
+
+    fn try_from(config: ClientConfig) -> Result<Self, Self::Error> {
+        if config.endpoints.is_empty() {
+            return Err(Error::IllegalArgument(String::from("no endpoints configured")));
+        }
+
+        let secure = !config.tls.is_empty();
+        let endpoints: Vec<String> = config
+            .endpoints
+            .iter()
+            .map(|endpoint| with_scheme(endpoint, secure))
+            .collect();
+
+        let mut builder = ClientOptions::builder()
+            .endpoints(endpoints)
+            .namespace(config.namespace);
+
+        if !config.tls.is_empty() {
+            builder = builder.tls(tls_options(config.tls)?);
+        }
+
+        builder = match (config.user, config.password) {
+            (Some(user), Some(password)) => builder.credentials(user, password),
+            (Some(_), None) => {
+                return Err(Error::IllegalArgument(String::from(
+                    "a password is required together with the user",
+                )));
+            }
+            (None, Some(_)) => {
+                return Err(Error::IllegalArgument(String::from(
+                    "a user is required together with the password",
+                )));
+            }
+            (None, None) => builder,
+        };
+
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(parse_duration(&timeout)?);
+        }
+
+        if let Some(timeout) = config.request_timeout {
+            builder = builder.request_timeout(parse_duration(&timeout)?);
+        }
+
+        if let Some(interval) = config.keep_alive_interval {
+            builder = builder.keep_alive_interval(parse_duration(&interval)?);
+        }
+
+        if let Some(timeout) = config.keep_alive_timeout {
+            builder = builder.keep_alive_timeout(parse_duration(&timeout)?);
+        }
+
+        if let Some(interval) = config.health_check_interval {
+            builder = builder.health_check_interval(parse_duration(&interval)?);
+        }
+
+        if let Some(timeout) = config.health_probe_timeout {
+            builder = builder.health_probe_timeout(parse_duration(&timeout)?);
+        }
+
+        if let Some(interval) = config.auto_sync_interval {
+            builder = builder.auto_sync_interval(parse_duration(&interval)?);
+        }
+
+        if config.disable_health_check {
+            builder = builder.disable_health_check();
+        }
+
+        if let Some(limit) = config.max_decoding_message_size {
+            builder = builder.max_decoding_message_size(limit);
+        }
+
+        if let Some(limit) = config.max_encoding_message_size {
+            builder = builder.max_encoding_message_size(limit);
+        }
+
+        if config.require_leader {
+            builder = builder.require_leader();
+        }
+
+        Ok(builder.build())
+    }
+}
+
+/// Adds a scheme to endpoints written as `host:port`, like `etcdctl` accepts them.
+fn with_scheme(endpoint: &str, secure: bool) -> String {
+    match endpoint.contains("://") || endpoint.starts_with("unix:") {
+        true => endpoint.to_string(),
+        false if secure => format!("https://{endpoint}"),
+        false => format!("http://{endpoint}"),
+    }
+}
+
+fn tls_options(config: TlsConfig) -> Result<TlsOptions, Error> {
+    let mut builder = TlsOptions::builder();
+
+    if let Some(ca_cert) = config.ca_cert {
+        builder = builder.ca_cert_file(ca_cert);
+    }
+
+    if let Some(client_cert) = config.client_cert {
+        builder = builder.client_cert_file(client_cert);
+    }
+
+    if let Some(client_key) = config.client_key {
+        builder = builder.client_key_file(client_key);
+    }
+
+    if let Some(domain_name) = config.domain_name {
+        builder = builder.domain_name(domain_name);
+    }
+
+    builder.build()
+}
+
+/// Parses a duration written like `etcdctl` flags, e.g. `500ms`, `5s`, `1m30s` or `1h`.
+fn parse_duration(value: &str) -> Result<Duration, Error> {
+    let invalid = || Error::IllegalArgument(format!("invalid duration: {value}"));
+
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut duration = Duration::ZERO;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or_else(invalid)?;
+        let amount: f64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+
+        let unit = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let seconds = match &rest[..unit] {
+            "ns" => 1e-9,
+            "us" | "µs" => 1e-6,
+            "ms" => 1e-3,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit..];
+
+        duration += Duration::try_from_secs_f64(amount * seconds).map_err(|_| invalid())?;
+    }
+
+    Ok(duration)
+}
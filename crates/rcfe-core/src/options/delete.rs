@@ -1,26 +1,31 @@
 use crate::{
-    ByteSequence,
+    KeyRange,
     etcdserverpb::DeleteRangeRequest
 };
+use std::time::Duration;
 
 /// Options for deleting keys in the key-value store
 /// # Fields
-/// * `prefix` - A key is treated as a prefix
+/// * `prefix` - A key is treated as a prefix, deprecated in favor of [`KeyRange::Prefix`]
 /// * `prev_kv` - Return the previous key-value pair before deletion
+/// * `timeout` - Overrides the client's default request timeout for this call
 /// # Examples
 /// ```rust
 /// use rcfe_core::options::kv::DeleteOptions;
 /// let delete_options = DeleteOptions {
-///     prefix: true,
-///     prev_kv: false,
+///     prev_kv: true,
+///     ..Default::default()
 /// };
 /// ```
 #[derive(Default, Debug, Clone)]
 pub struct DeleteOptions {
     /// A key is treated as a prefix
+    #[deprecated(note = "use KeyRange")]
     pub prefix: bool,
     /// Return the previous key-value pair before deletion
     pub prev_kv: bool,
+    /// Overrides the client's default request timeout for this call
+    pub timeout: Option<Duration>,
 }
 
 impl DeleteOptions {
@@ -29,7 +34,6 @@ impl DeleteOptions {
     /// ```rust
     /// use rcfe_core::options::kv::{DeleteOptions, DeleteOptionsBuilder};
     /// let delete_options = DeleteOptions::builder()
-    ///     .prefix(true)
     ///     .prev_kv(true)
     ///     .build();
     /// ```
@@ -41,28 +45,22 @@ impl DeleteOptions {
     /// # Examples
     /// ```rust
     /// use rcfe_core::options::kv::DeleteOptions;
-    /// use rcfe_core::ByteSequence;
+    /// use rcfe_core::KeyRange;
     /// use rcfe_core::etcdserverpb::DeleteRangeRequest;
     /// let delete_options = DeleteOptions::builder()
-    ///     .prefix(true)
     ///     .prev_kv(true)
     ///     .build();
-    /// let key = ByteSequence::from("my_key");
-    /// let request: DeleteRangeRequest = delete_options.to_request(key);
+    /// let request: DeleteRangeRequest = delete_options.to_request(KeyRange::prefix("my_key"));
+    /// assert_eq!(request.range_end, b"my_kez");
     /// ```
-    pub fn to_request(&self, key: &ByteSequence) -> DeleteRangeRequest {
-        let mut request = DeleteRangeRequest {
-            key: key.to_vec(),
-            ..Default::default()
-        };
-
-        if self.prefix {
-            request.range_end = key.next().to_vec();
+    pub fn to_request(&self, range: impl Into<KeyRange>) -> DeleteRangeRequest {
+        #[allow(deprecated)]
+        let (key, range_end) = range.into().with_flags(self.prefix, None).bounds();
+        DeleteRangeRequest {
+            key,
+            range_end,
+            prev_kv: self.prev_kv,
         }
-
-        request.prev_kv = self.prev_kv;
-
-        request
     }
 }
 
@@ -71,7 +69,6 @@ impl DeleteOptions {
 /// ```rust
 /// use rcfe_core::options::kv::DeleteOptionsBuilder;
 /// let delete_options = DeleteOptionsBuilder::default()
-///     .prefix(true)
 ///     .prev_kv(true)
 ///     .build();
 /// ```
@@ -79,17 +76,12 @@ impl DeleteOptions {
 pub struct DeleteOptionsBuilder {
     prefix: Option<bool>,
     prev_kv: Option<bool>,
+    timeout: Option<Duration>,
 }
 
 impl DeleteOptionsBuilder {
     /// Sets the prefix option for DeleteOptions
-    /// # Examples
-    /// ```rust
-    /// use rcfe_core::options::kv::DeleteOptionsBuilder;
-    /// let delete_options = DeleteOptionsBuilder::default()
-    ///     .prefix(true)
-    ///     .build();
-    /// ```
+    #[deprecated(note = "use KeyRange")]
     pub fn prefix(mut self, prefix: bool) -> Self {
         self.prefix = Some(prefix);
         self
@@ -108,17 +100,31 @@ impl DeleteOptionsBuilder {
         self
     }
 
+    /// Sets the timeout for this call, overriding the client's default request timeout
+    /// # Examples
+    /// ```rust
+    /// use rcfe_core::options::kv::DeleteOptionsBuilder;
+    /// use std::time::Duration;
+    /// let delete_options = DeleteOptionsBuilder::default()
+    ///    .timeout(Duration::from_secs(1))
+    ///    .build();
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Builds the DeleteOptions from the builder
     /// # Examples
     /// ```rust
     /// use rcfe_core::options::kv::DeleteOptionsBuilder;
     /// let delete_options = DeleteOptionsBuilder::default()
-    ///     .prefix(true)
     ///     .prev_kv(true)
     ///     .build();
     /// ```
     pub fn build(self) -> DeleteOptions {
         let mut options = DeleteOptions::default();
+        #[allow(deprecated)]
         if let Some(prefix) = self.prefix {
             options.prefix = prefix;
         }
@@ -127,6 +133,8 @@ impl DeleteOptionsBuilder {
             options.prev_kv = prev_kv;
         }
 
+        options.timeout = self.timeout;
+
         options
     }
 }
\ No newline at end of file
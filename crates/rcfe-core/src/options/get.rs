@@ -1,13 +1,14 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use crate::{
-    ByteSequence, etcdserverpb,
+    ByteSequence, KeyRange, etcdserverpb,
     etcdserverpb::range_request::{SortOrder, SortTarget},
-    options::{NamespaceBuilder, Namespaceable},
 };
 
 /// Options for Get operations
 #[derive(Debug, Clone)]
 pub struct GetOptions {
+    #[deprecated(note = "use KeyRange")]
     pub end_key: Option<ByteSequence>, // Optional end key for range queries
     pub limit: i64,                    // limit on number of results
     pub revision: i64,                 // revision to read from
@@ -20,8 +21,9 @@ pub struct GetOptions {
     pub max_mod_revision: i64,         // maximum modification revision
     pub min_create_revision: i64,      // minimum creation revision
     pub max_create_revision: i64,      // maximum creation revision
+    #[deprecated(note = "use KeyRange")]
     pub prefix: bool,                  // prefix flag
-    namespace: Option<ByteSequence>,
+    pub timeout: Option<Duration>,     // overrides the default request timeout
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
@@ -86,10 +88,11 @@ pub struct GetOptionsBuilder {
     min_create_revision: Option<i64>,
     max_create_revision: Option<i64>,
     prefix: Option<bool>,
-    namespace: Option<ByteSequence>,
+    timeout: Option<Duration>,
 }
 
 impl GetOptions {
+    #[allow(deprecated)]
     fn new() -> Self {
         GetOptions {
             end_key: None,
@@ -105,7 +108,7 @@ impl GetOptions {
             min_create_revision: 0,
             max_create_revision: 0,
             prefix: false,
-            namespace: None,
+            timeout: None,
         }
     }
 
@@ -120,30 +123,14 @@ impl GetOptions {
     }
 
     /// Converts GetOptions to an etcdserverpb::RangeRequest
-    pub fn to_request(self, key: &ByteSequence) -> etcdserverpb::RangeRequest {
-        let (key, end_key) = match self.namespace {
-            None => (
-                key.clone(),
-                match self.prefix {
-                    true => key.next(),
-                    false => self.end_key.unwrap_or_else(|| ByteSequence::empty()),
-                },
-            ),
-            Some(mut ns) => {
-                let namespaced_key = ns.clone().append(&key);
-                let namespaced_end_key = match self.prefix {
-                    true => namespaced_key.next(),
-                    false => match self.end_key {
-                        Some(end_key) => ns.append(&end_key),
-                        None => ByteSequence::empty(),
-                    },
-                };
-                (namespaced_key, namespaced_end_key)
-            }
-        };
+    /// The deprecated `prefix` and `end_key` options apply when the range is a single key.
+    #[allow(deprecated)]
+    pub fn to_request(self, range: impl Into<KeyRange>) -> etcdserverpb::RangeRequest {
+        let range = range.into().with_flags(self.prefix, self.end_key.as_ref());
+        let (key, range_end) = range.bounds();
         etcdserverpb::RangeRequest {
-            key: key.as_bytes().to_vec(),
-            range_end: end_key.inner,
+            key,
+            range_end,
             limit: self.limit,
             revision: self.revision,
             sort_order: self.sort_order as i32,
@@ -159,14 +146,9 @@ impl GetOptions {
     }
 }
 
-impl Namespaceable for GetOptions {
-    fn namespace(&self) -> Option<ByteSequence> {
-        self.namespace.clone()
-    }
-}
-
 impl GetOptionsBuilder {
     /// Sets the end key for range queries.
+    #[deprecated(note = "use KeyRange")]
     pub fn end_key<K>(mut self, end_key: K) -> Self
     where
         K: Into<ByteSequence>,
@@ -236,11 +218,19 @@ impl GetOptionsBuilder {
         self
     }
 
+    #[deprecated(note = "use KeyRange")]
     pub fn prefix(mut self, prefix: bool) -> Self {
         self.prefix = Some(prefix);
         self
     }
 
+    /// Sets the timeout for this call, overriding the client's default request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[allow(deprecated)]
     pub fn build(self) -> GetOptions {
         let mut options = GetOptions::new();
 
@@ -296,22 +286,10 @@ impl GetOptionsBuilder {
             options.prefix = prefix;
         }
 
-        if let Some(namespace) = self.namespace {
-            options.namespace = Some(namespace);
+        if let Some(timeout) = self.timeout {
+            options.timeout = Some(timeout);
         }
 
         options
     }
 }
-
-impl NamespaceBuilder for GetOptionsBuilder {
-    fn namespace<N>(mut self, namespace: Option<N>) -> Self
-    where
-        N: Into<ByteSequence>,
-    {
-        if let Some(ns) = namespace {
-            self.namespace = Some(ns.into());
-        }
-        self
-    }
-}
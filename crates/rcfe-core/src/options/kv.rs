@@ -26,6 +26,14 @@ impl KVOptions {
         self.channel
     }
 
+    /// Returns a copy of the options using the namespace.
+    pub fn with_namespace(&self, namespace: Option<ByteSequence>) -> KVOptions {
+        KVOptions {
+            channel: self.channel.clone(),
+            namespace,
+        }
+    }
+
     /// Creates a builder for KVOptions
     pub fn builder() -> KVOptionsBuilder {
         KVOptionsBuilder {
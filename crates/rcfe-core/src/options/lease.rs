@@ -1,5 +1,6 @@
 use tonic::transport::Channel;
 use crate::{
+    ByteSequence, NamespaceBuilder, Namespaceable,
     error::Error,
     etcdserverpb::LeaseTimeToLiveRequest
 };
@@ -8,6 +9,13 @@ pub mod grant;
 
 pub struct LeaseClientOptions {
     channel: Channel,
+    namespace: Option<ByteSequence>,
+}
+
+impl Namespaceable for LeaseClientOptions {
+    fn namespace(&self) -> Option<ByteSequence> {
+        self.namespace.clone()
+    }
 }
 
 impl LeaseClientOptions {
@@ -23,6 +31,19 @@ impl LeaseClientOptions {
 #[derive(Default)]
 pub struct LeaseClientOptionsBuilder {
     channel: Option<Channel>,
+    namespace: Option<ByteSequence>,
+}
+
+impl NamespaceBuilder for LeaseClientOptionsBuilder {
+    fn namespace<N>(mut self, namespace: Option<N>) -> Self
+    where
+        N: Into<ByteSequence>
+    {
+        if let Some(ns) = namespace {
+            self.namespace = Some(ns.into());
+        };
+        self
+    }
 }
 
 impl LeaseClientOptionsBuilder {
@@ -33,7 +54,7 @@ impl LeaseClientOptionsBuilder {
 
     pub fn build(self) -> Result<LeaseClientOptions, Error> {
         let channel = self.channel.ok_or(Error::IllegalArgument(String::from("channel not specified")))?;
-        Ok(LeaseClientOptions { channel })
+        Ok(LeaseClientOptions { channel, namespace: self.namespace })
     }
 }
 
@@ -0,0 +1,107 @@
+use std::{error::Error, fmt, sync::Arc};
+use tonic::{
+    body::Body,
+    codegen::{Body as HttpBody, Bytes, Service, http},
+    service::{Interceptor, InterceptorLayer},
+};
+use tower::{Layer, ServiceExt, util::BoxCloneSyncService};
+
+/// Boxed error of a layer or the transport.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Type-erased gRPC transport service wrapped by the client middleware.
+pub type GrpcService =
+    BoxCloneSyncService<http::Request<Body>, http::Response<Body>, MiddlewareError>;
+
+/// Error returned by a [`GrpcService`].
+/// Wraps the error of a layer or the transport, which stays reachable through
+/// [`Error::source`] so that tonic still maps it to the matching status.
+#[derive(Debug)]
+pub struct MiddlewareError(BoxError);
+
+impl MiddlewareError {
+    /// Wraps the error of a layer or the transport.
+    pub fn new(error: impl Into<BoxError>) -> Self {
+        MiddlewareError(error.into())
+    }
+}
+
+impl fmt::Display for MiddlewareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for MiddlewareError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+type BoxLayer = Arc<dyn Fn(GrpcService) -> GrpcService + Send + Sync>;
+
+/// Tower layers and tonic interceptors applied to the channel of every service client.
+/// Layers are applied in the order they were added, so the last added layer sees requests first.
+/// All layers wrap the client's own interceptor, metadata they set takes precedence over
+/// the metadata set by the client options.
+#[derive(Clone, Default)]
+pub struct ClientMiddleware {
+    layers: Vec<BoxLayer>,
+}
+
+impl fmt::Debug for ClientMiddleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientMiddleware")
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+impl ClientMiddleware {
+    /// Adds a tower layer.
+    /// # Arguments
+    /// * `layer` - The layer wrapping the gRPC transport service.
+    pub fn layer<L, B>(&mut self, layer: L)
+    where
+        L: Layer<GrpcService> + Send + Sync + 'static,
+        L::Service: Service<http::Request<Body>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        <L::Service as Service<http::Request<Body>>>::Error: Into<BoxError>,
+        <L::Service as Service<http::Request<Body>>>::Future: Send + 'static,
+        B: HttpBody<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        self.layers.push(Arc::new(move |service| {
+            let service = layer
+                .layer(service)
+                .map_response(|response| response.map(Body::new))
+                .map_err(MiddlewareError::new);
+            BoxCloneSyncService::new(service)
+        }));
+    }
+
+    /// Adds a tonic interceptor.
+    /// # Arguments
+    /// * `interceptor` - The interceptor called with the metadata of every request.
+    pub fn interceptor<I>(&mut self, interceptor: I)
+    where
+        I: Interceptor + Clone + Send + Sync + 'static,
+    {
+        self.layer(InterceptorLayer::new(interceptor));
+    }
+
+    /// Returns true if no layer was added.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Wraps the service with every layer.
+    pub fn apply(&self, service: GrpcService) -> GrpcService {
+        self.layers
+            .iter()
+            .fold(service, |service, layer| layer(service))
+    }
+}
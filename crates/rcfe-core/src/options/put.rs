@@ -1,5 +1,6 @@
 use crate::{ByteSequence, etcdserverpb};
 use etcdserverpb::PutRequest;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Default)]
 pub struct PutOptions {
@@ -18,6 +19,10 @@ pub struct PutOptions {
     /// If true, the value field in the PutRequest will be ignored.
     /// Default is false.
     pub ignore_value: bool,
+
+    /// Overrides the client's default request timeout for this call.
+    /// Default is None.
+    pub timeout: Option<Duration>,
 }
 
 impl PutOptions {
@@ -50,6 +55,7 @@ pub struct PutOptionsBuilder {
     prev_kv: Option<bool>,
     ignore_lease: Option<bool>,
     ignore_value: Option<bool>,
+    timeout: Option<Duration>,
 }
 
 impl PutOptionsBuilder {
@@ -73,6 +79,11 @@ impl PutOptionsBuilder {
         self
     }
 
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     pub fn build(self) -> PutOptions {
         let mut options = PutOptions::default();
         if let Some(lease) = self.lease {
@@ -90,6 +101,8 @@ impl PutOptionsBuilder {
         if let Some(ignore_value) = self.ignore_value {
             options.ignore_value = ignore_value;
         }
+
+        options.timeout = self.timeout;
         options
     }
 }
@@ -0,0 +1,227 @@
+use crate::error::Error;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+use tonic::{Code, Status};
+
+/// Policy for retrying failed requests that are safe to send again.
+///
+/// Retries are only applied to requests that cannot change the outcome when they are
+/// sent twice: reads, watch creation, lease keep-alive and time-to-live queries, read-only
+/// transactions and transactions whose failure branch only reads and whose written keys are
+/// all compared by mod revision, version or create revision. Plain puts and deletes are
+/// never retried.
+/// # Fields
+/// * `max_attempts` - Total number of attempts, including the first one
+/// * `initial_backoff` - Delay before the first retry
+/// * `max_backoff` - Upper bound of the delay between two attempts
+/// * `multiplier` - Factor applied to the delay after every retry
+/// * `jitter` - Fraction of the delay that is randomized, between `0.0` and `1.0`
+/// * `retryable_codes` - Status codes that are retried
+/// # Examples
+/// ```rust
+/// use rcfe_core::RetryPolicy;
+/// use std::time::Duration;
+/// use tonic::Code;
+/// let policy = RetryPolicy::builder()
+///     .max_attempts(5)
+///     .initial_backoff(Duration::from_millis(100))
+///     .retryable_codes([Code::Unavailable, Code::ResourceExhausted])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: f64,
+    retryable_codes: Vec<Code>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with a backoff starting at 50ms, retrying `Unavailable` only.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(2),
+            multiplier: 2.0,
+            jitter: 0.2,
+            retryable_codes: vec![Code::Unavailable],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a builder for RetryPolicy, starting from the defaults.
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::default()
+    }
+
+    /// Returns the total number of attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the first retry.
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Returns the upper bound of the delay between two attempts.
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Returns the factor applied to the delay after every retry.
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Returns the randomized fraction of the delay.
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    /// Returns the status codes that are retried.
+    pub fn retryable_codes(&self) -> &[Code] {
+        &self.retryable_codes
+    }
+
+    /// Returns true if a request failing with this status may be retried.
+    pub fn is_retryable(&self, status: &Status) -> bool {
+        self.retryable_codes.contains(&status.code())
+    }
+
+    /// Returns the delay before the given retry, starting at 1 for the first retry.
+    /// # Arguments
+    /// * `retry` - The number of the retry
+    /// # Returns
+    /// * `Duration` - The exponential backoff capped at `max_backoff`, with jitter applied.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let backoff = backoff.min(self.max_backoff.as_secs_f64());
+
+        // Spread the delay uniformly over [backoff * (1 - jitter), backoff * (1 + jitter)].
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        let factor = 1.0 + self.jitter * (2.0 * random - 1.0);
+
+        Duration::from_secs_f64(backoff * factor)
+    }
+}
+
+/// Builder for RetryPolicy
+#[derive(Debug, Clone, Default)]
+pub struct RetryPolicyBuilder {
+    max_attempts: Option<u32>,
+    initial_backoff: Option<Duration>,
+    max_backoff: Option<Duration>,
+    multiplier: Option<f64>,
+    jitter: Option<f64>,
+    retryable_codes: Option<Vec<Code>>,
+}
+
+impl RetryPolicyBuilder {
+    /// Sets the total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = Some(initial_backoff);
+        self
+    }
+
+    /// Sets the upper bound of the delay between two attempts.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = Some(max_backoff);
+        self
+    }
+
+    /// Sets the factor applied to the delay after every retry.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = Some(multiplier);
+        self
+    }
+
+    /// Sets the randomized fraction of the delay, `0.0` disables jitter.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Sets the status codes that are retried.
+    pub fn retryable_codes<I>(mut self, codes: I) -> Self
+    where
+        I: IntoIterator<Item = Code>,
+    {
+        self.retryable_codes = Some(codes.into_iter().collect());
+        self
+    }
+
+    /// Builds the RetryPolicy
+    /// # Errors
+    /// Returns an `Error::IllegalArgument` if `max_attempts` is zero, `multiplier` is
+    /// below `1.0`, `jitter` is outside of `[0.0, 1.0]` or `max_backoff` is shorter
+    /// than `initial_backoff`.
+    pub fn build(self) -> Result<RetryPolicy, Error> {
+        let mut policy = RetryPolicy::default();
+
+        if let Some(max_attempts) = self.max_attempts {
+            policy.max_attempts = max_attempts;
+        }
+
+        if let Some(initial_backoff) = self.initial_backoff {
+            policy.initial_backoff = initial_backoff;
+        }
+
+        if let Some(max_backoff) = self.max_backoff {
+            policy.max_backoff = max_backoff;
+        }
+
+        if let Some(multiplier) = self.multiplier {
+            policy.multiplier = multiplier;
+        }
+
+        if let Some(jitter) = self.jitter {
+            policy.jitter = jitter;
+        }
+
+        if let Some(retryable_codes) = self.retryable_codes {
+            policy.retryable_codes = retryable_codes;
+        }
+
+        if policy.max_attempts == 0 {
+            return Err(Error::IllegalArgument(String::from(
+                "max_attempts must be at least 1",
+            )));
+        }
+
+        if policy.multiplier.is_nan() || policy.multiplier < 1.0 {
+            return Err(Error::IllegalArgument(String::from(
+                "multiplier must be at least 1.0",
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&policy.jitter) {
+            return Err(Error::IllegalArgument(String::from(
+                "jitter must be between 0.0 and 1.0",
+            )));
+        }
+
+        if policy.max_backoff < policy.initial_backoff {
+            return Err(Error::IllegalArgument(String::from(
+                "max_backoff must not be shorter than initial_backoff",
+            )));
+        }
+
+        Ok(policy)
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+/// Default time to wait for in-flight requests when shutting down a client.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Options for shutting down a client.
+/// # Fields
+/// * `revoke_leases` - Whether the leases granted through the client are revoked
+/// * `timeout` - Deadline for in-flight requests and lease revocation
+#[derive(Debug, Clone)]
+pub struct ShutdownOptions {
+    pub revoke_leases: bool,
+    pub timeout: Duration,
+}
+
+impl Default for ShutdownOptions {
+    fn default() -> Self {
+        ShutdownOptions {
+            revoke_leases: false,
+            timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+}
+
+impl ShutdownOptions {
+    /// Creates a builder for ShutdownOptions
+    /// # Examples
+    /// ```rust
+    /// use rcfe_core::ShutdownOptions;
+    /// use std::time::Duration;
+    /// let options = ShutdownOptions::builder()
+    ///     .revoke_leases(true)
+    ///     .timeout(Duration::from_secs(2))
+    ///     .build();
+    /// ```
+    pub fn builder() -> ShutdownOptionsBuilder {
+        ShutdownOptionsBuilder::default()
+    }
+}
+
+/// Builder for ShutdownOptions
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownOptionsBuilder {
+    revoke_leases: Option<bool>,
+    timeout: Option<Duration>,
+}
+
+impl ShutdownOptionsBuilder {
+    /// Sets whether the leases granted through the client are revoked, so that keys
+    /// attached to them are deleted right away instead of when their TTL expires.
+    pub fn revoke_leases(mut self, revoke_leases: bool) -> Self {
+        self.revoke_leases = Some(revoke_leases);
+        self
+    }
+
+    /// Sets the deadline for in-flight requests and lease revocation.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Builds the ShutdownOptions
+    pub fn build(self) -> ShutdownOptions {
+        ShutdownOptions {
+            revoke_leases: self.revoke_leases.unwrap_or(false),
+            timeout: self.timeout.unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT),
+        }
+    }
+}
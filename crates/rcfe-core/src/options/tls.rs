@@ -0,0 +1,147 @@
+use crate::error::Error;
+use std::path::PathBuf;
+
+/// Source of a PEM encoded certificate or private key.
+/// # Variants
+/// * `File` - Path to a PEM file, read when the client connects
+/// * `Pem` - PEM encoded bytes held in memory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PemSource {
+    File(PathBuf),
+    Pem(Vec<u8>),
+}
+
+impl PemSource {
+    /// Loads the PEM encoded bytes from this source.
+    /// # Errors
+    /// Returns an `Error::Io` if the file cannot be read.
+    pub fn load(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            PemSource::File(path) => Ok(std::fs::read(path)?),
+            PemSource::Pem(pem) => Ok(pem.clone()),
+        }
+    }
+}
+
+/// TLS options for connecting to `https://` endpoints.
+/// # Fields
+/// * `ca_cert` - CA bundle used to verify the server certificate, the system roots are used when absent
+/// * `client_cert` - Client certificate presented for mutual TLS
+/// * `client_key` - Private key of the client certificate
+/// * `domain_name` - Overrides the server name used for SNI and certificate verification
+/// # Examples
+/// ```rust
+/// use rcfe_core::TlsOptions;
+/// let tls = TlsOptions::builder()
+///     .ca_cert_file("/etc/etcd/ca.pem")
+///     .client_cert_file("/etc/etcd/client.pem")
+///     .client_key_file("/etc/etcd/client-key.pem")
+///     .domain_name("etcd.cluster.local")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    ca_cert: Option<PemSource>,
+    client_cert: Option<PemSource>,
+    client_key: Option<PemSource>,
+    domain_name: Option<String>,
+}
+
+impl TlsOptions {
+    /// Creates a builder for TlsOptions
+    pub fn builder() -> TlsOptionsBuilder {
+        TlsOptionsBuilder::default()
+    }
+
+    /// Returns the CA bundle source, if any.
+    pub fn ca_cert(&self) -> Option<&PemSource> {
+        self.ca_cert.as_ref()
+    }
+
+    /// Returns the client certificate source, if any.
+    pub fn client_cert(&self) -> Option<&PemSource> {
+        self.client_cert.as_ref()
+    }
+
+    /// Returns the client private key source, if any.
+    pub fn client_key(&self) -> Option<&PemSource> {
+        self.client_key.as_ref()
+    }
+
+    /// Returns the server name override, if any.
+    pub fn domain_name(&self) -> Option<&str> {
+        self.domain_name.as_deref()
+    }
+}
+
+/// Builder for TlsOptions
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptionsBuilder {
+    ca_cert: Option<PemSource>,
+    client_cert: Option<PemSource>,
+    client_key: Option<PemSource>,
+    domain_name: Option<String>,
+}
+
+impl TlsOptionsBuilder {
+    /// Sets the CA bundle from a PEM file.
+    pub fn ca_cert_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.ca_cert = Some(PemSource::File(path.into()));
+        self
+    }
+
+    /// Sets the CA bundle from PEM encoded bytes.
+    pub fn ca_cert_pem<B: Into<Vec<u8>>>(mut self, pem: B) -> Self {
+        self.ca_cert = Some(PemSource::Pem(pem.into()));
+        self
+    }
+
+    /// Sets the client certificate from a PEM file.
+    pub fn client_cert_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.client_cert = Some(PemSource::File(path.into()));
+        self
+    }
+
+    /// Sets the client certificate from PEM encoded bytes.
+    pub fn client_cert_pem<B: Into<Vec<u8>>>(mut self, pem: B) -> Self {
+        self.client_cert = Some(PemSource::Pem(pem.into()));
+        self
+    }
+
+    /// Sets the client private key from a PEM file.
+    pub fn client_key_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.client_key = Some(PemSource::File(path.into()));
+        self
+    }
+
+    /// Sets the client private key from PEM encoded bytes.
+    pub fn client_key_pem<B: Into<Vec<u8>>>(mut self, pem: B) -> Self {
+        self.client_key = Some(PemSource::Pem(pem.into()));
+        self
+    }
+
+    /// Overrides the server name used to verify the server certificate.
+    pub fn domain_name<S: Into<String>>(mut self, domain_name: S) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+
+    /// Builds the TlsOptions
+    /// # Errors
+    /// Returns an `Error::IllegalArgument` if only one of the client certificate and key is set.
+    pub fn build(self) -> Result<TlsOptions, Error> {
+        if self.client_cert.is_some() != self.client_key.is_some() {
+            return Err(Error::IllegalArgument(String::from(
+                "client certificate and client key must be specified together",
+            )));
+        }
+
+        Ok(TlsOptions {
+            ca_cert: self.ca_cert,
+            client_cert: self.client_cert,
+            client_key: self.client_key,
+            domain_name: self.domain_name,
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    ByteSequence,
+    ByteSequence, KeyRange,
     error::Error,
     etcdserverpb::{
         Compare as PbCompare,
@@ -214,6 +214,20 @@ impl Compare {
         self
     }
 
+    /// Set the key and range end of the compare to the bounds of the range
+    /// # Examples
+    /// ```rust
+    /// use rcfe_core::options::txn::compare::Compare;
+    /// use rcfe_core::KeyRange;
+    /// let compare = Compare::version_eq("", 0).with_range(KeyRange::prefix("dir/"));
+    /// ```
+    pub fn with_range(mut self, range: KeyRange) -> Self {
+        let (key, range_end) = range.bounds();
+        self.key = key.into();
+        self.range_end = (!range_end.is_empty()).then(|| range_end.into());
+        self
+    }
+
     /// Convert to protobuf Compare
     /// # Examples
     /// ```rust
@@ -308,6 +322,14 @@ impl CompareBuilder {
         self
     }
 
+    /// Sets the key and range end to the bounds of the range.
+    pub fn range(mut self, range: KeyRange) -> Self {
+        let (key, range_end) = range.bounds();
+        self.key = Some(key.into());
+        self.range_end = (!range_end.is_empty()).then(|| range_end.into());
+        self
+    }
+
     pub fn build(self) -> Compare {
         Compare {
             result: self.result.expect("CompareResult is required"),
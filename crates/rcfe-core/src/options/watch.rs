@@ -1,29 +1,63 @@
 use crate::{
-    ByteSequence, WatchRequest, etcdserverpb::WatchCancelRequest, etcdserverpb::WatchCreateRequest,
+    ByteSequence, KeyRange, NamespaceBuilder, Namespaceable, WatchRequest, etcdserverpb::WatchCancelRequest, etcdserverpb::WatchCreateRequest,
 };
 use tonic::transport::Channel;
 
 #[derive(Debug, Clone)]
 pub struct WatchClientOptions {
     channel: Channel,
+    namespace: Option<ByteSequence>,
+}
+
+impl Namespaceable for WatchClientOptions {
+    fn namespace(&self) -> Option<ByteSequence> {
+        self.namespace.clone()
+    }
 }
 
 impl WatchClientOptions {
     pub fn new(channel: Channel) -> Self {
-        WatchClientOptions { channel }
+        WatchClientOptions {
+            channel,
+            namespace: None,
+        }
     }
 
     pub fn builder() -> WatchClientOptionsBuilder {
-        WatchClientOptionsBuilder { channel: None }
+        WatchClientOptionsBuilder {
+            channel: None,
+            namespace: None,
+        }
     }
 
     pub fn channel(self) -> Channel {
         self.channel
     }
+
+    /// Returns a copy of the options using the namespace.
+    pub fn with_namespace(&self, namespace: Option<ByteSequence>) -> WatchClientOptions {
+        WatchClientOptions {
+            channel: self.channel.clone(),
+            namespace,
+        }
+    }
 }
 
 pub struct WatchClientOptionsBuilder {
     channel: Option<Channel>,
+    namespace: Option<ByteSequence>,
+}
+
+impl NamespaceBuilder for WatchClientOptionsBuilder {
+    fn namespace<N>(mut self, namespace: Option<N>) -> Self
+    where
+        N: Into<ByteSequence>,
+    {
+        if let Some(ns) = namespace {
+            self.namespace = Some(ns.into());
+        };
+        self
+    }
 }
 
 impl WatchClientOptionsBuilder {
@@ -38,7 +72,10 @@ impl WatchClientOptionsBuilder {
             .ok_or(crate::error::Error::IllegalArgument(String::from(
                 "channel not specified",
             )))?;
-        Ok(WatchClientOptions { channel })
+        Ok(WatchClientOptions {
+            channel,
+            namespace: self.namespace,
+        })
     }
 }
 
@@ -56,6 +93,8 @@ pub struct WatchCreateOptions {
     pub progress_notify: bool,
     pub filters: Vec<FilterType>,
     pub prev_kv: bool,
+    /// Overrides whether the watch requires a leader, `None` uses the client options.
+    pub require_leader: Option<bool>,
 }
 
 impl WatchCreateOptions {
@@ -90,6 +129,7 @@ impl WatchCreateOptions {
             progress_notify: None,
             filters: vec![],
             prev_kv: None,
+            require_leader: None,
         }
     }
 }
@@ -101,6 +141,7 @@ pub struct WatchCreateOptionsBuilder {
     progress_notify: Option<bool>,
     filters: Vec<FilterType>,
     prev_kv: Option<bool>,
+    require_leader: Option<bool>,
 }
 
 impl WatchCreateOptionsBuilder {
@@ -114,6 +155,14 @@ impl WatchCreateOptionsBuilder {
         self
     }
 
+    /// Sets the key and range end of the watch to the bounds of the range.
+    pub fn range(mut self, range: KeyRange) -> Self {
+        let (key, range_end) = range.bounds();
+        self.key = Some(key.into());
+        self.range_end = Some(range_end.into());
+        self
+    }
+
     pub fn start_revision(mut self, start_revision: i64) -> Self {
         self.start_revision = Some(start_revision);
         self
@@ -134,6 +183,13 @@ impl WatchCreateOptionsBuilder {
         self
     }
 
+    /// Sets whether the watch stream fails with `Error::NoLeader` once the member
+    /// loses its leader, overriding `ClientOptions::require_leader`.
+    pub fn require_leader(mut self, require_leader: bool) -> Self {
+        self.require_leader = Some(require_leader);
+        self
+    }
+
     pub fn build(self) -> Result<WatchCreateOptions, crate::error::Error> {
         Ok(WatchCreateOptions {
             key: self
@@ -146,6 +202,7 @@ impl WatchCreateOptionsBuilder {
             progress_notify: self.progress_notify.unwrap_or(false),
             filters: self.filters,
             prev_kv: self.prev_kv.unwrap_or(false),
+            require_leader: self.require_leader,
         })
     }
 }
@@ -1,30 +1,50 @@
 pub use crate::{
+    bulk::{BatchResult, BulkResult},
+    cas::{CasOutcome, UpdateOutcome},
     client::Client,
+    codec::Codec,
+    dynamic::{
+        DynClient, DynKVClient, DynKeepAliveHandler, DynLeaseClient, DynTxn, DynWatchClient,
+        DynWatcher,
+    },
+    endpoint::{EndpointHealth, EndpointStatus, HealthReport},
     error::Error,
     etcdserverpb::{
         CompactionResponse, DeleteRangeResponse, LeaseGrantResponse, LeaseKeepAliveRequest,
         LeaseKeepAliveResponse, LeaseRevokeRequest, LeaseRevokeResponse, LeaseTimeToLiveResponse,
         PutResponse, RangeResponse, TxnRequest, TxnResponse, WatchProgressRequest, WatchRequest,
-        WatchResponse, kv_client::KvClient as GrpcKVClient,
-        lease_client::LeaseClient as GrpcLeaseClient, range_request::SortOrder,
-        watch_client::WatchClient as GrpcWatchClient,
+        WatchResponse, auth_client::AuthClient as GrpcAuthClient,
+        cluster_client::ClusterClient as GrpcClusterClient,
+        kv_client::KvClient as GrpcKVClient, lease_client::LeaseClient as GrpcLeaseClient,
+        maintenance_client::MaintenanceClient as GrpcMaintenanceClient,
+        range_request::SortOrder, watch_client::WatchClient as GrpcWatchClient,
     },
     factory::ClientFactory,
     kv::KVClient,
     lease::{KeepAliveHandler, LeaseClient},
+    namespace::{Namespace, PrefixNamespace, StripNamespace},
     options::{
         NamespaceBuilder, Namespaceable,
-        client::ClientOptions,
+        auth::Credentials,
+        bulk::{BulkOptions, BulkOptionsBuilder, DEFAULT_MAX_TXN_BYTES, DEFAULT_MAX_TXN_OPS},
+        client::{
+            ClientOptions, ClientOptionsBuilder, DEFAULT_HEALTH_CHECK_INTERVAL,
+            DEFAULT_HEALTH_PROBE_TIMEOUT,
+        },
         compact::{CompactOptions, CompactOptionsBuilder},
         delete::{DeleteOptions, DeleteOptionsBuilder},
         get::{GetOptions, GetOptionsBuilder, SortTargetOption, SortOrderOption},
         kv::{KVOptions, KVOptionsBuilder},
+        middleware::{BoxError, ClientMiddleware, GrpcService, MiddlewareError},
         lease::{
             TimeToLiveOptions, TimeToLiveOptionsBuilder,
             grant::{GrantOptions, GrantOptionsBuilder},
             {LeaseClientOptions, LeaseClientOptionsBuilder},
         },
         put::{PutOptions, PutOptionsBuilder},
+        retry::{RetryPolicy, RetryPolicyBuilder},
+        shutdown::{DEFAULT_SHUTDOWN_TIMEOUT, ShutdownOptions, ShutdownOptionsBuilder},
+        tls::{PemSource, TlsOptions, TlsOptionsBuilder},
         txn::{
             compare::{Compare, CompareBuilder, CompareResult, CompareTarget},
             op::RequestOp,
@@ -34,6 +54,16 @@ pub use crate::{
             WatchCreateOptionsBuilder, WatchRequestType,
         },
     },
+    range::KeyRange,
+    response::{DeleteResult, GetResult, KeyValue, PutResult, ResponseHeader},
     txn::Txn,
+    typed::{TypedKv, TypedValue},
     watch::{WatchClient, Watcher},
 };
+pub use tonic::codec::CompressionEncoding;
+#[cfg(feature = "bincode")]
+pub use crate::codec::BincodeCodec;
+#[cfg(feature = "json")]
+pub use crate::codec::JsonCodec;
+#[cfg(feature = "msgpack")]
+pub use crate::codec::MessagePackCodec;
@@ -0,0 +1,149 @@
+use crate::{ByteSequence, namespace::Namespace};
+
+/// The `"\0"` key, the smallest key etcd accepts.
+/// As the end of a range it selects every key from the start of the range.
+const ZERO_KEY: [u8; 1] = [0];
+
+/// The keys selected by a request.
+/// Converts to the `key` and `range_end` pair of the etcd API, see [`KeyRange::bounds`].
+/// Keys convert into a single key range, so a key can be passed wherever a range is accepted.
+/// # Examples
+/// ```rust
+/// use rcfe_core::KeyRange;
+/// let (key, range_end) = KeyRange::prefix("dir/").bounds();
+/// assert_eq!(key, b"dir/");
+/// assert_eq!(range_end, b"dir0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyRange {
+    /// A single key.
+    Key(ByteSequence),
+    /// Every key starting with the prefix, an empty prefix selects all keys.
+    Prefix(ByteSequence),
+    /// Every key greater than or equal to the key.
+    FromKey(ByteSequence),
+    /// Every key in `[start, end)`.
+    Range {
+        start: ByteSequence,
+        end: ByteSequence,
+    },
+    /// Every key.
+    All,
+}
+
+impl KeyRange {
+    /// Selects a single key.
+    pub fn key(key: impl Into<ByteSequence>) -> Self {
+        KeyRange::Key(key.into())
+    }
+
+    /// Selects every key starting with the prefix.
+    pub fn prefix(prefix: impl Into<ByteSequence>) -> Self {
+        KeyRange::Prefix(prefix.into())
+    }
+
+    /// Selects every key greater than or equal to the key.
+    pub fn from_key(key: impl Into<ByteSequence>) -> Self {
+        KeyRange::FromKey(key.into())
+    }
+
+    /// Selects every key in `[start, end)`.
+    pub fn range(start: impl Into<ByteSequence>, end: impl Into<ByteSequence>) -> Self {
+        KeyRange::Range {
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+
+    /// Selects every key.
+    pub fn all() -> Self {
+        KeyRange::All
+    }
+
+    /// Returns the `key` and `range_end` of the range.
+    /// An empty `range_end` selects the single key, a `"\0"` `range_end` every key from `key`.
+    pub fn bounds(&self) -> (Vec<u8>, Vec<u8>) {
+        match self {
+            KeyRange::Key(key) => (key.to_vec(), Vec::new()),
+            KeyRange::Prefix(prefix) if prefix.as_bytes().is_empty() => KeyRange::All.bounds(),
+            KeyRange::Prefix(prefix) => (prefix.to_vec(), Self::prefix_end(prefix.as_bytes())),
+            KeyRange::FromKey(key) if key.as_bytes().is_empty() => KeyRange::All.bounds(),
+            KeyRange::FromKey(key) => (key.to_vec(), ZERO_KEY.to_vec()),
+            KeyRange::Range { start, end } => (start.to_vec(), end.to_vec()),
+            KeyRange::All => (ZERO_KEY.to_vec(), ZERO_KEY.to_vec()),
+        }
+    }
+
+    /// Returns the `key` and `range_end` of the range within the namespace.
+    /// Ranges extending to the end of the keyspace end at the end of the namespace.
+    pub fn bounds_in(&self, namespace: &Namespace) -> (Vec<u8>, Vec<u8>) {
+        let (key, range_end) = self.bounds();
+        if namespace.is_empty() {
+            return (key, range_end);
+        }
+        (
+            namespace.prefix_key(&key),
+            namespace.prefix_range_end(&range_end),
+        )
+    }
+
+    /// Returns the end of the range of keys starting with the prefix.
+    /// A prefix of `0xFF` bytes, which [`ByteSequence::next`] cannot increment,
+    /// extends to the end of the keyspace.
+    pub fn prefix_end(prefix: &[u8]) -> Vec<u8> {
+        match ByteSequence::from(prefix).next().to_vec() {
+            end if end.is_empty() => ZERO_KEY.to_vec(),
+            end => end,
+        }
+    }
+
+    /// Applies the legacy `prefix` and `end_key` flags of the options to a single key.
+    /// Other ranges already select their end and are returned unchanged.
+    pub(crate) fn with_flags(self, prefix: bool, end_key: Option<&ByteSequence>) -> Self {
+        match (self, end_key) {
+            (KeyRange::Key(key), _) if prefix => KeyRange::Prefix(key),
+            (KeyRange::Key(key), Some(end)) if end.as_bytes() == ZERO_KEY => KeyRange::FromKey(key),
+            (KeyRange::Key(key), Some(end)) if !end.as_bytes().is_empty() => KeyRange::Range {
+                start: key,
+                end: end.clone(),
+            },
+            (range, _) => range,
+        }
+    }
+}
+
+impl From<ByteSequence> for KeyRange {
+    fn from(key: ByteSequence) -> Self {
+        KeyRange::Key(key)
+    }
+}
+
+impl From<&ByteSequence> for KeyRange {
+    fn from(key: &ByteSequence) -> Self {
+        KeyRange::Key(key.clone())
+    }
+}
+
+impl From<&str> for KeyRange {
+    fn from(key: &str) -> Self {
+        KeyRange::Key(key.into())
+    }
+}
+
+impl From<String> for KeyRange {
+    fn from(key: String) -> Self {
+        KeyRange::Key(key.into())
+    }
+}
+
+impl From<Vec<u8>> for KeyRange {
+    fn from(key: Vec<u8>) -> Self {
+        KeyRange::Key(key.into())
+    }
+}
+
+impl From<&[u8]> for KeyRange {
+    fn from(key: &[u8]) -> Self {
+        KeyRange::Key(key.into())
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{
+    ByteSequence,
+    etcdserverpb::{self, DeleteRangeResponse, PutResponse, RangeResponse},
+    mvccpb,
+};
+use tonic::{Extensions, Response, metadata::MetadataMap};
+
+/// Header of a response, describing the member that served it.
+/// # Fields
+/// * `cluster_id` - The ID of the cluster
+/// * `member_id` - The ID of the member that served the request
+/// * `revision` - The revision of the key-value store when the request was applied
+/// * `raft_term` - The raft term of the member when the request was applied
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseHeader {
+    pub cluster_id: u64,
+    pub member_id: u64,
+    pub revision: i64,
+    pub raft_term: u64,
+}
+
+impl From<etcdserverpb::ResponseHeader> for ResponseHeader {
+    fn from(header: etcdserverpb::ResponseHeader) -> Self {
+        ResponseHeader {
+            cluster_id: header.cluster_id,
+            member_id: header.member_id,
+            revision: header.revision,
+            raft_term: header.raft_term,
+        }
+    }
+}
+
+impl From<ResponseHeader> for etcdserverpb::ResponseHeader {
+    fn from(header: ResponseHeader) -> Self {
+        etcdserverpb::ResponseHeader {
+            cluster_id: header.cluster_id,
+            member_id: header.member_id,
+            revision: header.revision,
+            raft_term: header.raft_term,
+        }
+    }
+}
+
+/// A key-value pair of the store.
+/// # Fields
+/// * `key` - The key
+/// * `value` - The value held by the key
+/// * `create_revision` - The revision of the last creation of the key
+/// * `mod_revision` - The revision of the last modification of the key
+/// * `version` - The number of modifications since the key was created, reset by deletion
+/// * `lease` - The ID of the lease attached to the key, `0` if there is none
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValue {
+    pub key: ByteSequence,
+    pub value: ByteSequence,
+    pub create_revision: i64,
+    pub mod_revision: i64,
+    pub version: i64,
+    pub lease: i64,
+}
+
+impl From<mvccpb::KeyValue> for KeyValue {
+    fn from(kv: mvccpb::KeyValue) -> Self {
+        KeyValue {
+            key: kv.key.into(),
+            value: kv.value.into(),
+            create_revision: kv.create_revision,
+            mod_revision: kv.mod_revision,
+            version: kv.version,
+            lease: kv.lease,
+        }
+    }
+}
+
+impl From<KeyValue> for mvccpb::KeyValue {
+    fn from(kv: KeyValue) -> Self {
+        mvccpb::KeyValue {
+            key: kv.key.into(),
+            value: kv.value.into(),
+            create_revision: kv.create_revision,
+            mod_revision: kv.mod_revision,
+            version: kv.version,
+            lease: kv.lease,
+        }
+    }
+}
+
+/// Result of a range query.
+/// The raw response stays available through [`GetResult::into_raw`].
+/// # Examples
+/// ```rust
+/// use rcfe_core::{GetResult, etcdserverpb::RangeResponse, mvccpb};
+/// let result = GetResult::from(RangeResponse {
+///     kvs: vec![mvccpb::KeyValue {
+///         key: b"key".to_vec(),
+///         value: b"value".to_vec(),
+///         ..Default::default()
+///     }],
+///     count: 1,
+///     ..Default::default()
+/// });
+/// assert_eq!(result.kvs()[0].value.as_bytes(), b"value");
+/// assert_eq!(result.count(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GetResult {
+    header: ResponseHeader,
+    kvs: Vec<KeyValue>,
+    more: bool,
+    count: i64,
+    metadata: MetadataMap,
+}
+
+impl GetResult {
+    /// Returns the header of the response.
+    pub fn header(&self) -> &ResponseHeader {
+        &self.header
+    }
+
+    /// Returns the key-value pairs matching the query.
+    pub fn kvs(&self) -> &[KeyValue] {
+        &self.kvs
+    }
+
+    /// Consumes the result, returning the key-value pairs matching the query.
+    pub fn into_kvs(self) -> Vec<KeyValue> {
+        self.kvs
+    }
+
+    /// Returns the first key-value pair, the pair of the key for single key queries.
+    pub fn first(&self) -> Option<&KeyValue> {
+        self.kvs.first()
+    }
+
+    /// Returns true if no key matched the query.
+    pub fn is_empty(&self) -> bool {
+        self.kvs.is_empty()
+    }
+
+    /// Returns true if the limit of the query left more keys to return.
+    pub fn more(&self) -> bool {
+        self.more
+    }
+
+    /// Returns the number of keys within the range, regardless of the limit of the query.
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+
+    /// Returns the gRPC metadata of the response.
+    pub fn metadata(&self) -> &MetadataMap {
+        &self.metadata
+    }
+
+    /// Converts the result back to the raw gRPC response.
+    pub fn into_raw(self) -> Response<RangeResponse> {
+        let response = RangeResponse {
+            header: Some(self.header.into()),
+            kvs: self.kvs.into_iter().map(Into::into).collect(),
+            more: self.more,
+            count: self.count,
+        };
+        Response::from_parts(self.metadata, response, Extensions::default())
+    }
+}
+
+impl From<RangeResponse> for GetResult {
+    fn from(response: RangeResponse) -> Self {
+        GetResult::from(Response::new(response))
+    }
+}
+
+impl From<Response<RangeResponse>> for GetResult {
+    fn from(response: Response<RangeResponse>) -> Self {
+        let (metadata, response, _) = response.into_parts();
+        GetResult {
+            header: response.header.map(Into::into).unwrap_or_default(),
+            kvs: response.kvs.into_iter().map(Into::into).collect(),
+            more: response.more,
+            count: response.count,
+            metadata,
+        }
+    }
+}
+
+impl IntoIterator for GetResult {
+    type Item = KeyValue;
+    type IntoIter = std::vec::IntoIter<KeyValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.kvs.into_iter()
+    }
+}
+
+/// Result of a put.
+/// The raw response stays available through [`PutResult::into_raw`].
+#[derive(Debug, Clone, Default)]
+pub struct PutResult {
+    header: ResponseHeader,
+    prev_kv: Option<KeyValue>,
+    metadata: MetadataMap,
+}
+
+impl PutResult {
+    /// Returns the header of the response.
+    pub fn header(&self) -> &ResponseHeader {
+        &self.header
+    }
+
+    /// Returns the key-value pair before the put, if `prev_kv` was requested and the key existed.
+    pub fn prev_kv(&self) -> Option<&KeyValue> {
+        self.prev_kv.as_ref()
+    }
+
+    /// Consumes the result, returning the key-value pair before the put.
+    pub fn into_prev_kv(self) -> Option<KeyValue> {
+        self.prev_kv
+    }
+
+    /// Returns the gRPC metadata of the response.
+    pub fn metadata(&self) -> &MetadataMap {
+        &self.metadata
+    }
+
+    /// Converts the result back to the raw gRPC response.
+    pub fn into_raw(self) -> Response<PutResponse> {
+        let response = PutResponse {
+            header: Some(self.header.into()),
+            prev_kv: self.prev_kv.map(Into::into),
+        };
+        Response::from_parts(self.metadata, response, Extensions::default())
+    }
+}
+
+impl From<PutResponse> for PutResult {
+    fn from(response: PutResponse) -> Self {
+        PutResult::from(Response::new(response))
+    }
+}
+
+impl From<Response<PutResponse>> for PutResult {
+    fn from(response: Response<PutResponse>) -> Self {
+        let (metadata, response, _) = response.into_parts();
+        PutResult {
+            header: response.header.map(Into::into).unwrap_or_default(),
+            prev_kv: response.prev_kv.map(Into::into),
+            metadata,
+        }
+    }
+}
+
+/// Result of a delete.
+/// The raw response stays available through [`DeleteResult::into_raw`].
+#[derive(Debug, Clone, Default)]
+pub struct DeleteResult {
+    header: ResponseHeader,
+    deleted: i64,
+    prev_kvs: Vec<KeyValue>,
+    metadata: MetadataMap,
+}
+
+impl DeleteResult {
+    /// Returns the header of the response.
+    pub fn header(&self) -> &ResponseHeader {
+        &self.header
+    }
+
+    /// Returns the number of deleted keys.
+    pub fn deleted(&self) -> i64 {
+        self.deleted
+    }
+
+    /// Returns the deleted key-value pairs, if `prev_kv` was requested.
+    pub fn prev_kvs(&self) -> &[KeyValue] {
+        &self.prev_kvs
+    }
+
+    /// Consumes the result, returning the deleted key-value pairs.
+    pub fn into_prev_kvs(self) -> Vec<KeyValue> {
+        self.prev_kvs
+    }
+
+    /// Returns the gRPC metadata of the response.
+    pub fn metadata(&self) -> &MetadataMap {
+        &self.metadata
+    }
+
+    /// Converts the result back to the raw gRPC response.
+    pub fn into_raw(self) -> Response<DeleteRangeResponse> {
+        let response = DeleteRangeResponse {
+            header: Some(self.header.into()),
+            deleted: self.deleted,
+            prev_kvs: self.prev_kvs.into_iter().map(Into::into).collect(),
+        };
+        Response::from_parts(self.metadata, response, Extensions::default())
+    }
+}
+
+impl From<DeleteRangeResponse> for DeleteResult {
+    fn from(response: DeleteRangeResponse) -> Self {
+        DeleteResult::from(Response::new(response))
+    }
+}
+
+impl From<Response<DeleteRangeResponse>> for DeleteResult {
+    fn from(response: Response<DeleteRangeResponse>) -> Self {
+        let (metadata, response, _) = response.into_parts();
+        DeleteResult {
+            header: response.header.map(Into::into).unwrap_or_default(),
+            deleted: response.deleted,
+            prev_kvs: response.prev_kvs.into_iter().map(Into::into).collect(),
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    ByteSequence, KeyRange,
+    error::Error,
+    etcdserverpb::range_request::{SortOrder, SortTarget},
+    kv::KVClient,
+    options::get::GetOptions,
+    response::KeyValue,
+};
+use futures_util::stream::{self, Stream};
+use std::collections::VecDeque;
+
+/// State of a scan between two pages.
+struct Scan<'a, K> {
+    client: &'a mut K,
+    options: GetOptions,
+    start: Vec<u8>,
+    end: Vec<u8>,
+    descending: bool,
+    page_size: i64,
+    /// Keys left to return when the options set a total `limit`.
+    remaining: Option<i64>,
+    page: VecDeque<KeyValue>,
+    done: bool,
+    error: Option<Error>,
+}
+
+/// Scans the range page by page, see [`KVClient::scan_with_options`].
+#[allow(deprecated)]
+pub(crate) fn scan<K>(
+    client: &mut K,
+    range: KeyRange,
+    page_size: i64,
+    mut options: GetOptions,
+) -> impl Stream<Item = Result<KeyValue, Error>> + Send + '_
+where
+    K: KVClient,
+{
+    let error = validate(page_size, &options);
+    let (start, end) = range
+        .with_flags(options.prefix, options.end_key.as_ref())
+        .bounds();
+    let descending = options.sort_order == SortOrder::Descend;
+    let remaining = (options.limit > 0).then_some(options.limit);
+    options.prefix = false;
+    options.end_key = None;
+
+    let scan = Scan {
+        client,
+        options,
+        start,
+        end,
+        descending,
+        page_size,
+        remaining,
+        page: VecDeque::new(),
+        done: false,
+        error,
+    };
+    stream::try_unfold(scan, |mut scan| async move {
+        if let Some(error) = scan.error.take() {
+            return Err(error);
+        }
+        if scan.page.is_empty() && !scan.done {
+            scan.next_page().await?;
+        }
+        Ok(scan.page.pop_front().map(|kv| (kv, scan)))
+    })
+}
+
+fn validate(page_size: i64, options: &GetOptions) -> Option<Error> {
+    if page_size <= 0 {
+        return Some(Error::IllegalArgument(format!(
+            "page size must be positive, got {page_size}"
+        )));
+    }
+    if options.sort_order != SortOrder::None && options.sort_target != SortTarget::Key {
+        return Some(Error::IllegalArgument(String::from(
+            "scans page by key and can only be sorted by key",
+        )));
+    }
+    if options.count_only {
+        return Some(Error::IllegalArgument(String::from(
+            "scans return key-value pairs and cannot be count only",
+        )));
+    }
+    None
+}
+
+impl<K: KVClient> Scan<'_, K> {
+    /// Fetches the page following the last returned key.
+    async fn next_page(&mut self) -> Result<(), Error> {
+        let limit = match self.remaining {
+            Some(remaining) => remaining.min(self.page_size),
+            None => self.page_size,
+        };
+        let mut options = self.options.clone();
+        options.limit = limit;
+        let range = KeyRange::Range {
+            start: ByteSequence::from(self.start.clone()),
+            end: ByteSequence::from(self.end.clone()),
+        };
+        let result = self.client.get_with_options(range, options).await?;
+
+        // Later pages read the revision of the first one.
+        if self.options.revision == 0 {
+            self.options.revision = result.header().revision;
+        }
+        let more = result.more();
+        self.page.extend(result);
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= self.page.len() as i64;
+        }
+        self.done = !more || self.page.is_empty() || self.remaining == Some(0);
+
+        if let Some(last) = self.page.back() {
+            let mut key = last.key.to_vec();
+            if self.descending {
+                self.end = key;
+            } else {
+                key.push(0);
+                self.start = key;
+            }
+        }
+        Ok(())
+    }
+}
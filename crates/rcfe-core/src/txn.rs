@@ -23,7 +23,7 @@ use tonic::Response;
 ///        .await?
 /// }
 #[tonic::async_trait]
-pub trait Txn {
+pub trait Txn: Send {
     /// Adds comparison conditions to the transaction.
     /// # Arguments
     /// * `compares` - An iterable collection of comparison conditions.
@@ -83,6 +83,13 @@ pub trait Txn {
         I: IntoIterator<Item = P>,
         P: Into<RequestOp>;
 
+    /// Sends the transaction at most once, even if the retry policy would retry it.
+    /// Used when an applied attempt whose response was lost must not be sent again,
+    /// compare-and-swap commits rely on it to not report their own write as a conflict.
+    /// # Returns
+    /// * `&mut Self` - A mutable reference to the transaction.
+    fn disable_retries(&mut self) -> &mut Self;
+
     /// Commits the transaction and executes the operations.
     /// # Returns
     /// * `Result<Response<TxnResponse>, error::Error>` - The response containing the transaction result or an error.
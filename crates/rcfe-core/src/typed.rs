@@ -0,0 +1,187 @@
+use crate::{
+    ByteSequence, KeyRange,
+    codec::Codec,
+    error::Error,
+    kv::KVClient,
+    options::{delete::DeleteOptions, get::GetOptions, put::PutOptions},
+    response::{DeleteResult, KeyValue, PutResult},
+};
+use std::marker::PhantomData;
+
+/// A value decoded by a [`TypedKv`], along with the metadata of its key.
+/// # Fields
+/// * `key` - The key
+/// * `value` - The decoded value
+/// * `create_revision` - The revision of the last creation of the key
+/// * `mod_revision` - The revision of the last modification of the key
+/// * `version` - The number of modifications since the key was created, reset by deletion
+/// * `lease` - The ID of the lease attached to the key, `0` if there is none
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedValue<T> {
+    pub key: ByteSequence,
+    pub value: T,
+    pub create_revision: i64,
+    pub mod_revision: i64,
+    pub version: i64,
+    pub lease: i64,
+}
+
+/// KV client storing values of type `T`, encoded by the codec `C`.
+/// Wraps a [`KVClient`], which stays available through [`TypedKv::client_mut`]
+/// for operations on raw bytes.
+/// # Examples
+/// ```rust,no_run
+/// use rcfe_core::{Error, JsonCodec, KVClient, TypedKv};
+/// use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     replicas: u32,
+/// }
+/// async fn example<K: KVClient>(client: K) -> Result<(), Error> {
+///     let mut configs: TypedKv<Config, _, _> = client.typed(JsonCodec);
+///     configs.put("config", &Config { replicas: 3 }).await?;
+///     let config = configs.get("config").await?.expect("config");
+///     assert_eq!(config.value.replicas, 3);
+///     Ok(())
+/// }
+/// ```
+pub struct TypedKv<T, C, K> {
+    client: K,
+    codec: C,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T, C, K> TypedKv<T, C, K>
+where
+    C: Codec<T>,
+    K: KVClient,
+{
+    /// Creates a typed client storing values through the KV client.
+    pub fn new(client: K, codec: C) -> Self {
+        TypedKv {
+            client,
+            codec,
+            value: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped KV client.
+    pub fn client(&self) -> &K {
+        &self.client
+    }
+
+    /// Returns the wrapped KV client, e.g. to operate on raw bytes.
+    pub fn client_mut(&mut self) -> &mut K {
+        &mut self.client
+    }
+
+    /// Returns the codec of the values.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Consumes the typed client, returning the wrapped KV client.
+    pub fn into_inner(self) -> K {
+        self.client
+    }
+
+    /// Encodes a value of the key.
+    pub fn encode(&self, key: &ByteSequence, value: &T) -> Result<ByteSequence, Error> {
+        self.codec
+            .encode(value)
+            .map(ByteSequence::from)
+            .map_err(|e| Error::Encode(format!("key {}: {e}", display_key(key))))
+    }
+
+    /// Decodes the value of a key-value pair.
+    pub fn decode(&self, kv: KeyValue) -> Result<TypedValue<T>, Error> {
+        let value = self
+            .codec
+            .decode(kv.value.as_bytes())
+            .map_err(|e| Error::Decode(format!("key {}: {e}", display_key(&kv.key))))?;
+        Ok(TypedValue {
+            key: kv.key,
+            value,
+            create_revision: kv.create_revision,
+            mod_revision: kv.mod_revision,
+            version: kv.version,
+            lease: kv.lease,
+        })
+    }
+
+    /// Retrieves and decodes the value of a key, `None` if the key does not exist.
+    pub async fn get<Key>(&mut self, key: Key) -> Result<Option<TypedValue<T>>, Error>
+    where
+        Key: Into<ByteSequence> + Send,
+    {
+        let result = self.client.get(KeyRange::Key(key.into())).await?;
+        result
+            .into_iter()
+            .next()
+            .map(|kv| self.decode(kv))
+            .transpose()
+    }
+
+    /// Retrieves and decodes the values of a range.
+    /// Fails with `Error::Decode` if any of the values cannot be decoded.
+    pub async fn get_range<R>(&mut self, range: R) -> Result<Vec<TypedValue<T>>, Error>
+    where
+        R: Into<KeyRange> + Send,
+    {
+        self.get_with_options(range, GetOptions::default()).await
+    }
+
+    /// Retrieves and decodes the values of a range with the specified options.
+    /// Fails with `Error::Decode` if any of the values cannot be decoded.
+    pub async fn get_with_options<R>(
+        &mut self,
+        range: R,
+        options: GetOptions,
+    ) -> Result<Vec<TypedValue<T>>, Error>
+    where
+        R: Into<KeyRange> + Send,
+    {
+        let result = self.client.get_with_options(range, options).await?;
+        result.into_iter().map(|kv| self.decode(kv)).collect()
+    }
+
+    /// Encodes and puts the value of a key.
+    pub async fn put<Key>(&mut self, key: Key, value: &T) -> Result<PutResult, Error>
+    where
+        Key: Into<ByteSequence> + Send,
+    {
+        self.put_with_options(key, value, PutOptions::default())
+            .await
+    }
+
+    /// Encodes and puts the value of a key with the specified options.
+    /// The previous value, if requested, can be decoded with [`TypedKv::decode`].
+    pub async fn put_with_options<Key>(
+        &mut self,
+        key: Key,
+        value: &T,
+        options: PutOptions,
+    ) -> Result<PutResult, Error>
+    where
+        Key: Into<ByteSequence> + Send,
+    {
+        let key = key.into();
+        let value = self.encode(&key, value)?;
+        self.client.put_with_options(key, value, options).await
+    }
+
+    /// Deletes the keys of the range.
+    pub async fn delete<R>(&mut self, range: R) -> Result<DeleteResult, Error>
+    where
+        R: Into<KeyRange> + Send,
+    {
+        self.client
+            .delete_with_options(range, DeleteOptions::default())
+            .await
+    }
+}
+
+/// Formats a key for error messages, keys are usually but not necessarily UTF-8.
+fn display_key(key: &ByteSequence) -> String {
+    format!("{:?}", String::from_utf8_lossy(key.as_bytes()))
+}
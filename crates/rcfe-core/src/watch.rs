@@ -2,7 +2,7 @@ use crate::{error::Error, etcdserverpb::WatchResponse, WatchRequestType};
 use tonic::{async_trait, Response, Streaming};
 
 #[async_trait]
-pub trait Watcher {
+pub trait Watcher: Send {
     /// Retrieves the ID of the watcher.
     fn id(&self) -> i64;
 
@@ -23,7 +23,7 @@ pub trait Watcher {
 }
 
 #[async_trait]
-pub trait WatchClient {
+pub trait WatchClient: Send + Sync {
 
     /// Watches a key or range of keys for changes.
     async fn watch(&mut self, request: WatchRequestType) -> Result<impl Watcher, Error>;
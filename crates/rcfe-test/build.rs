@@ -0,0 +1,12 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Server stubs for the in-process etcd stand-ins, reusing the message types of rcfe-core.
+    tonic_prost_build::configure()
+        .build_client(false)
+        .build_server(true)
+        .extern_path(".etcdserverpb", "::rcfe::etcdserverpb")
+        .extern_path(".mvccpb", "::rcfe::mvccpb")
+        .extern_path(".authpb", "::rcfe::authpb")
+        .compile_protos(&["../rcfe-core/proto/rpc.proto"], &["../rcfe-core/proto"])?;
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::{MockEtcd, etcdserverpb::auth_server::Auth};
+use rcfe::etcdserverpb::*;
+use tonic::{Request, Response, Status};
+
+/// Only `Authenticate` is served; user and role management is not needed by the tests.
+#[tonic::async_trait]
+impl Auth for MockEtcd {
+    async fn authenticate(
+        &self,
+        request: Request<AuthenticateRequest>,
+    ) -> Result<Response<AuthenticateResponse>, Status> {
+        let request = request.into_inner();
+        let mut state = self.state.lock().unwrap();
+
+        if state.users.get(&request.name) != Some(&request.password) {
+            return Err(Status::invalid_argument(
+                "etcdserver: authentication failed, invalid user ID or password",
+            ));
+        }
+
+        state.issued_tokens += 1;
+        let token = format!("{}.{}", request.name, state.issued_tokens);
+        state.tokens.insert(token.clone());
+
+        Ok(Response::new(AuthenticateResponse {
+            header: state.header(),
+            token,
+        }))
+    }
+
+    async fn auth_enable(
+        &self,
+        _request: Request<AuthEnableRequest>,
+    ) -> Result<Response<AuthEnableResponse>, Status> {
+        Err(Status::unimplemented("auth_enable"))
+    }
+
+    async fn auth_disable(
+        &self,
+        _request: Request<AuthDisableRequest>,
+    ) -> Result<Response<AuthDisableResponse>, Status> {
+        Err(Status::unimplemented("auth_disable"))
+    }
+
+    async fn user_add(
+        &self,
+        _request: Request<AuthUserAddRequest>,
+    ) -> Result<Response<AuthUserAddResponse>, Status> {
+        Err(Status::unimplemented("user_add"))
+    }
+
+    async fn user_get(
+        &self,
+        _request: Request<AuthUserGetRequest>,
+    ) -> Result<Response<AuthUserGetResponse>, Status> {
+        Err(Status::unimplemented("user_get"))
+    }
+
+    async fn user_list(
+        &self,
+        _request: Request<AuthUserListRequest>,
+    ) -> Result<Response<AuthUserListResponse>, Status> {
+        Err(Status::unimplemented("user_list"))
+    }
+
+    async fn user_delete(
+        &self,
+        _request: Request<AuthUserDeleteRequest>,
+    ) -> Result<Response<AuthUserDeleteResponse>, Status> {
+        Err(Status::unimplemented("user_delete"))
+    }
+
+    async fn user_change_password(
+        &self,
+        _request: Request<AuthUserChangePasswordRequest>,
+    ) -> Result<Response<AuthUserChangePasswordResponse>, Status> {
+        Err(Status::unimplemented("user_change_password"))
+    }
+
+    async fn user_grant_role(
+        &self,
+        _request: Request<AuthUserGrantRoleRequest>,
+    ) -> Result<Response<AuthUserGrantRoleResponse>, Status> {
+        Err(Status::unimplemented("user_grant_role"))
+    }
+
+    async fn user_revoke_role(
+        &self,
+        _request: Request<AuthUserRevokeRoleRequest>,
+    ) -> Result<Response<AuthUserRevokeRoleResponse>, Status> {
+        Err(Status::unimplemented("user_revoke_role"))
+    }
+
+    async fn role_add(
+        &self,
+        _request: Request<AuthRoleAddRequest>,
+    ) -> Result<Response<AuthRoleAddResponse>, Status> {
+        Err(Status::unimplemented("role_add"))
+    }
+
+    async fn role_get(
+        &self,
+        _request: Request<AuthRoleGetRequest>,
+    ) -> Result<Response<AuthRoleGetResponse>, Status> {
+        Err(Status::unimplemented("role_get"))
+    }
+
+    async fn role_list(
+        &self,
+        _request: Request<AuthRoleListRequest>,
+    ) -> Result<Response<AuthRoleListResponse>, Status> {
+        Err(Status::unimplemented("role_list"))
+    }
+
+    async fn role_delete(
+        &self,
+        _request: Request<AuthRoleDeleteRequest>,
+    ) -> Result<Response<AuthRoleDeleteResponse>, Status> {
+        Err(Status::unimplemented("role_delete"))
+    }
+
+    async fn role_grant_permission(
+        &self,
+        _request: Request<AuthRoleGrantPermissionRequest>,
+    ) -> Result<Response<AuthRoleGrantPermissionResponse>, Status> {
+        Err(Status::unimplemented("role_grant_permission"))
+    }
+
+    async fn role_revoke_permission(
+        &self,
+        _request: Request<AuthRoleRevokePermissionRequest>,
+    ) -> Result<Response<AuthRoleRevokePermissionResponse>, Status> {
+        Err(Status::unimplemented("role_revoke_permission"))
+    }
+}
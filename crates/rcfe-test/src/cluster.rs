@@ -0,0 +1,48 @@
+use crate::{MockEtcd, etcdserverpb::cluster_server::Cluster};
+use rcfe::etcdserverpb::*;
+use tonic::{Request, Response, Status};
+
+/// Only `MemberList` is served, reporting the members set with [`MockEtcd::set_members`].
+#[tonic::async_trait]
+impl Cluster for MockEtcd {
+    async fn member_list(
+        &self,
+        _request: Request<MemberListRequest>,
+    ) -> Result<Response<MemberListResponse>, Status> {
+        self.check_available()?;
+        let state = self.state.lock().unwrap();
+
+        Ok(Response::new(MemberListResponse {
+            header: state.header(),
+            members: state.members.clone(),
+        }))
+    }
+
+    async fn member_add(
+        &self,
+        _request: Request<MemberAddRequest>,
+    ) -> Result<Response<MemberAddResponse>, Status> {
+        Err(Status::unimplemented("member_add"))
+    }
+
+    async fn member_remove(
+        &self,
+        _request: Request<MemberRemoveRequest>,
+    ) -> Result<Response<MemberRemoveResponse>, Status> {
+        Err(Status::unimplemented("member_remove"))
+    }
+
+    async fn member_update(
+        &self,
+        _request: Request<MemberUpdateRequest>,
+    ) -> Result<Response<MemberUpdateResponse>, Status> {
+        Err(Status::unimplemented("member_update"))
+    }
+
+    async fn member_promote(
+        &self,
+        _request: Request<MemberPromoteRequest>,
+    ) -> Result<Response<MemberPromoteResponse>, Status> {
+        Err(Status::unimplemented("member_promote"))
+    }
+}
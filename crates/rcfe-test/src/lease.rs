@@ -0,0 +1,105 @@
+use crate::{MockEtcd, etcdserverpb::lease_server::Lease};
+use rcfe::etcdserverpb::*;
+use tonic::{
+    Request, Response, Status, Streaming,
+    codegen::{BoxStream, tokio_stream::wrappers::ReceiverStream},
+};
+
+/// Grants, revokes and keeps alive leases without ever expiring them.
+#[tonic::async_trait]
+impl Lease for MockEtcd {
+    async fn lease_grant(
+        &self,
+        request: Request<LeaseGrantRequest>,
+    ) -> Result<Response<LeaseGrantResponse>, Status> {
+        self.check_available()?;
+        let request = request.into_inner();
+        let mut state = self.state.lock().unwrap();
+
+        let id = match request.id {
+            0 => state.leases.keys().max().map_or(1, |id| id + 1),
+            id if state.leases.contains_key(&id) => {
+                return Err(Status::failed_precondition(
+                    "etcdserver: lease already exists",
+                ));
+            }
+            id => id,
+        };
+        state.leases.insert(id, request.ttl);
+
+        Ok(Response::new(LeaseGrantResponse {
+            header: state.header(),
+            id,
+            ttl: request.ttl,
+            error: String::new(),
+        }))
+    }
+
+    async fn lease_revoke(
+        &self,
+        request: Request<LeaseRevokeRequest>,
+    ) -> Result<Response<LeaseRevokeResponse>, Status> {
+        self.check_available()?;
+        let mut state = self.state.lock().unwrap();
+
+        match state.leases.remove(&request.get_ref().id) {
+            Some(_) => Ok(Response::new(LeaseRevokeResponse {
+                header: state.header(),
+            })),
+            None => Err(Status::not_found("etcdserver: requested lease not found")),
+        }
+    }
+
+    type LeaseKeepAliveStream = BoxStream<LeaseKeepAliveResponse>;
+
+    async fn lease_keep_alive(
+        &self,
+        request: Request<Streaming<LeaseKeepAliveRequest>>,
+    ) -> Result<Response<Self::LeaseKeepAliveStream>, Status> {
+        self.check_available()?;
+        let etcd = self.clone();
+        let mut requests = request.into_inner();
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+
+        tokio::spawn(async move {
+            while let Ok(Some(request)) = requests.message().await {
+                let response = {
+                    let state = etcd.state.lock().unwrap();
+                    LeaseKeepAliveResponse {
+                        header: state.header(),
+                        id: request.id,
+                        ttl: state.leases.get(&request.id).copied().unwrap_or(0),
+                    }
+                };
+
+                if tx.send(Ok(response)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
+    async fn lease_time_to_live(
+        &self,
+        request: Request<LeaseTimeToLiveRequest>,
+    ) -> Result<Response<LeaseTimeToLiveResponse>, Status> {
+        self.check_available()?;
+        let state = self.state.lock().unwrap();
+        let id = request.get_ref().id;
+        let ttl = state.leases.get(&id).copied();
+        let keys = match request.get_ref().keys {
+            true => state.lease_keys(id),
+            false => vec![],
+        };
+
+        Ok(Response::new(LeaseTimeToLiveResponse {
+            header: state.header(),
+            id,
+            ttl: ttl.unwrap_or(-1),
+            granted_ttl: ttl.unwrap_or(0),
+            keys,
+        }))
+    }
+}
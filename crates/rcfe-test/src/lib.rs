@@ -0,0 +1,18 @@
+//! Test utilities shared by the integration tests.
+//!
+//! Most integration tests talk to a real etcd server configured through `TEST_ENDPOINT`.
+//! Transport level features (TLS, fault injection, custom connectors) are tested against
+//! [`MockEtcd`], an in-process stand-in serving the etcd gRPC API from memory.
+
+pub mod etcdserverpb {
+    tonic::include_proto!("etcdserverpb");
+}
+
+mod auth;
+mod cluster;
+mod lease;
+mod maintenance;
+mod mock;
+mod watch;
+
+pub use mock::{MockEtcd, MockServer};
@@ -0,0 +1,71 @@
+use crate::{MockEtcd, etcdserverpb::maintenance_server::Maintenance};
+use rcfe::etcdserverpb::*;
+use tonic::{Request, Response, Status, codegen::BoxStream};
+
+/// Only `Status` is served, which clients use to health check the member.
+#[tonic::async_trait]
+impl Maintenance for MockEtcd {
+    async fn status(
+        &self,
+        _request: Request<StatusRequest>,
+    ) -> Result<Response<StatusResponse>, Status> {
+        self.check_available()?;
+        let state = self.state.lock().unwrap();
+        let header = state.header();
+        let leader = match state.no_leader {
+            true => 0,
+            false => header.as_ref().map_or(0, |h| h.member_id),
+        };
+
+        Ok(Response::new(StatusResponse {
+            leader,
+            raft_term: header.as_ref().map_or(0, |h| h.raft_term),
+            raft_index: header.as_ref().map_or(0, |h| h.revision as u64),
+            raft_applied_index: header.as_ref().map_or(0, |h| h.revision as u64),
+            version: String::from("3.6.0"),
+            header,
+            ..Default::default()
+        }))
+    }
+
+    async fn alarm(
+        &self,
+        _request: Request<AlarmRequest>,
+    ) -> Result<Response<AlarmResponse>, Status> {
+        Err(Status::unimplemented("alarm"))
+    }
+
+    async fn defragment(
+        &self,
+        _request: Request<DefragmentRequest>,
+    ) -> Result<Response<DefragmentResponse>, Status> {
+        Err(Status::unimplemented("defragment"))
+    }
+
+    async fn hash(&self, _request: Request<HashRequest>) -> Result<Response<HashResponse>, Status> {
+        Err(Status::unimplemented("hash"))
+    }
+
+    async fn hash_kv(
+        &self,
+        _request: Request<HashKvRequest>,
+    ) -> Result<Response<HashKvResponse>, Status> {
+        Err(Status::unimplemented("hash_kv"))
+    }
+
+    type SnapshotStream = BoxStream<SnapshotResponse>;
+
+    async fn snapshot(
+        &self,
+        _request: Request<SnapshotRequest>,
+    ) -> Result<Response<Self::SnapshotStream>, Status> {
+        Err(Status::unimplemented("snapshot"))
+    }
+
+    async fn move_leader(
+        &self,
+        _request: Request<MoveLeaderRequest>,
+    ) -> Result<Response<MoveLeaderResponse>, Status> {
+        Err(Status::unimplemented("move_leader"))
+    }
+}
@@ -0,0 +1,684 @@
+use crate::{
+    etcdserverpb::{
+        auth_server::AuthServer,
+        cluster_server::ClusterServer,
+        kv_server::{Kv, KvServer},
+        lease_server::LeaseServer,
+        maintenance_server::MaintenanceServer,
+        watch_server::WatchServer,
+    },
+    watch::MockWatcher,
+};
+use rcfe::{
+    etcdserverpb::{
+        CompactionRequest, CompactionResponse, Compare, DeleteRangeRequest, DeleteRangeResponse,
+        Member, PutRequest, PutResponse, RangeRequest, RangeResponse, RequestOp, ResponseHeader,
+        ResponseOp, TxnRequest, TxnResponse,
+        compare::{CompareResult, CompareTarget, TargetUnion},
+        range_request::SortOrder,
+        request_op::Request,
+        response_op::Response as ResponseUnion,
+    },
+    mvccpb::{Event, KeyValue, event::EventType},
+};
+use prost::Message;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::oneshot,
+    task::JoinHandle,
+};
+use tonic::{
+    Code, Request as GrpcRequest, Response, Status,
+    codec::CompressionEncoding,
+    codegen::tokio_stream::Stream,
+    transport::{
+        Server, ServerTlsConfig,
+        server::{Router, TcpIncoming},
+    },
+};
+
+const CLUSTER_ID: u64 = 1;
+const MEMBER_ID: u64 = 1;
+
+/// An in-memory stand-in for the etcd KV, Auth, Cluster, Lease, Maintenance and Watch services.
+///
+/// Supports ranges, puts, deletes, compare-and-swap transactions and keeps a single
+/// monotonically increasing revision like etcd does. Ranges can read historical revisions,
+/// which are never compacted.
+/// Once a user is added, every KV request must carry a token issued by `Authenticate`.
+#[derive(Clone, Default)]
+pub struct MockEtcd {
+    pub(crate) state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+pub(crate) struct State {
+    revision: i64,
+    kvs: BTreeMap<Vec<u8>, KeyValue>,
+    /// Every change of a key with its revision, `None` for deletions.
+    history: Vec<(i64, Vec<u8>, Option<KeyValue>)>,
+    pub(crate) users: HashMap<String, String>,
+    pub(crate) tokens: HashSet<String>,
+    pub(crate) issued_tokens: usize,
+    delay: Option<Duration>,
+    failures: VecDeque<Status>,
+    dropped_responses: usize,
+    requests: usize,
+    unavailable: bool,
+    max_txn_ops: Option<usize>,
+    max_request_bytes: Option<usize>,
+    pub(crate) no_leader: bool,
+    pub(crate) members: Vec<Member>,
+    pub(crate) leases: BTreeMap<i64, i64>,
+    pub(crate) watchers: Vec<MockWatcher>,
+}
+
+impl MockEtcd {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current revision of the store.
+    pub fn revision(&self) -> i64 {
+        self.state.lock().unwrap().revision
+    }
+
+    /// Returns the stored key-value pair for the given key.
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Option<KeyValue> {
+        self.state.lock().unwrap().kvs.get(key.as_ref()).cloned()
+    }
+
+    /// Puts a key-value pair directly into the store, bypassing the gRPC services.
+    pub fn put(&self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) {
+        let mut state = self.state.lock().unwrap();
+        state.revision += 1;
+        let revision = state.revision;
+        let request = PutRequest {
+            key: key.as_ref().to_vec(),
+            value: value.as_ref().to_vec(),
+            ..Default::default()
+        };
+        state.put(&request, revision);
+    }
+
+    /// Enables authentication and adds a user.
+    pub fn with_user(self, user: &str, password: &str) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .users
+            .insert(user.to_string(), password.to_string());
+        self
+    }
+
+    /// Invalidates every issued token, as if they expired on the server.
+    pub fn expire_tokens(&self) {
+        self.state.lock().unwrap().tokens.clear();
+    }
+
+    /// Returns how many tokens were issued by `Authenticate`.
+    pub fn issued_tokens(&self) -> usize {
+        self.state.lock().unwrap().issued_tokens
+    }
+
+    /// Delays every KV request by the given duration, simulating a hung member.
+    pub fn set_delay(&self, delay: Option<Duration>) {
+        self.state.lock().unwrap().delay = delay;
+    }
+
+    /// Fails the next `count` KV requests with the given status code, as a member
+    /// losing its leader or shutting down would.
+    pub fn fail_next(&self, count: usize, code: Code) {
+        let mut state = self.state.lock().unwrap();
+        for _ in 0..count {
+            state
+                .failures
+                .push_back(Status::new(code, "injected failure"));
+        }
+    }
+
+    /// Applies the next `count` KV writes but fails them with `Unavailable`, as if the
+    /// connection broke before the response arrived.
+    pub fn drop_next_responses(&self, count: usize) {
+        self.state.lock().unwrap().dropped_responses += count;
+    }
+
+    /// Returns how many KV requests were received, including failed ones.
+    pub fn requests(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+
+    /// Makes the member reject every request with `Unavailable`, as a member that
+    /// lost its connection to the cluster would.
+    pub fn set_available(&self, available: bool) {
+        self.state.lock().unwrap().unavailable = !available;
+    }
+
+    /// Makes the member lose or regain its leader. Without a leader, KV requests and
+    /// watches carrying the `hasleader` metadata fail like they do on etcd, others are
+    /// still served.
+    pub fn set_leader(&self, has_leader: bool) {
+        self.state.lock().unwrap().no_leader = !has_leader;
+    }
+
+    /// Limits the operations per transaction, like the `--max-txn-ops` of etcd.
+    pub fn set_max_txn_ops(&self, max_txn_ops: Option<usize>) {
+        self.state.lock().unwrap().max_txn_ops = max_txn_ops;
+    }
+
+    /// Limits the encoded size of transactions, like the `--max-request-bytes` of etcd.
+    pub fn set_max_request_bytes(&self, max_request_bytes: Option<usize>) {
+        self.state.lock().unwrap().max_request_bytes = max_request_bytes;
+    }
+
+    /// Returns the IDs of the leases that were granted and not revoked.
+    pub fn leases(&self) -> Vec<i64> {
+        self.state.lock().unwrap().leases.keys().copied().collect()
+    }
+
+    /// Sets the members reported by `MemberList`.
+    pub fn set_members(&self, members: Vec<Member>) {
+        self.state.lock().unwrap().members = members;
+    }
+
+    /// Returns a router serving this store, so tests can add more services.
+    pub fn router(&self) -> Router {
+        self.routes(Server::builder())
+    }
+
+    /// Adds the services of this store to the server.
+    fn routes(&self, mut server: Server) -> Router {
+        server
+            .add_service(
+                KvServer::new(self.clone())
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX)
+                    .max_encoding_message_size(usize::MAX),
+            )
+            .add_service(AuthServer::new(self.clone()))
+            .add_service(LeaseServer::new(self.clone()))
+            .add_service(MaintenanceServer::new(self.clone()))
+            .add_service(ClusterServer::new(self.clone()))
+            .add_service(WatchServer::new(self.clone()))
+    }
+
+    /// Returns an error if the member was made unavailable.
+    pub(crate) fn check_available(&self) -> Result<(), Status> {
+        match self.state.lock().unwrap().unavailable {
+            true => Err(Status::unavailable("etcdserver: mock member is unavailable")),
+            false => Ok(()),
+        }
+    }
+
+    /// Rejects a request requiring a leader while the member has none.
+    pub(crate) fn check_leader<T>(&self, request: &GrpcRequest<T>) -> Result<(), Status> {
+        let require_leader = request.metadata().get("hasleader").is_some_and(|v| v == "true");
+        match require_leader && self.state.lock().unwrap().no_leader {
+            true => Err(Status::unavailable("etcdserver: no leader")),
+            false => Ok(()),
+        }
+    }
+
+    /// Counts the request and applies the availability, the configured delay and injected failures.
+    async fn inject_faults(&self) -> Result<(), Status> {
+        let (delay, failure) = {
+            let mut state = self.state.lock().unwrap();
+            state.requests += 1;
+            (state.delay, state.failures.pop_front())
+        };
+        self.check_available()?;
+
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        match failure {
+            Some(status) => Err(status),
+            None => Ok(()),
+        }
+    }
+
+    /// Locks the state, checking the leader requirement and, if auth is enabled,
+    /// the auth token of the request.
+    fn authorize<T>(&self, request: &GrpcRequest<T>) -> Result<MutexGuard<'_, State>, Status> {
+        self.check_leader(request)?;
+        let state = self.state.lock().unwrap();
+        if state.users.is_empty() {
+            return Ok(state);
+        }
+
+        match request.metadata().get("token").and_then(|t| t.to_str().ok()) {
+            None => Err(Status::invalid_argument("etcdserver: user name is empty")),
+            Some(token) if !state.tokens.contains(token) => {
+                Err(Status::unauthenticated("etcdserver: invalid auth token"))
+            }
+            Some(_) => Ok(state),
+        }
+    }
+}
+
+impl State {
+    /// Fails an applied write if its response is to be dropped.
+    fn drop_response(&mut self) -> Result<(), Status> {
+        if self.dropped_responses == 0 {
+            return Ok(());
+        }
+        self.dropped_responses -= 1;
+        Err(Status::unavailable("injected failure after the write was applied"))
+    }
+
+    pub(crate) fn header(&self) -> Option<ResponseHeader> {
+        Some(ResponseHeader {
+            cluster_id: CLUSTER_ID,
+            member_id: MEMBER_ID,
+            revision: self.revision,
+            raft_term: 1,
+        })
+    }
+
+    /// Sends the event to the watchers of its key, dropping the watchers of closed streams.
+    fn notify(&mut self, event: Event) {
+        let header = self.header();
+        self.watchers
+            .retain(|watcher| watcher.notify(header, &event));
+    }
+
+    /// Returns the keys attached to the lease.
+    pub(crate) fn lease_keys(&self, lease: i64) -> Vec<Vec<u8>> {
+        self.kvs
+            .values()
+            .filter(|kv| kv.lease == lease)
+            .map(|kv| kv.key.clone())
+            .collect()
+    }
+
+    fn matching_keys(&self, key: &[u8], range_end: &[u8]) -> Vec<Vec<u8>> {
+        Self::matching(&self.kvs, key, range_end)
+    }
+
+    fn matching(
+        kvs: &BTreeMap<Vec<u8>, KeyValue>,
+        key: &[u8],
+        range_end: &[u8],
+    ) -> Vec<Vec<u8>> {
+        if range_end.is_empty() {
+            return kvs.get(key).map(|kv| vec![kv.key.clone()]).unwrap_or_default();
+        }
+
+        kvs.keys()
+            .filter(|k| k.as_slice() >= key && (range_end == [0] || k.as_slice() < range_end))
+            .cloned()
+            .collect()
+    }
+
+    /// Replays the history up to the revision, `None` for the current revision.
+    fn at_revision(&self, revision: i64) -> Option<BTreeMap<Vec<u8>, KeyValue>> {
+        if revision <= 0 || revision >= self.revision {
+            return None;
+        }
+        let mut kvs = BTreeMap::new();
+        for (_, key, kv) in self.history.iter().take_while(|(rev, _, _)| *rev <= revision) {
+            match kv {
+                Some(kv) => kvs.insert(key.clone(), kv.clone()),
+                None => kvs.remove(key),
+            };
+        }
+        Some(kvs)
+    }
+
+    fn range(&self, request: &RangeRequest) -> RangeResponse {
+        let snapshot = self.at_revision(request.revision);
+        let store = snapshot.as_ref().unwrap_or(&self.kvs);
+        let mut kvs: Vec<KeyValue> = Self::matching(store, &request.key, &request.range_end)
+            .into_iter()
+            .filter_map(|k| store.get(&k).cloned())
+            .collect();
+
+        if request.sort_order == SortOrder::Descend as i32 {
+            kvs.reverse();
+        }
+
+        let count = kvs.len() as i64;
+        let more = request.limit > 0 && count > request.limit;
+        if request.limit > 0 {
+            kvs.truncate(request.limit as usize);
+        }
+
+        if request.keys_only {
+            kvs.iter_mut().for_each(|kv| kv.value.clear());
+        }
+
+        if request.count_only {
+            kvs.clear();
+        }
+
+        RangeResponse {
+            header: self.header(),
+            kvs,
+            more,
+            count,
+        }
+    }
+
+    fn put(&mut self, request: &PutRequest, revision: i64) -> PutResponse {
+        let prev = self.kvs.get(&request.key).cloned();
+        let kv = match &prev {
+            Some(prev) => KeyValue {
+                key: request.key.clone(),
+                create_revision: prev.create_revision,
+                mod_revision: revision,
+                version: prev.version + 1,
+                value: match request.ignore_value {
+                    true => prev.value.clone(),
+                    false => request.value.clone(),
+                },
+                lease: match request.ignore_lease {
+                    true => prev.lease,
+                    false => request.lease,
+                },
+            },
+            None => KeyValue {
+                key: request.key.clone(),
+                create_revision: revision,
+                mod_revision: revision,
+                version: 1,
+                value: request.value.clone(),
+                lease: request.lease,
+            },
+        };
+        self.kvs.insert(request.key.clone(), kv.clone());
+        self.history
+            .push((revision, request.key.clone(), Some(kv.clone())));
+        self.notify(Event {
+            r#type: EventType::Put as i32,
+            kv: Some(kv),
+            prev_kv: prev.clone(),
+        });
+
+        PutResponse {
+            header: None,
+            prev_kv: prev.filter(|_| request.prev_kv),
+        }
+    }
+
+    fn delete(&mut self, request: &DeleteRangeRequest) -> DeleteRangeResponse {
+        let prev_kvs: Vec<KeyValue> = self
+            .matching_keys(&request.key, &request.range_end)
+            .into_iter()
+            .filter_map(|k| self.kvs.remove(&k))
+            .collect();
+        for prev in &prev_kvs {
+            self.history
+                .push((self.revision + 1, prev.key.clone(), None));
+            self.notify(Event {
+                r#type: EventType::Delete as i32,
+                kv: Some(KeyValue {
+                    key: prev.key.clone(),
+                    mod_revision: self.revision + 1,
+                    ..Default::default()
+                }),
+                prev_kv: Some(prev.clone()),
+            });
+        }
+
+        DeleteRangeResponse {
+            header: None,
+            deleted: prev_kvs.len() as i64,
+            prev_kvs: match request.prev_kv {
+                true => prev_kvs,
+                false => vec![],
+            },
+        }
+    }
+
+    fn compare(&self, compare: &Compare) -> bool {
+        let kvs: Vec<Option<&KeyValue>> = match compare.range_end.is_empty() {
+            true => vec![self.kvs.get(&compare.key)],
+            false => self
+                .matching_keys(&compare.key, &compare.range_end)
+                .iter()
+                .map(|k| self.kvs.get(k))
+                .collect(),
+        };
+
+        kvs.into_iter().all(|kv| {
+            let ordering = match (compare.target(), &compare.target_union) {
+                (CompareTarget::Version, Some(TargetUnion::Version(v))) => {
+                    kv.map_or(0, |kv| kv.version).cmp(v)
+                }
+                (CompareTarget::Create, Some(TargetUnion::CreateRevision(v))) => {
+                    kv.map_or(0, |kv| kv.create_revision).cmp(v)
+                }
+                (CompareTarget::Mod, Some(TargetUnion::ModRevision(v))) => {
+                    kv.map_or(0, |kv| kv.mod_revision).cmp(v)
+                }
+                (CompareTarget::Value, Some(TargetUnion::Value(v))) => match kv {
+                    Some(kv) => kv.value.cmp(v),
+                    None => return false,
+                },
+                _ => return false,
+            };
+
+            match compare.result() {
+                CompareResult::Equal => ordering.is_eq(),
+                CompareResult::Greater => ordering.is_gt(),
+                CompareResult::Less => ordering.is_lt(),
+                CompareResult::NotEqual => ordering.is_ne(),
+            }
+        })
+    }
+
+    fn is_write(ops: &[RequestOp]) -> bool {
+        ops.iter()
+            .any(|op| !matches!(op.request, Some(Request::RequestRange(_))))
+    }
+
+    fn txn(&mut self, request: &TxnRequest) -> Result<TxnResponse, Status> {
+        let ops = request.success.len().max(request.failure.len());
+        if self.max_txn_ops.is_some_and(|max| ops > max) {
+            return Err(Status::invalid_argument(
+                "etcdserver: too many operations in txn request",
+            ));
+        }
+        if self
+            .max_request_bytes
+            .is_some_and(|max| request.encoded_len() > max)
+        {
+            return Err(Status::invalid_argument("etcdserver: request is too large"));
+        }
+
+        let succeeded = request.compare.iter().all(|c| self.compare(c));
+        let ops = match succeeded {
+            true => &request.success,
+            false => &request.failure,
+        };
+
+        let revision = match Self::is_write(ops) {
+            true => self.revision + 1,
+            false => self.revision,
+        };
+
+        let mut responses = Vec::with_capacity(ops.len());
+        for op in ops {
+            let response = match &op.request {
+                Some(Request::RequestRange(r)) => ResponseUnion::ResponseRange(self.range(r)),
+                Some(Request::RequestPut(r)) => ResponseUnion::ResponsePut(self.put(r, revision)),
+                Some(Request::RequestDeleteRange(r)) => {
+                    ResponseUnion::ResponseDeleteRange(self.delete(r))
+                }
+                _ => return Err(Status::unimplemented("nested transactions")),
+            };
+            responses.push(ResponseOp {
+                response: Some(response),
+            });
+        }
+
+        self.revision = revision;
+        Ok(TxnResponse {
+            header: self.header(),
+            succeeded,
+            responses,
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Kv for MockEtcd {
+    async fn range(
+        &self,
+        request: GrpcRequest<RangeRequest>,
+    ) -> Result<Response<RangeResponse>, Status> {
+        self.inject_faults().await?;
+        let state = self.authorize(&request)?;
+        Ok(Response::new(state.range(request.get_ref())))
+    }
+
+    async fn put(&self, request: GrpcRequest<PutRequest>) -> Result<Response<PutResponse>, Status> {
+        self.inject_faults().await?;
+        let mut state = self.authorize(&request)?;
+        state.revision += 1;
+        let revision = state.revision;
+        let mut response = state.put(request.get_ref(), revision);
+        response.header = state.header();
+        state.drop_response()?;
+        Ok(Response::new(response))
+    }
+
+    async fn delete_range(
+        &self,
+        request: GrpcRequest<DeleteRangeRequest>,
+    ) -> Result<Response<DeleteRangeResponse>, Status> {
+        self.inject_faults().await?;
+        let mut state = self.authorize(&request)?;
+        let mut response = state.delete(request.get_ref());
+        if response.deleted > 0 {
+            state.revision += 1;
+        }
+        response.header = state.header();
+        state.drop_response()?;
+        Ok(Response::new(response))
+    }
+
+    async fn txn(&self, request: GrpcRequest<TxnRequest>) -> Result<Response<TxnResponse>, Status> {
+        self.inject_faults().await?;
+        let mut state = self.authorize(&request)?;
+        let response = state.txn(request.get_ref())?;
+        state.drop_response()?;
+        Ok(Response::new(response))
+    }
+
+    async fn compact(
+        &self,
+        request: GrpcRequest<CompactionRequest>,
+    ) -> Result<Response<CompactionResponse>, Status> {
+        self.inject_faults().await?;
+        let state = self.authorize(&request)?;
+        Ok(Response::new(CompactionResponse {
+            header: state.header(),
+        }))
+    }
+}
+
+/// A running in-process server, stopped when dropped.
+pub struct MockServer {
+    addr: Option<SocketAddr>,
+    endpoint: String,
+    socket: Option<PathBuf>,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: JoinHandle<Result<(), tonic::transport::Error>>,
+}
+
+/// Connections accepted on a unix domain socket.
+struct UnixIncoming(UnixListener);
+
+impl Stream for UnixIncoming {
+    type Item = std::io::Result<UnixStream>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0
+            .poll_accept(cx)
+            .map(|result| Some(result.map(|(stream, _)| stream)))
+    }
+}
+
+impl MockServer {
+    /// Serves the router on a random local port over plain HTTP/2.
+    pub fn start(router: Router) -> Self {
+        let incoming = TcpIncoming::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .expect("failed to bind mock server");
+        let addr = incoming.local_addr().expect("failed to read local address");
+        let (shutdown, rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(router.serve_with_incoming_shutdown(incoming, async {
+            rx.await.ok();
+        }));
+
+        MockServer {
+            addr: Some(addr),
+            endpoint: format!("http://{addr}"),
+            socket: None,
+            shutdown: Some(shutdown),
+            handle,
+        }
+    }
+
+    /// Serves the router on a unix domain socket at the given path.
+    pub fn start_unix(router: Router, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path).expect("failed to bind mock server socket");
+        let (shutdown, rx) = oneshot::channel::<()>();
+        let incoming = UnixIncoming(listener);
+        let handle = tokio::spawn(router.serve_with_incoming_shutdown(incoming, async {
+            rx.await.ok();
+        }));
+
+        MockServer {
+            addr: None,
+            endpoint: format!("unix://{}", path.display()),
+            socket: Some(path),
+            shutdown: Some(shutdown),
+            handle,
+        }
+    }
+
+    /// Serves the store on a random local port using the given TLS configuration.
+    pub fn start_tls(etcd: MockEtcd, tls: ServerTlsConfig) -> Self {
+        let server = Server::builder()
+            .tls_config(tls)
+            .expect("invalid server TLS configuration");
+        Self::start(etcd.routes(server))
+    }
+
+    /// Returns the local address of the server.
+    /// # Panics
+    /// Panics if the server listens on a unix domain socket.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr.expect("server does not listen on TCP")
+    }
+
+    /// Returns an `http://` endpoint for the server, or a `unix://` one for a socket.
+    pub fn endpoint(&self) -> String {
+        self.endpoint.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+        self.handle.abort();
+        if let Some(socket) = &self.socket {
+            std::fs::remove_file(socket).ok();
+        }
+    }
+}
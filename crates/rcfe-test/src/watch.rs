@@ -0,0 +1,132 @@
+use crate::{MockEtcd, etcdserverpb::watch_server::Watch};
+use rcfe::{
+    etcdserverpb::{watch_request::RequestUnion, *},
+    mvccpb::Event,
+};
+use tokio::sync::mpsc::Sender;
+use tonic::{
+    Request, Response, Status, Streaming,
+    codegen::{BoxStream, tokio_stream::wrappers::ReceiverStream},
+};
+
+/// A watch created on a watch stream, notified of the events on its key range.
+pub(crate) struct MockWatcher {
+    id: i64,
+    key: Vec<u8>,
+    range_end: Vec<u8>,
+    prev_kv: bool,
+    sender: Sender<Result<WatchResponse, Status>>,
+}
+
+impl MockWatcher {
+    fn matches(&self, key: &[u8]) -> bool {
+        match self.range_end.as_slice() {
+            [] => key == self.key,
+            [0] => key >= self.key.as_slice(),
+            end => key >= self.key.as_slice() && key < end,
+        }
+    }
+
+    /// Sends the event if it is on the key range, returns false once the stream is closed.
+    pub(crate) fn notify(&self, header: Option<ResponseHeader>, event: &Event) -> bool {
+        let Some(kv) = &event.kv else {
+            return true;
+        };
+        if self.matches(&kv.key) {
+            let mut event = event.clone();
+            if !self.prev_kv {
+                event.prev_kv = None;
+            }
+            let response = WatchResponse {
+                header,
+                watch_id: self.id,
+                events: vec![event],
+                ..Default::default()
+            };
+            // Events are dropped if the client does not keep up, like a slow etcd watcher.
+            let _ = self.sender.try_send(Ok(response));
+        }
+        !self.sender.is_closed()
+    }
+}
+
+/// Acknowledges watch creation and cancellation and sends the put and delete events
+/// of the watched key ranges.
+#[tonic::async_trait]
+impl Watch for MockEtcd {
+    type WatchStream = BoxStream<WatchResponse>;
+
+    async fn watch(
+        &self,
+        request: Request<Streaming<WatchRequest>>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        self.check_available()?;
+        self.check_leader(&request)?;
+        let header = self.state.lock().unwrap().header();
+
+        let mut requests = request.into_inner();
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            let mut next_id = 0;
+            while let Ok(Some(request)) = requests.message().await {
+                let (response, watcher) = match request.request_union {
+                    Some(RequestUnion::CreateRequest(create)) => {
+                        next_id += 1;
+                        let watcher = MockWatcher {
+                            id: next_id - 1,
+                            key: create.key,
+                            range_end: create.range_end,
+                            prev_kv: create.prev_kv,
+                            sender: tx.clone(),
+                        };
+                        let response = WatchResponse {
+                            header,
+                            watch_id: next_id - 1,
+                            created: true,
+                            ..Default::default()
+                        };
+                        (response, Some(watcher))
+                    }
+                    Some(RequestUnion::CancelRequest(cancel)) => {
+                        state.lock().unwrap().watchers.retain(|watcher| {
+                            watcher.id != cancel.watch_id || !watcher.sender.same_channel(&tx)
+                        });
+                        let response = WatchResponse {
+                            header,
+                            watch_id: cancel.watch_id,
+                            canceled: true,
+                            ..Default::default()
+                        };
+                        (response, None)
+                    }
+                    _ => {
+                        let response = WatchResponse {
+                            header,
+                            watch_id: -1,
+                            ..Default::default()
+                        };
+                        (response, None)
+                    }
+                };
+
+                if tx.send(Ok(response)).await.is_err() {
+                    break;
+                }
+                // Registered after the created response, so that no event precedes it.
+                if let Some(watcher) = watcher {
+                    state.lock().unwrap().watchers.push(watcher);
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
+    async fn progress(
+        &self,
+        _request: Request<WatchProgressRequest>,
+    ) -> Result<Response<WatchResponse>, Status> {
+        Err(Status::unimplemented("progress"))
+    }
+}
@@ -0,0 +1,88 @@
+use rcfe::{Client, ClientFactory, ClientOptions, DefaultClient, DefaultClientFactory, Error, KVClient};
+use rcfe_test::{MockEtcd, MockServer};
+
+fn options(server: &MockServer, password: &str) -> ClientOptions {
+    ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .credentials("root", password)
+        .build()
+}
+
+#[tokio::test]
+async fn test_authenticate_on_connect() -> Result<(), Error> {
+    let etcd = MockEtcd::new().with_user("root", "secret");
+    let server = MockServer::start(etcd.router());
+
+    let client = DefaultClientFactory::new()
+        .create(options(&server, "secret"))
+        .await?;
+    assert_eq!(etcd.issued_tokens(), 1);
+
+    let mut kv_client = client.get_kv_client();
+    kv_client.put("auth_key", "auth_value").await?;
+    let response = kv_client.get("auth_key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"auth_value".to_vec());
+    assert_eq!(etcd.issued_tokens(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_authenticate_with_wrong_password() {
+    let etcd = MockEtcd::new().with_user("root", "secret");
+    let server = MockServer::start(etcd.router());
+
+    let result = DefaultClientFactory::new()
+        .create(options(&server, "wrong"))
+        .await;
+    assert!(matches!(result, Err(Error::TonicStatus(_))));
+}
+
+#[tokio::test]
+async fn test_reauthenticate_on_expired_token() -> Result<(), Error> {
+    let etcd = MockEtcd::new().with_user("root", "secret");
+    let server = MockServer::start(etcd.router());
+
+    let client = DefaultClientFactory::new()
+        .create(options(&server, "secret"))
+        .await?;
+    let mut kv_client = client.get_kv_client();
+    kv_client.put("auth_key", "v1").await?;
+
+    etcd.expire_tokens();
+
+    kv_client.put("auth_key", "v2").await?;
+    assert_eq!(etcd.issued_tokens(), 2);
+    assert_eq!(etcd.get("auth_key").unwrap().value, b"v2".to_vec());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lazy_authentication() -> Result<(), Error> {
+    let etcd = MockEtcd::new().with_user("root", "secret");
+    let server = MockServer::start(etcd.router());
+
+    let client = DefaultClient::new(options(&server, "secret"))?;
+    assert_eq!(etcd.issued_tokens(), 0);
+
+    client.get_kv_client().get("auth_key").await?;
+    assert_eq!(etcd.issued_tokens(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_without_credentials_is_rejected() -> Result<(), Error> {
+    let etcd = MockEtcd::new().with_user("root", "secret");
+    let server = MockServer::start(etcd.router());
+
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .build();
+    let client = DefaultClientFactory::new().create(options).await?;
+
+    assert!(client.get_kv_client().get("auth_key").await.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use rcfe::{
+    ByteSequence, ClientOptions, Compare, Error, RequestOp, WatchCreateOptions, WatchRequestType,
+    blocking::BlockingClient,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Serves the store on a runtime of its own, the blocking client must not run within one.
+fn start_server(etcd: &MockEtcd) -> (Runtime, MockServer) {
+    let runtime = Runtime::new().expect("Failed to start server runtime");
+    let server = {
+        let _guard = runtime.enter();
+        MockServer::start(etcd.router())
+    };
+    (runtime, server)
+}
+
+fn create_client(server: &MockServer) -> BlockingClient {
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .build();
+    BlockingClient::connect(options).expect("Failed to create client")
+}
+
+#[test]
+fn test_blocking_kv() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let (_runtime, server) = start_server(&etcd);
+    let client = create_client(&server);
+
+    client.put("blocking_key", "value")?;
+    let response = client.get("blocking_key")?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"value");
+
+    let key = ByteSequence::from("blocking_key");
+    let response = client
+        .txn()
+        .when(vec![Compare::version_eq(key.clone(), 1)])?
+        .then(vec![RequestOp::Delete { key, options: None }])?
+        .commit()?;
+    assert!(response.get_ref().succeeded);
+    assert!(client.get("blocking_key")?.is_empty());
+
+    client.put("blocking_key", "value")?;
+    assert_eq!(client.delete("blocking_key")?.deleted(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_blocking_lease() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let (_runtime, server) = start_server(&etcd);
+    let client = create_client(&server);
+
+    let lease_id = client.grant(Duration::from_secs(10))?.get_ref().id;
+    assert_eq!(client.time_to_live(lease_id)?.get_ref().ttl, 10);
+
+    let mut keep_alive = client.keep_alive(lease_id)?;
+    assert_eq!(keep_alive.lease_id(), lease_id);
+    assert_eq!(keep_alive.keep_alive()?.id, lease_id);
+
+    client.revoke(lease_id)?;
+    assert!(etcd.leases().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_blocking_watch_iterator() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let (_runtime, server) = start_server(&etcd);
+    let client = create_client(&server);
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("watched_key"))
+            .build()?,
+    );
+    let mut watcher = client.watch(request)?;
+    watcher.cancel()?;
+
+    let response = watcher.next().expect("cancel response")?;
+    assert!(response.canceled);
+    assert_eq!(response.watch_id, watcher.id());
+
+    client.shutdown()?;
+    assert!(matches!(client.get("watched_key"), Err(Error::ClientClosed)));
+
+    Ok(())
+}
+
+#[test]
+fn test_blocking_watch_iterator_ends_on_shutdown() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let (_runtime, server) = start_server(&etcd);
+    let client = create_client(&server);
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("watched_key"))
+            .build()?,
+    );
+    let mut watcher = client.watch(request)?;
+
+    client.shutdown()?;
+    assert!(watcher.next().is_none());
+
+    Ok(())
+}
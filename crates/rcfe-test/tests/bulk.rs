@@ -0,0 +1,170 @@
+use rcfe::{
+    BulkOptions, ByteSequence, Client, ClientFactory, ClientOptions, DefaultClient,
+    DefaultClientFactory, Error, KVClient, KeyRange,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use tonic::Code;
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+fn sequential() -> BulkOptions {
+    BulkOptions::builder().concurrency(1).build().unwrap()
+}
+
+#[tokio::test]
+async fn test_put_and_delete_many() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    etcd.set_max_txn_ops(Some(128));
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let kvs = (0..300).map(|i| (format!("bulk/{i:03}"), format!("value-{i}")));
+    let result = kv_client.put_many(kvs, BulkOptions::default()).await;
+    assert!(result.is_ok(), "{:?}", result.error());
+    assert_eq!(result.succeeded(), 300);
+    let ranges: Vec<_> = result.batches().iter().map(|batch| batch.range()).collect();
+    assert_eq!(ranges, vec![0..128, 128..256, 256..300]);
+    assert_eq!(etcd.requests(), 3);
+
+    let stored = kv_client.get(KeyRange::prefix("bulk/")).await?;
+    assert_eq!(stored.count(), 300);
+    assert_eq!(stored.kvs()[299].value, ByteSequence::from("value-299"));
+    // Every operation of a batch shares its revision.
+    assert_eq!(stored.kvs()[0].mod_revision, stored.kvs()[127].mod_revision);
+
+    let keys = (0..300).map(|i| format!("bulk/{i:03}"));
+    let result = kv_client.delete_many(keys, BulkOptions::default()).await;
+    assert!(result.is_ok());
+    assert_eq!(result.deleted(), 300);
+    assert_eq!(result.batches()[2].deleted(), 44);
+    assert!(kv_client.get(KeyRange::prefix("bulk/")).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_request_size_limit() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    etcd.set_max_request_bytes(Some(4096));
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let value = "x".repeat(1000);
+    let kvs = (0..10).map(|i| (format!("big/{i}"), value.clone()));
+    let options = BulkOptions::builder()
+        .max_txn_bytes(4096)
+        .concurrency(1)
+        .build()?;
+    let result = kv_client.put_many(kvs, options.clone()).await;
+    assert!(result.is_ok(), "{:?}", result.error());
+    assert!(result.batches().iter().all(|batch| batch.len() <= 4));
+    assert_eq!(kv_client.get(KeyRange::prefix("big/")).await?.count(), 10);
+
+    // An operation over the limit is sent alone and fails on its own.
+    let kvs = vec![
+        ("huge/0", value.clone()),
+        ("huge/1", "x".repeat(5000)),
+        ("huge/2", value.clone()),
+    ];
+    let result = kv_client.put_many(kvs, options).await;
+    assert!(!result.is_ok());
+    assert_eq!(result.succeeded(), 2);
+    let failed: Vec<_> = result.failed().map(|batch| batch.range()).collect();
+    assert_eq!(failed, vec![1..2]);
+    assert!(etcd.get("huge/0").is_some());
+    assert!(etcd.get("huge/1").is_none());
+    assert!(etcd.get("huge/2").is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_partial_failure() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.fail_next(1, Code::Unavailable);
+    let kvs = (0..6).map(|i| (format!("key/{i}"), "value"));
+    let options = BulkOptions::builder()
+        .max_txn_ops(2)
+        .concurrency(1)
+        .build()?;
+    let result = kv_client.put_many(kvs, options).await;
+    assert_eq!(result.batches().len(), 3);
+    assert!(!result.batches()[0].is_ok());
+    assert!(result.batches()[0].header().is_none());
+    assert!(result.batches()[1].header().unwrap().revision > 0);
+    assert_eq!(result.succeeded(), 4);
+    assert!(etcd.get("key/0").is_none());
+    assert!(etcd.get("key/5").is_some());
+    assert!(matches!(
+        result.into_result(),
+        Err(Error::TonicStatus(status)) if status.code() == Code::Unavailable
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_repeated_keys() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    // A transaction cannot write a key twice, so a repeated key starts a new batch.
+    let kvs = vec![("a", "1"), ("b", "1"), ("a", "2")];
+    let result = kv_client.put_many(kvs, sequential()).await.into_result()?;
+    let ranges: Vec<_> = result.batches().iter().map(|batch| batch.range()).collect();
+    assert_eq!(ranges, vec![0..2, 2..3]);
+    assert_eq!(etcd.get("a").unwrap().value, b"2");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_bulk() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await.with_namespace("tenant/");
+    let mut kv_client = client.get_kv_client();
+
+    let kvs = (0..5).map(|i| (format!("key/{i}"), "value"));
+    kv_client.put_many(kvs, sequential()).await.into_result()?;
+    assert!(etcd.get("tenant/key/4").is_some());
+
+    let keys = (0..5).map(|i| format!("key/{i}"));
+    let result = kv_client.delete_many(keys, sequential()).await;
+    assert_eq!(result.deleted(), 5);
+    assert!(etcd.get("tenant/key/4").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_bulk_options() {
+    let options = BulkOptions::default();
+    assert_eq!(options.max_txn_ops(), 128);
+    assert_eq!(options.max_txn_bytes(), 1536 * 1024);
+
+    for builder in [
+        BulkOptions::builder().max_txn_ops(0),
+        BulkOptions::builder().max_txn_bytes(0),
+        BulkOptions::builder().concurrency(0),
+    ] {
+        assert!(matches!(builder.build(), Err(Error::IllegalArgument(_))));
+    }
+}
@@ -0,0 +1,143 @@
+use rcfe::{
+    ByteSequence, CasOutcome, Client, ClientFactory, ClientOptions, DefaultClient,
+    DefaultClientFactory, Error, KVClient, PutOptions,
+};
+use rcfe_test::{MockEtcd, MockServer};
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_create() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let outcome = kv_client.create("leader", "node-1").await?;
+    assert_eq!(outcome, CasOutcome::Applied { revision: 1 });
+    assert_eq!(etcd.get("leader").unwrap().value, b"node-1");
+
+    let outcome = kv_client.create("leader", "node-2").await?;
+    assert!(!outcome.is_applied());
+    assert_eq!(outcome.revision(), None);
+    let current = outcome.current().expect("current leader");
+    assert_eq!(current.value, ByteSequence::from("node-1"));
+    assert_eq!(current.mod_revision, 1);
+    assert_eq!(etcd.get("leader").unwrap().value, b"node-1");
+
+    let options = PutOptions::builder().lease(42).build();
+    let outcome = kv_client
+        .create_with_options("lock", "owner", options)
+        .await?;
+    assert!(outcome.is_applied());
+    assert_eq!(etcd.get("lock").unwrap().lease, 42);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compare_and_swap() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let revision = kv_client.put("config", "v1").await?.header().revision;
+
+    let outcome = kv_client.compare_and_swap("config", revision, "v2").await?;
+    let swapped = outcome.revision().expect("applied");
+    assert_eq!(etcd.get("config").unwrap().mod_revision, swapped);
+
+    // The revision of the first read is stale now.
+    let outcome = kv_client.compare_and_swap("config", revision, "v3").await?;
+    match outcome {
+        CasOutcome::Conflict {
+            current: Some(current),
+        } => {
+            assert_eq!(current.value, ByteSequence::from("v2"));
+            assert_eq!(current.mod_revision, swapped);
+        }
+        other => panic!("expected a conflict, got {other:?}"),
+    }
+    assert_eq!(etcd.get("config").unwrap().value, b"v2");
+
+    // An expected revision of 0 creates the key.
+    assert!(
+        kv_client
+            .compare_and_swap("new", 0, "v1")
+            .await?
+            .is_applied()
+    );
+    assert!(
+        !kv_client
+            .compare_and_swap("new", 0, "v2")
+            .await?
+            .is_applied()
+    );
+    assert_eq!(
+        kv_client.compare_and_swap("missing", 1, "v1").await?,
+        CasOutcome::Conflict { current: None }
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compare_and_delete() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let revision = kv_client.put("session", "a").await?.header().revision;
+    kv_client.put("session", "b").await?;
+
+    let outcome = kv_client.compare_and_delete("session", revision).await?;
+    assert_eq!(
+        outcome.current().map(|kv| kv.value.clone()),
+        Some(ByteSequence::from("b"))
+    );
+    assert!(etcd.get("session").is_some());
+
+    let current = outcome.current().unwrap().mod_revision;
+    let outcome = kv_client.compare_and_delete("session", current).await?;
+    assert!(outcome.is_applied());
+    assert!(etcd.get("session").is_none());
+
+    assert_eq!(
+        kv_client.compare_and_delete("session", current).await?,
+        CasOutcome::Conflict { current: None }
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_cas() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await.with_namespace("tenant/");
+    let mut kv_client = client.get_kv_client();
+
+    // The same key outside of the namespace does not conflict.
+    etcd.put("leader", "outside");
+    assert!(kv_client.create("leader", "node-1").await?.is_applied());
+    assert_eq!(etcd.get("tenant/leader").unwrap().value, b"node-1");
+
+    let outcome = kv_client.create("leader", "node-2").await?;
+    assert_eq!(
+        outcome.current().map(|kv| kv.key.clone()),
+        Some(ByteSequence::from("leader"))
+    );
+
+    Ok(())
+}
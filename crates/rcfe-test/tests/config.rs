@@ -0,0 +1,183 @@
+use rcfe::{
+    ByteSequence, ClientOptions, DEFAULT_HEALTH_CHECK_INTERVAL, DEFAULT_HEALTH_PROBE_TIMEOUT,
+    Error, Namespaceable, PemSource,
+};
+use serde::Deserialize;
+use std::{path::PathBuf, time::Duration};
+
+#[test]
+fn test_from_vars_defaults() -> Result<(), Error> {
+    let options = ClientOptions::from_vars([("PATH", "/usr/bin")])?;
+
+    assert_eq!(options.endpoints(), &vec![String::from("http://127.0.0.1:2379")]);
+    assert!(options.tls().is_none());
+    assert!(options.credentials().is_none());
+    assert!(options.request_timeout().is_none());
+    assert_eq!(options.health_probe_timeout(), DEFAULT_HEALTH_PROBE_TIMEOUT);
+    assert!(options.namespace().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_from_vars() -> Result<(), Error> {
+    let options = ClientOptions::from_vars([
+        ("ETCDCTL_ENDPOINTS", "etcd-0:2379, https://etcd-1:2379"),
+        ("ETCDCTL_CACERT", "/etc/etcd/ca.pem"),
+        ("ETCDCTL_CERT", "/etc/etcd/client.pem"),
+        ("ETCDCTL_KEY", "/etc/etcd/client-key.pem"),
+        ("ETCDCTL_USER", "root:secret"),
+        ("ETCDCTL_DIAL_TIMEOUT", "2s"),
+        ("ETCDCTL_COMMAND_TIMEOUT", "1m30s"),
+        ("ETCDCTL_KEEPALIVE_TIME", "500ms"),
+        ("ETCDCTL_NAMESPACE", "app/"),
+    ])?;
+
+    assert_eq!(
+        options.endpoints(),
+        &vec![
+            String::from("https://etcd-0:2379"),
+            String::from("https://etcd-1:2379")
+        ]
+    );
+
+    let tls = options.tls().expect("TLS options");
+    assert_eq!(tls.ca_cert(), Some(&PemSource::File(PathBuf::from("/etc/etcd/ca.pem"))));
+    assert_eq!(
+        tls.client_key(),
+        Some(&PemSource::File(PathBuf::from("/etc/etcd/client-key.pem")))
+    );
+
+    let credentials = options.credentials().expect("credentials");
+    assert_eq!(credentials.user(), "root");
+    assert_eq!(credentials.password(), "secret");
+
+    assert_eq!(options.connect_timeout(), Some(Duration::from_secs(2)));
+    assert_eq!(options.request_timeout(), Some(Duration::from_secs(90)));
+    // The 90s request timeout is bounded by the default health check interval.
+    assert_eq!(options.health_probe_timeout(), DEFAULT_HEALTH_CHECK_INTERVAL);
+    assert_eq!(options.keep_alive_interval(), Some(Duration::from_millis(500)));
+    assert_eq!(options.namespace(), Some(ByteSequence::from("app/")));
+
+    Ok(())
+}
+
+#[test]
+fn test_from_vars_separate_password() -> Result<(), Error> {
+    let options = ClientOptions::from_vars([
+        ("ETCDCTL_USER", "root"),
+        ("ETCDCTL_PASSWORD", "pass:word"),
+    ])?;
+
+    let credentials = options.credentials().expect("credentials");
+    assert_eq!(credentials.user(), "root");
+    assert_eq!(credentials.password(), "pass:word");
+
+    Ok(())
+}
+
+#[test]
+fn test_from_vars_invalid() {
+    let result = ClientOptions::from_vars([("ETCDCTL_COMMAND_TIMEOUT", "5 seconds")]);
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+
+    let result = ClientOptions::from_vars([("ETCDCTL_USER", "root")]);
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+
+    let result = ClientOptions::from_vars([("ETCDCTL_CERT", "/etc/etcd/client.pem")]);
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+}
+
+#[test]
+fn test_health_probe_timeout() {
+    let builder = || ClientOptions::builder().endpoints(vec!["http://localhost:2379"]);
+
+    let options = builder().request_timeout(Duration::from_secs(3)).build();
+    assert_eq!(options.health_probe_timeout(), Duration::from_secs(3));
+
+    let options = builder()
+        .request_timeout(Duration::from_secs(3))
+        .health_probe_timeout(Duration::from_secs(1))
+        .build();
+    assert_eq!(options.health_probe_timeout(), Duration::from_secs(1));
+
+    let options = builder().request_timeout(Duration::from_secs(30)).build();
+    assert_eq!(options.health_probe_timeout(), DEFAULT_HEALTH_CHECK_INTERVAL);
+
+    let options = builder()
+        .health_probe_timeout(Duration::from_secs(30))
+        .health_check_interval(Duration::from_secs(2))
+        .build();
+    assert_eq!(options.health_probe_timeout(), Duration::from_secs(2));
+
+    // Without periodic checks, only `Client::health` probes and nothing bounds them.
+    let options = builder()
+        .health_probe_timeout(Duration::from_secs(30))
+        .disable_health_check()
+        .build();
+    assert_eq!(options.health_probe_timeout(), Duration::from_secs(30));
+}
+
+#[derive(Deserialize)]
+struct AppConfig {
+    name: String,
+    etcd: ClientOptions,
+}
+
+#[test]
+fn test_deserialize_config_section() -> Result<(), serde_json::Error> {
+    let config: AppConfig = serde_json::from_str(
+        r#"{
+            "name": "app",
+            "etcd": {
+                "endpoints": ["http://etcd-0:2379", "etcd-1:2379"],
+                "namespace": "app/",
+                "user": "root",
+                "password": "secret",
+                "request_timeout": "5s",
+                "health_check_interval": "1m",
+                "health_probe_timeout": "2s",
+                "require_leader": true,
+                "tls": { "domain_name": "etcd.cluster.local" }
+            }
+        }"#,
+    )?;
+
+    let options = config.etcd;
+    assert_eq!(config.name, "app");
+    assert_eq!(
+        options.endpoints(),
+        &vec![
+            String::from("http://etcd-0:2379"),
+            String::from("https://etcd-1:2379")
+        ]
+    );
+    assert_eq!(options.namespace(), Some(ByteSequence::from("app/")));
+    assert_eq!(options.credentials().map(|c| c.user()), Some("root"));
+    assert_eq!(options.request_timeout(), Some(Duration::from_secs(5)));
+    assert_eq!(options.health_check_interval(), Some(Duration::from_secs(60)));
+    assert_eq!(options.health_probe_timeout(), Duration::from_secs(2));
+    assert_eq!(
+        options.tls().and_then(|tls| tls.domain_name()),
+        Some("etcd.cluster.local")
+    );
+    assert!(options.require_leader());
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_rejects_invalid_config() {
+    let result = serde_json::from_str::<ClientOptions>(r#"{ "endpoints": [] }"#);
+    assert!(result.is_err());
+
+    let result = serde_json::from_str::<ClientOptions>(
+        r#"{ "endpoints": ["http://etcd-0:2379"], "request_timeout": "soon" }"#,
+    );
+    assert!(result.is_err());
+
+    let result = serde_json::from_str::<ClientOptions>(
+        r#"{ "endpoints": ["http://etcd-0:2379"], "endpoint": "http://etcd-1:2379" }"#,
+    );
+    assert!(result.is_err());
+}
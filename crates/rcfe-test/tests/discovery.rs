@@ -0,0 +1,120 @@
+use rcfe::{
+    Client, ClientFactory, ClientOptions, DefaultClientFactory, Error, KVClient,
+    etcdserverpb::Member,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+
+const INTERVAL: Duration = Duration::from_millis(50);
+
+fn member(id: u64, server: &MockServer) -> Member {
+    Member {
+        id,
+        name: format!("member-{id}"),
+        client_ur_ls: vec![server.endpoint()],
+        ..Default::default()
+    }
+}
+
+fn endpoints(client: &impl Client) -> Vec<String> {
+    let mut endpoints: Vec<String> = client
+        .endpoint_statuses()
+        .into_iter()
+        .map(|status| status.endpoint)
+        .collect();
+    endpoints.sort();
+    endpoints
+}
+
+async fn create_client(seed: String, auto_sync_interval: Duration) -> impl Client {
+    let options = ClientOptions::builder()
+        .endpoints(vec![seed])
+        .auto_sync_interval(auto_sync_interval)
+        .build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_members_are_discovered_on_connect() -> Result<(), Error> {
+    let (seed, other) = (MockEtcd::new(), MockEtcd::new());
+    let seed_server = MockServer::start(seed.router());
+    let other_server = MockServer::start(other.router());
+    seed.set_members(vec![member(1, &seed_server), member(2, &other_server)]);
+
+    let client = create_client(seed_server.endpoint(), Duration::from_secs(60)).await;
+
+    let mut expected = vec![seed_server.endpoint(), other_server.endpoint()];
+    expected.sort();
+    assert_eq!(endpoints(&client), expected);
+
+    let mut kv_client = client.get_kv_client();
+    for _ in 0..20 {
+        kv_client.get("discovery_key").await?;
+    }
+    assert!(other.requests() > 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_learners_are_skipped() -> Result<(), Error> {
+    let (seed, learner) = (MockEtcd::new(), MockEtcd::new());
+    let seed_server = MockServer::start(seed.router());
+    let learner_server = MockServer::start(learner.router());
+    seed.set_members(vec![
+        member(1, &seed_server),
+        Member {
+            is_learner: true,
+            ..member(2, &learner_server)
+        },
+    ]);
+
+    let client = create_client(seed_server.endpoint(), Duration::from_secs(60)).await;
+    assert_eq!(endpoints(&client), vec![seed_server.endpoint()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replaced_member_is_followed() -> Result<(), Error> {
+    let (seed, replacement) = (MockEtcd::new(), MockEtcd::new());
+    let seed_server = MockServer::start(seed.router());
+    let replacement_server = MockServer::start(replacement.router());
+    seed.set_members(vec![member(1, &seed_server)]);
+
+    let client = create_client(seed_server.endpoint(), INTERVAL).await;
+    assert_eq!(endpoints(&client), vec![seed_server.endpoint()]);
+
+    // The seed is replaced by a new member and then shut down.
+    seed.set_members(vec![member(2, &replacement_server)]);
+    for _ in 0..40 {
+        if endpoints(&client) == vec![replacement_server.endpoint()] {
+            break;
+        }
+        tokio::time::sleep(INTERVAL).await;
+    }
+    assert_eq!(endpoints(&client), vec![replacement_server.endpoint()]);
+    drop(seed_server);
+
+    let mut kv_client = client.get_kv_client();
+    kv_client.put("discovery_key", "value").await?;
+    assert!(replacement.get("discovery_key").is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_member_list_keeps_endpoints() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+
+    let client = create_client(server.endpoint(), Duration::from_secs(60)).await;
+    assert_eq!(endpoints(&client), vec![server.endpoint()]);
+    client.get_kv_client().get("discovery_key").await?;
+
+    Ok(())
+}
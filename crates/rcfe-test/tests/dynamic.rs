@@ -0,0 +1,89 @@
+use rcfe::{
+    ByteSequence, ClientFactory, ClientOptions, Compare, DefaultClientFactory, DynClient, Error,
+    RequestOp, WatchCreateOptions, WatchRequestType,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::{sync::Arc, time::Duration};
+
+async fn create_client(endpoint: String) -> Arc<dyn DynClient> {
+    let options = ClientOptions::builder().endpoints(vec![endpoint]).build();
+
+    let client = DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client");
+    Arc::new(client)
+}
+
+#[tokio::test]
+async fn test_dyn_kv_client() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+    let mut kv_client = client.kv_client();
+
+    kv_client.put("dyn_key", "value").await?;
+    let response = kv_client.get("dyn_key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"value");
+
+    let key = ByteSequence::from("dyn_key");
+    let response = kv_client
+        .txn()
+        .when(vec![Compare::version_eq(key.clone(), 1)])?
+        .then(vec![RequestOp::Delete { key, options: None }])?
+        .commit()
+        .await?;
+    assert!(response.get_ref().succeeded);
+    assert!(etcd.get("dyn_key").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dyn_lease_client() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+    let mut lease_client = client.lease_client();
+
+    let lease_id = lease_client
+        .grant(Duration::from_secs(10))
+        .await?
+        .get_ref()
+        .id;
+    assert_eq!(lease_client.time_to_live(lease_id).await?.get_ref().ttl, 10);
+
+    let mut handler = lease_client.keep_alive(lease_id).await?;
+    assert_eq!(handler.lease_id(), lease_id);
+    handler.keep_alive().await?;
+    let response = handler.into_response().into_inner().message().await?;
+    assert_eq!(response.expect("keep-alive response").id, lease_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dyn_watch_client() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("dyn_key"))
+            .build()?,
+    );
+    let mut watch_client = client.watch_client();
+    let mut watcher = watch_client.watch(request.clone()).await?;
+    assert!(matches!(watcher.request(), WatchRequestType::Create(_)));
+
+    watcher.cancel().await?;
+    let mut stream = watcher.into_response().into_inner();
+    let response = stream.message().await?.expect("cancel response");
+    assert!(response.canceled);
+
+    client.shutdown().await?;
+    assert!(matches!(client.health().await, Err(Error::ClientClosed)));
+
+    Ok(())
+}
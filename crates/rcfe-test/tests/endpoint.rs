@@ -0,0 +1,128 @@
+use rcfe::{
+    Client, ClientFactory, ClientOptions, DefaultClientFactory, EndpointStatus, Error, KVClient,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+use tonic::Code;
+
+const INTERVAL: Duration = Duration::from_millis(50);
+
+async fn create_client(endpoints: Vec<String>) -> impl Client {
+    let options = ClientOptions::builder()
+        .endpoints(endpoints)
+        .health_check_interval(INTERVAL)
+        .build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+fn status_of(client: &impl Client, endpoint: &str) -> EndpointStatus {
+    client
+        .endpoint_statuses()
+        .into_iter()
+        .find(|status| status.endpoint == endpoint)
+        .expect("unknown endpoint")
+}
+
+/// Waits until the condition holds, giving up after a few health check rounds.
+async fn wait_until(condition: impl Fn() -> bool) {
+    for _ in 0..40 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(INTERVAL).await;
+    }
+    panic!("condition not reached");
+}
+
+#[tokio::test]
+async fn test_unhealthy_endpoint_is_ejected_and_readded() -> Result<(), Error> {
+    let (first, second) = (MockEtcd::new(), MockEtcd::new());
+    let (first_server, second_server) = (
+        MockServer::start(first.router()),
+        MockServer::start(second.router()),
+    );
+    let client = create_client(vec![first_server.endpoint(), second_server.endpoint()]).await;
+    let mut kv_client = client.get_kv_client();
+
+    assert!(client.endpoint_statuses().iter().all(|s| s.healthy && s.in_rotation));
+
+    first.set_available(false);
+    wait_until(|| !status_of(&client, &first_server.endpoint()).in_rotation).await;
+
+    let status = status_of(&client, &first_server.endpoint());
+    assert!(!status.healthy);
+    assert!(status.last_checked.is_some());
+    assert!(status.last_error.is_some());
+    assert!(status_of(&client, &second_server.endpoint()).in_rotation);
+
+    let ejected_requests = first.requests();
+    for _ in 0..10 {
+        kv_client.get("endpoint_key").await?;
+    }
+    assert_eq!(first.requests(), ejected_requests);
+
+    first.set_available(true);
+    wait_until(|| status_of(&client, &first_server.endpoint()).in_rotation).await;
+    let status = status_of(&client, &first_server.endpoint());
+    assert!(status.healthy);
+    assert!(status.last_error.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stopped_member_is_ejected() -> Result<(), Error> {
+    let (first, second) = (MockEtcd::new(), MockEtcd::new());
+    let first_server = MockServer::start(first.router());
+    let second_server = MockServer::start(second.router());
+    let stopped = second_server.endpoint();
+    let client = create_client(vec![first_server.endpoint(), stopped.clone()]).await;
+    let mut kv_client = client.get_kv_client();
+
+    drop(second_server);
+    wait_until(|| !status_of(&client, &stopped).in_rotation).await;
+
+    for _ in 0..10 {
+        kv_client.get("endpoint_key").await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_last_endpoint_stays_in_rotation() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(vec![server.endpoint()]).await;
+
+    etcd.set_available(false);
+    wait_until(|| !status_of(&client, &server.endpoint()).healthy).await;
+    assert!(status_of(&client, &server.endpoint()).in_rotation);
+
+    let result = client.get_kv_client().get("endpoint_key").await;
+    assert!(matches!(result, Err(Error::TonicStatus(status)) if status.code() == Code::Unavailable));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_health_check_disabled() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .health_check_interval(INTERVAL)
+        .disable_health_check()
+        .build();
+    assert_eq!(options.health_check_interval(), None);
+
+    let client = DefaultClientFactory::new().create(options).await?;
+    tokio::time::sleep(INTERVAL * 4).await;
+    assert!(status_of(&client, &server.endpoint()).last_checked.is_none());
+
+    Ok(())
+}
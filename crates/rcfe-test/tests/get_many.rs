@@ -0,0 +1,197 @@
+use rcfe::{
+    BulkOptions, ByteSequence, Client, ClientFactory, ClientOptions, CompactOptions,
+    CompactionResponse, DefaultClient, DefaultClientFactory, DeleteOptions, DeleteResult, Error,
+    GetOptions, GetResult, KVClient, KVOptions, KeyRange, PutOptions, PutResult, Txn,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use tonic::Response;
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+/// Changes a key right before the second transaction, as a concurrent writer could.
+#[derive(Clone)]
+struct InterleavedWrite<K> {
+    inner: K,
+    etcd: MockEtcd,
+    txns: Arc<AtomicUsize>,
+}
+
+#[tonic::async_trait]
+impl<K: KVClient + Clone> KVClient for InterleavedWrite<K> {
+    async fn compact_with_options(
+        &mut self,
+        revision: i64,
+        options: CompactOptions,
+    ) -> Result<Response<CompactionResponse>, Error> {
+        self.inner.compact_with_options(revision, options).await
+    }
+
+    fn txn(&mut self) -> impl Txn {
+        if self.txns.fetch_add(1, Ordering::SeqCst) == 1 {
+            self.etcd.put("key/3", "changed");
+            self.etcd.put("key/4", "created");
+        }
+        self.inner.txn()
+    }
+
+    async fn delete_with_options<R>(
+        &mut self,
+        key: R,
+        options: DeleteOptions,
+    ) -> Result<DeleteResult, Error>
+    where
+        R: Into<KeyRange> + Send,
+    {
+        self.inner.delete_with_options(key, options).await
+    }
+
+    async fn put_with_options<R, V>(
+        &mut self,
+        key: R,
+        value: V,
+        options: PutOptions,
+    ) -> Result<PutResult, Error>
+    where
+        R: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        self.inner.put_with_options(key, value, options).await
+    }
+
+    async fn get_with_options<R>(&mut self, key: R, options: GetOptions) -> Result<GetResult, Error>
+    where
+        R: Into<KeyRange> + Send,
+    {
+        self.inner.get_with_options(key, options).await
+    }
+
+    fn options(&self) -> &KVOptions {
+        self.inner.options()
+    }
+}
+
+#[tokio::test]
+async fn test_get_many() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("a", "1").await?;
+    kv_client.put("b", "2").await?;
+    let requests = etcd.requests();
+
+    let kvs = kv_client.get_many(["a", "b", "missing", "a"]).await?;
+    assert_eq!(etcd.requests(), requests + 1);
+    assert_eq!(kvs.len(), 3);
+    assert_eq!(
+        kvs[&ByteSequence::from("a")].as_ref().unwrap().value,
+        ByteSequence::from("1")
+    );
+    assert_eq!(kvs[&ByteSequence::from("b")].as_ref().unwrap().version, 1);
+    assert!(kvs[&ByteSequence::from("missing")].is_none());
+
+    assert!(kv_client.get_many(Vec::<String>::new()).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_many_splits_at_limits() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    etcd.set_max_txn_ops(Some(4));
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    for i in 0..10 {
+        kv_client
+            .put(format!("key/{i}"), format!("value-{i}"))
+            .await?;
+    }
+    assert!(
+        kv_client
+            .get_many((0..10).map(|i| format!("key/{i}")))
+            .await
+            .is_err()
+    );
+
+    let requests = etcd.requests();
+    let options = BulkOptions::builder().max_txn_ops(4).build()?;
+    let kvs = kv_client
+        .get_many_with_options((0..10).map(|i| format!("key/{i}")), options)
+        .await?;
+    assert_eq!(etcd.requests(), requests + 3);
+    assert!(kvs.values().all(Option::is_some));
+    assert_eq!(
+        kvs[&ByteSequence::from("key/9")].as_ref().unwrap().value,
+        ByteSequence::from("value-9")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_many_reads_one_revision() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+
+    for i in 0..4 {
+        client
+            .get_kv_client()
+            .put(format!("key/{i}"), format!("value-{i}"))
+            .await?;
+    }
+    let revision = etcd.revision();
+
+    let mut kv_client = InterleavedWrite {
+        inner: client.get_kv_client(),
+        etcd: etcd.clone(),
+        txns: Arc::new(AtomicUsize::new(0)),
+    };
+    let options = BulkOptions::builder().max_txn_ops(2).build()?;
+    let kvs = kv_client
+        .get_many_with_options((0..6).map(|i| format!("key/{i}")), options)
+        .await?;
+    assert_eq!(kv_client.txns.load(Ordering::SeqCst), 3);
+    assert!(etcd.revision() > revision);
+
+    let key_3 = kvs[&ByteSequence::from("key/3")].as_ref().unwrap();
+    assert_eq!(key_3.value, ByteSequence::from("value-3"));
+    assert!(key_3.mod_revision <= revision);
+    assert!(kvs[&ByteSequence::from("key/4")].is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_get_many() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await.with_namespace("tenant/");
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("key", "value").await?;
+    etcd.put("key", "outside");
+
+    let kvs = kv_client.get_many(["key"]).await?;
+    let kv = kvs[&ByteSequence::from("key")].as_ref().unwrap();
+    assert_eq!(kv.key, ByteSequence::from("key"));
+    assert_eq!(kv.value, ByteSequence::from("value"));
+
+    Ok(())
+}
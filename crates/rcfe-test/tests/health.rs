@@ -0,0 +1,101 @@
+use rcfe::{Client, ClientFactory, ClientOptions, DefaultClientFactory, Error, KVClient};
+use rcfe_test::{MockEtcd, MockServer};
+
+async fn create_client(endpoints: Vec<String>) -> impl Client {
+    let options = ClientOptions::builder()
+        .endpoints(endpoints)
+        .disable_health_check()
+        .build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_health_reports_every_endpoint() -> Result<(), Error> {
+    let (first, second) = (MockEtcd::new(), MockEtcd::new());
+    let (first_server, second_server) = (
+        MockServer::start(first.router()),
+        MockServer::start(second.router()),
+    );
+    let client = create_client(vec![first_server.endpoint(), second_server.endpoint()]).await;
+
+    second.set_leader(false);
+    let report = client.health().await?;
+
+    assert!(report.is_healthy());
+    assert_eq!(report.leader(), Some(1));
+    assert_eq!(report.endpoints.len(), 2);
+
+    let healthy = &report.endpoints[0];
+    assert_eq!(healthy.endpoint, first_server.endpoint());
+    assert!(healthy.is_healthy());
+    assert_eq!(healthy.member_id, 1);
+    assert_eq!(healthy.leader, 1);
+    assert_eq!(healthy.raft_index, first.revision() as u64);
+    assert_eq!(healthy.version, "3.6.0");
+
+    let leaderless = &report.endpoints[1];
+    assert_eq!(leaderless.endpoint, second_server.endpoint());
+    assert!(!leaderless.is_healthy());
+    assert_eq!(leaderless.leader, 0);
+    assert_eq!(leaderless.error.as_deref(), Some("member has no leader"));
+
+    // The endpoint statuses are updated like by a periodic health check.
+    let statuses = client.endpoint_statuses();
+    assert!(statuses[0].healthy && statuses[0].last_checked.is_some());
+    assert!(!statuses[1].healthy && !statuses[1].in_rotation);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ping_does_not_read_keys() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(vec![server.endpoint()]).await;
+
+    client.ping().await?;
+    assert_eq!(etcd.requests(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ping_fails_without_healthy_endpoint() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(vec![server.endpoint()]).await;
+
+    etcd.set_available(false);
+    let report = client.health().await?;
+    assert!(!report.is_healthy());
+    assert_eq!(report.leader(), None);
+    assert_eq!(report.errors().len(), 1);
+
+    let result = client.ping().await;
+    assert!(
+        matches!(&result, Err(Error::Unhealthy(e)) if e.contains("unavailable")),
+        "{result:?}"
+    );
+
+    etcd.set_available(true);
+    client.ping().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_health_after_shutdown() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(vec![server.endpoint()]).await;
+
+    client.get_kv_client().put("health_key", "value").await?;
+    client.shutdown().await?;
+    assert!(matches!(client.health().await, Err(Error::ClientClosed)));
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use rcfe::{ByteSequence, Client, CompactOptions, Compare, CompareResult, CompareTarget, DeleteOptions, Error, GetOptions, KVClient, PutOptions, RequestOp, Txn};
+use rcfe::{
+    ByteSequence, Client, CompactOptions, Compare, CompareResult, CompareTarget, DeleteOptions,
+    Error, GetOptions, KVClient, KeyRange, PutOptions, RequestOp, Txn,
+};
 mod common;
 
 use tokio::test;
@@ -20,14 +23,14 @@ async fn test_put() -> Result<(), Error> {
 
     let put_response = kv_client.put(key.clone(), value.clone()).await?;
 
-    assert!(put_response.get_ref().header.is_some());
+    assert!(put_response.header().revision > 0);
 
     // Verify that the key was inserted
     let get_response = kv_client.get(key.clone()).await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].key, key.to_vec());
-    assert_eq!(kvs[0].value, value.to_vec());
+    assert_eq!(kvs[0].key, key);
+    assert_eq!(kvs[0].value, value);
 
     // Clean up
     let _ = kv_client
@@ -56,8 +59,8 @@ async fn test_put_with_options() -> Result<(), Error> {
     let put_response = kv_client
         .put_with_options(key.clone(), original_value.clone(), put_options)
         .await?;
-    assert!(put_response.get_ref().header.is_some());
-    assert!(put_response.get_ref().prev_kv.is_none());
+    assert!(put_response.header().revision > 0);
+    assert!(put_response.prev_kv().is_none());
 
     // Test updating the key with prev_kv option
     let new_value = ByteSequence::from("new_test_value_options");
@@ -67,10 +70,10 @@ async fn test_put_with_options() -> Result<(), Error> {
         .await?;
 
     // Verify that prev_kv is returned and matches the original value
-    assert!(put_response.get_ref().header.is_some());
-    assert!(put_response.get_ref().prev_kv.is_some());
+    assert!(put_response.header().revision > 0);
+    assert!(put_response.prev_kv().is_some());
     assert_eq!(
-        put_response.get_ref().prev_kv.as_ref().unwrap().value,
+        put_response.prev_kv().unwrap().value.as_bytes(),
         original_value.to_vec()
     );
 
@@ -83,12 +86,12 @@ async fn test_put_with_options() -> Result<(), Error> {
             put_options,
         )
         .await?;
-    assert!(put_response.get_ref().header.is_some());
+    assert!(put_response.header().revision > 0);
     // Verify that the value was not changed
     let get_response = kv_client.get(key.clone()).await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].value, new_value.to_vec());
+    assert_eq!(kvs[0].value, new_value);
 
     // Clean up
     let _ = kv_client
@@ -118,11 +121,11 @@ async fn test_get() -> Result<(), Error> {
 
     // Get the key
     let get_response = kv_client.get(key.clone()).await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
 
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].key, key.to_vec());
-    assert_eq!(kvs[0].value, value.to_vec());
+    assert_eq!(kvs[0].key, key);
+    assert_eq!(kvs[0].value, value);
 
     // Clean up
     let _ = kv_client
@@ -146,8 +149,8 @@ async fn test_get_all() -> Result<(), Error> {
     // Clean up test keys if they exist
     let _ = kv_client
         .delete_with_options(
-            prefix_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(prefix_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -157,18 +160,18 @@ async fn test_get_all() -> Result<(), Error> {
 
     // Get all keys
     let get_response = kv_client.get_all(None).await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
 
     // Check that our keys are in the result
-    let keys: Vec<Vec<u8>> = kvs.iter().map(|kv| kv.key.clone()).collect();
+    let keys: Vec<Vec<u8>> = kvs.iter().map(|kv| kv.key.to_vec()).collect();
     assert!(keys.contains(&key1.to_vec()));
     assert!(keys.contains(&key2.to_vec()));
 
     // Clean up
     let _ = kv_client
         .delete_with_options(
-            prefix_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(prefix_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
     Ok(())
@@ -194,10 +197,10 @@ async fn test_get_with_options() -> Result<(), Error> {
     let get_response = kv_client
         .get_with_options(key.clone(), GetOptions::default())
         .await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].key, key.to_vec());
-    assert_eq!(kvs[0].value, value.to_vec());
+    assert_eq!(kvs[0].key, key);
+    assert_eq!(kvs[0].value, value);
 
     // Clean up
     let _ = kv_client
@@ -215,8 +218,8 @@ async fn test_get_with_options() -> Result<(), Error> {
     // Clean up prefix keys if they exist
     let _ = kv_client
         .delete_with_options(
-            prefix_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(prefix_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -228,21 +231,21 @@ async fn test_get_with_options() -> Result<(), Error> {
         .put(prefix_key2.clone(), prefix_value2.clone())
         .await?;
 
-    let get_options = GetOptions::builder().prefix(true).build();
+    let get_options = GetOptions::builder().build();
     let get_response = kv_client
-        .get_with_options(prefix_key.clone(), get_options)
+        .get_with_options(KeyRange::prefix(prefix_key.clone()), get_options)
         .await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 2);
-    let keys: Vec<Vec<u8>> = kvs.iter().map(|kv| kv.key.clone()).collect();
+    let keys: Vec<Vec<u8>> = kvs.iter().map(|kv| kv.key.to_vec()).collect();
     assert!(keys.contains(&prefix_key1.to_vec()));
     assert!(keys.contains(&prefix_key2.to_vec()));
 
     // Clean up prefix keys
     let _ = kv_client
         .delete_with_options(
-            prefix_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(prefix_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -263,8 +266,8 @@ async fn test_get_with_options() -> Result<(), Error> {
     // Clean up range keys if they exist
     let _ = kv_client
         .delete_with_options(
-            range_start_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(range_start_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -275,20 +278,19 @@ async fn test_get_with_options() -> Result<(), Error> {
     let _ = kv_client
         .put(range_key2.clone(), range_value2.clone())
         .await?;
-    let get_options = GetOptions::builder().end_key(range_end_key.clone()).build();
-    let get_response = kv_client
-        .get_with_options(range_start_key.clone(), get_options)
-        .await?;
-    let kvs = get_response.into_inner().kvs;
+    let get_options = GetOptions::builder().build();
+    let range = KeyRange::range(range_start_key.clone(), range_end_key.clone());
+    let get_response = kv_client.get_with_options(range, get_options).await?;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].key, range_key1.to_vec());
-    assert_eq!(kvs[0].value, range_value1.to_vec());
+    assert_eq!(kvs[0].key, range_key1);
+    assert_eq!(kvs[0].value, range_value1);
 
     // Clean up range keys
     let _ = kv_client
         .delete_with_options(
-            range_start_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(range_start_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -302,8 +304,8 @@ async fn test_get_with_options() -> Result<(), Error> {
     // Clean up limit keys if they exist
     let _ = kv_client
         .delete_with_options(
-            limit_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(limit_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -315,19 +317,19 @@ async fn test_get_with_options() -> Result<(), Error> {
         .put(limit_key2.clone(), limit_value2.clone())
         .await?;
 
-    let get_options = GetOptions::builder().prefix(true).limit(1).build();
+    let get_options = GetOptions::builder().limit(1).build();
     let get_response = kv_client
-        .get_with_options(limit_key.clone(), get_options)
+        .get_with_options(KeyRange::prefix(limit_key.clone()), get_options)
         .await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 1);
-    assert!(kvs[0].key == limit_key1.to_vec() || kvs[0].key == limit_key2.to_vec());
+    assert!(kvs[0].key == limit_key1 || kvs[0].key == limit_key2);
 
     // Clean up limit keys
     let _ = kv_client
         .delete_with_options(
-            limit_key.clone(),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(limit_key.clone()),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -353,12 +355,12 @@ async fn test_delete() -> Result<(), Error> {
     // Delete the key
     let delete_response = kv_client.delete(key.clone()).await?;
 
-    assert!(delete_response.get_ref().header.is_some());
-    assert_eq!(delete_response.get_ref().deleted, 1);
+    assert!(delete_response.header().revision > 0);
+    assert_eq!(delete_response.deleted(), 1);
 
     // Verify that the key was deleted
     let get_response = kv_client.get(key.clone()).await?;
-    let kvs = get_response.into_inner().kvs;
+    let kvs = get_response.into_kvs();
     assert_eq!(kvs.len(), 0);
 
     Ok(())
@@ -377,8 +379,8 @@ async fn test_delete_with_options() -> Result<(), Error> {
     // Clean up test keys if they exist
     let _ = kv_client
         .delete_with_options(
-            ByteSequence::from("test_delete_options_"),
-            DeleteOptions::builder().prefix(true).build(),
+            KeyRange::prefix(ByteSequence::from("test_delete_options_")),
+            DeleteOptions::default(),
         )
         .await;
 
@@ -387,21 +389,21 @@ async fn test_delete_with_options() -> Result<(), Error> {
     let _ = kv_client.put(key2.clone(), value2.clone()).await?;
 
     // Delete with prefix option
-    let delete_options = DeleteOptions::builder().prefix(true).build();
+    let delete_options = DeleteOptions::builder().build();
     let delete_response = kv_client
-        .delete_with_options(ByteSequence::from("test_delete_options_"), delete_options)
+        .delete_with_options(KeyRange::prefix("test_delete_options_"), delete_options)
         .await?;
 
-    assert!(delete_response.get_ref().header.is_some());
-    assert_eq!(delete_response.get_ref().deleted, 2);
+    assert!(delete_response.header().revision > 0);
+    assert_eq!(delete_response.deleted(), 2);
 
     // Verify that the keys were deleted
     let get_response1 = kv_client.get(key1.clone()).await?;
-    let kvs1 = get_response1.into_inner().kvs;
+    let kvs1 = get_response1.into_kvs();
     assert_eq!(kvs1.len(), 0);
 
     let get_response2 = kv_client.get(key2.clone()).await?;
-    let kvs2 = get_response2.into_inner().kvs;
+    let kvs2 = get_response2.into_kvs();
     assert_eq!(kvs2.len(), 0);
 
     Ok(())
@@ -443,10 +445,10 @@ async fn test_txn() -> Result<(), Error> {
 
     // Verify the value was set
     let get_response = kv_client.get(key.clone()).await?;
-    let kvs = &get_response.get_ref().kvs;
+    let kvs = get_response.kvs();
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].key, key.clone().to_vec());
-    assert_eq!(kvs[0].value, value.clone().to_vec());
+    assert_eq!(kvs[0].key, key);
+    assert_eq!(kvs[0].value, value);
 
     // Compare key 'foo' not exists(version == 0), if true put key 'foo' 'bar', else get key 'foo'
     let compares = vec![
@@ -480,10 +482,10 @@ async fn test_txn() -> Result<(), Error> {
 
     // Verify the value was set
     let get_response = kv_client.get(key.clone()).await?;
-    let kvs = &get_response.get_ref().kvs;
+    let kvs = get_response.kvs();
     assert_eq!(kvs.len(), 1);
-    assert_eq!(kvs[0].key, key.clone().to_vec());
-    assert_eq!(kvs[0].value, value.clone().to_vec());
+    assert_eq!(kvs[0].key, key);
+    assert_eq!(kvs[0].value, value);
 
     Ok(())
 }
@@ -565,7 +567,7 @@ async fn text_compact() -> Result<(), Error> {
 
     // Compact the store at the current revision
     let get_response = kv_client.get(key.clone()).await?;
-    let revision = get_response.get_ref().header.as_ref().unwrap().revision;
+    let revision = get_response.header().revision;
 
     let compact_response = kv_client.compact(revision).await?;
 
@@ -593,7 +595,7 @@ async fn test_compact_with_options() -> Result<(), Error> {
 
     // Compact the store at the current revision with physical option
     let get_response = kv_client.get(key.clone()).await?;
-    let revision = get_response.get_ref().header.as_ref().unwrap().revision;
+    let revision = get_response.header().revision;
 
     let compact_options = CompactOptions::builder().physical(true).build();
     let compact_response = kv_client
@@ -606,4 +608,4 @@ async fn test_compact_with_options() -> Result<(), Error> {
     let _ = kv_client.delete(key.clone()).await;
 
     Ok(())
-}
\ No newline at end of file
+}
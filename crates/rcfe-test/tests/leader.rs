@@ -0,0 +1,104 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, DefaultClientFactory, Error, KVClient,
+    WatchClient, WatchCreateOptions, WatchRequestType, Watcher,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use tonic::Status;
+
+async fn create_client(endpoint: String, require_leader: bool) -> impl Client {
+    let mut builder = ClientOptions::builder().endpoints(vec![endpoint]);
+    if require_leader {
+        builder = builder.require_leader();
+    }
+
+    DefaultClientFactory::new()
+        .create(builder.build())
+        .await
+        .expect("Failed to create client")
+}
+
+fn watch_request(require_leader: Option<bool>) -> Result<WatchRequestType, Error> {
+    let mut builder = WatchCreateOptions::builder().key(ByteSequence::from("leader_key"));
+    if let Some(require_leader) = require_leader {
+        builder = builder.require_leader(require_leader);
+    }
+    Ok(WatchRequestType::Create(builder.build()?))
+}
+
+#[tokio::test]
+async fn test_request_fails_without_leader() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), true).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.set_leader(false);
+    let result = kv_client.get("leader_key").await;
+    assert!(matches!(result, Err(Error::NoLeader(_))), "{result:?}");
+
+    etcd.set_leader(true);
+    kv_client.get("leader_key").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_request_is_served_without_leader_requirement() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), false).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.set_leader(false);
+    kv_client.put("leader_key", "value").await?;
+    kv_client.get("leader_key").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_watch_fails_without_leader() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), true).await;
+    let mut watch_client = client.get_watch_client();
+
+    etcd.set_leader(false);
+    {
+        let result = watch_client.watch(watch_request(None)?).await;
+        assert!(matches!(result, Err(Error::NoLeader(_))));
+    }
+
+    etcd.set_leader(true);
+    let watcher = watch_client.watch(watch_request(None)?).await?;
+    assert_eq!(watcher.id(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_watch_overrides_client_leader_requirement() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    etcd.set_leader(false);
+
+    let client = create_client(server.endpoint(), false).await;
+    let mut watch_client = client.get_watch_client();
+    let result = watch_client.watch(watch_request(Some(true))?).await;
+    assert!(matches!(result, Err(Error::NoLeader(_))));
+
+    let client = create_client(server.endpoint(), true).await;
+    let mut watch_client = client.get_watch_client();
+    watch_client.watch(watch_request(Some(false))?).await?;
+
+    Ok(())
+}
+
+#[test]
+fn test_no_leader_status_conversion() {
+    let error = Error::from(Status::unavailable("etcdserver: no leader"));
+    assert!(matches!(error, Error::NoLeader(_)));
+
+    let error = Error::from(Status::unavailable("etcdserver: request timed out"));
+    assert!(matches!(error, Error::TonicStatus(_)));
+}
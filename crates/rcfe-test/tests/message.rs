@@ -0,0 +1,124 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, ClientOptionsBuilder, CompressionEncoding,
+    DefaultClientFactory, Error, KVClient, RequestOp, Txn,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use tonic::Code;
+
+const MIB: usize = 1024 * 1024;
+
+async fn create_client(
+    endpoint: String,
+    configure: impl FnOnce(ClientOptionsBuilder) -> ClientOptionsBuilder,
+) -> impl Client {
+    let options = configure(ClientOptions::builder().endpoints(vec![endpoint])).build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+/// Returns a value that does not shrink when compressed.
+fn incompressible(len: usize) -> Vec<u8> {
+    let mut state: u32 = 0x9E37_79B9;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_response_above_default_limit_fails() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), |builder| builder).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("large_key", incompressible(5 * MIB)).await?;
+
+    match kv_client.get_all(None).await {
+        Err(Error::TonicStatus(status)) => assert_eq!(status.code(), Code::OutOfRange),
+        other => panic!("expected the response to exceed the limit, got {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_decoding_message_size() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), |builder| {
+        builder.max_decoding_message_size(8 * MIB)
+    })
+    .await;
+    let mut kv_client = client.get_kv_client();
+
+    let value = incompressible(5 * MIB);
+    kv_client.put("large_key", value.clone()).await?;
+
+    let response = kv_client.get_all(None).await?;
+    assert_eq!(response.kvs().len(), 1);
+    assert_eq!(response.kvs()[0].value.as_bytes(), value);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_encoding_message_size() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), |builder| {
+        builder.max_encoding_message_size(1024)
+    })
+    .await;
+    let mut kv_client = client.get_kv_client();
+
+    let result = kv_client.put("large_key", incompressible(2048)).await;
+    assert!(matches!(result, Err(Error::TonicStatus(status)) if status.code() == Code::OutOfRange));
+    assert_eq!(etcd.requests(), 0);
+
+    let result = kv_client
+        .txn()
+        .then([RequestOp::Put {
+            key: ByteSequence::from("large_key"),
+            value: ByteSequence::from(incompressible(2048)),
+            options: None,
+        }])?
+        .commit()
+        .await;
+    assert!(matches!(result, Err(Error::TonicStatus(status)) if status.code() == Code::OutOfRange));
+    assert_eq!(etcd.requests(), 0);
+
+    kv_client.put("small_key", "small_value").await?;
+    assert_eq!(etcd.requests(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gzip_compression() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), |builder| {
+        builder.compression(CompressionEncoding::Gzip)
+    })
+    .await;
+    let mut kv_client = client.get_kv_client();
+
+    let value = vec![b'a'; MIB];
+    kv_client.put("compressed_key", value.clone()).await?;
+
+    let response = kv_client.get("compressed_key").await?;
+    let encoding = response.metadata().get("grpc-encoding");
+    assert_eq!(encoding.and_then(|e| e.to_str().ok()), Some("gzip"));
+    assert_eq!(response.kvs().len(), 1);
+    assert_eq!(response.kvs()[0].value.as_bytes(), value);
+
+    Ok(())
+}
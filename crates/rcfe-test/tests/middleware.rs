@@ -0,0 +1,159 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, ClientOptionsBuilder, DefaultClient,
+    DefaultClientFactory, Error, KVClient, LeaseClient, WatchClient, WatchCreateOptions,
+    WatchRequestType,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tonic::{Code, Request, Status, metadata::AsciiMetadataValue};
+use tower::util::MapRequestLayer;
+
+async fn create_client(
+    endpoint: String,
+    configure: impl FnOnce(ClientOptionsBuilder) -> ClientOptionsBuilder,
+) -> DefaultClient {
+    let options = configure(ClientOptions::builder().endpoints(vec![endpoint])).build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_interceptor_applies_to_every_service_client() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let client = create_client(server.endpoint(), |builder| {
+        builder.interceptor(move |request: Request<()>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(request)
+        })
+    })
+    .await;
+
+    client
+        .get_kv_client()
+        .put("middleware_key", "value")
+        .await?;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    client
+        .get_lease_client()
+        .grant(Duration::from_secs(10))
+        .await?;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("middleware_key"))
+            .build()?,
+    );
+    client.get_watch_client().watch(request).await?;
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_interceptor_rejects_request() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), |builder| {
+        builder.interceptor(|_: Request<()>| Err(Status::permission_denied("blocked")))
+    })
+    .await;
+
+    let result = client.get_kv_client().get("middleware_key").await;
+    assert!(
+        matches!(&result, Err(Error::TonicStatus(status)) if status.code() == Code::PermissionDenied),
+        "{result:?}"
+    );
+    assert_eq!(etcd.requests(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_interceptor_metadata_takes_precedence() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint(), |builder| {
+        builder
+            .require_leader()
+            .interceptor(|mut request: Request<()>| {
+                request
+                    .metadata_mut()
+                    .insert("hasleader", AsciiMetadataValue::from_static("false"));
+                Ok(request)
+            })
+    })
+    .await;
+
+    etcd.set_leader(false);
+    client.get_kv_client().get("middleware_key").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_layers_apply_in_order() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let record = |name: &'static str| {
+        let seen = seen.clone();
+        MapRequestLayer::new(move |request| {
+            seen.lock().unwrap().push(name);
+            request
+        })
+    };
+    let client = create_client(server.endpoint(), |builder| {
+        builder.layer(record("first")).layer(record("second"))
+    })
+    .await;
+
+    client.get_kv_client().get("middleware_key").await?;
+    assert_eq!(*seen.lock().unwrap(), vec!["second", "first"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_layers_apply_to_authentication_and_health_checks() -> Result<(), Error> {
+    let etcd = MockEtcd::new().with_user("root", "secret");
+    let server = MockServer::start(etcd.router());
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let recorded = paths.clone();
+    let client = create_client(server.endpoint(), |builder| {
+        builder
+            .credentials("root", "secret")
+            .require_leader()
+            .layer(MapRequestLayer::new(move |request: tonic::codegen::http::Request<_>| {
+                recorded.lock().unwrap().push(request.uri().path().to_string());
+                request
+            }))
+    })
+    .await;
+
+    etcd.set_leader(false);
+    let report = client.health().await?;
+    assert_eq!(report.leader(), None);
+    assert_eq!(
+        *paths.lock().unwrap(),
+        vec![
+            "/etcdserverpb.Auth/Authenticate",
+            "/etcdserverpb.Maintenance/Status"
+        ]
+    );
+
+    Ok(())
+}
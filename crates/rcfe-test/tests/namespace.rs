@@ -0,0 +1,464 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, Compare, DefaultClient,
+    DefaultClientFactory, DeleteOptions, Error, KVClient, KeyRange, LeaseClient, Namespace,
+    NamespaceBuilder, Namespaceable, PutOptions, RequestOp, TimeToLiveOptions, Txn, WatchClient,
+    WatchCreateOptions, WatchRequestType, Watcher,
+    etcdserverpb::{
+        DeleteRangeResponse, PutRequest, PutResponse, RangeRequest, RangeResponse,
+        RequestOp as PbRequestOp, ResponseOp, TxnRequest, TxnResponse, WatchCancelRequest,
+        WatchRequest, WatchResponse, request_op, response_op, watch_request::RequestUnion,
+    },
+    mvccpb::{Event, KeyValue},
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+
+async fn create_client(options: ClientOptions) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_client_options_namespace() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_client(
+        ClientOptions::builder()
+            .endpoints(vec![server.endpoint()])
+            .build(),
+    )
+    .await;
+    root.get_kv_client().put("tenant/key", "value").await?;
+
+    let client = create_client(
+        ClientOptions::builder()
+            .endpoints(vec![server.endpoint()])
+            .namespace(Some("tenant/"))
+            .build(),
+    )
+    .await;
+    let response = client.get_kv_client().get("key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"value");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_namespace_nests() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_client(
+        ClientOptions::builder()
+            .endpoints(vec![server.endpoint()])
+            .build(),
+    )
+    .await;
+    root.get_kv_client().put("tenant-a/key", "a").await?;
+    root.get_kv_client().put("tenant-a/app/key", "app").await?;
+    root.get_kv_client().put("tenant-b/key", "b").await?;
+
+    let tenant_a = root.with_namespace("tenant-a/");
+    let tenant_b = root.with_namespace("tenant-b/");
+    let app = tenant_a.with_namespace("app/");
+    assert_eq!(root.get_options().namespace(), None);
+    assert_eq!(
+        app.get_options().namespace(),
+        Some(ByteSequence::from("tenant-a/app/"))
+    );
+
+    for (client, expected) in [(&tenant_a, "a"), (&tenant_b, "b"), (&app, "app")] {
+        let response = client.get_kv_client().get("key").await?;
+        assert_eq!(response.kvs()[0].value.as_bytes(), expected.as_bytes());
+    }
+    assert!(root.get_kv_client().get("key").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_namespace_shares_lifecycle() -> Result<(), Error> {
+    use rcfe::DynClient;
+
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_client(
+        ClientOptions::builder()
+            .endpoints(vec![server.endpoint()])
+            .build(),
+    )
+    .await;
+    root.get_kv_client().put("tenant/key", "value").await?;
+
+    let client: &dyn DynClient = &root;
+    let tenant = client.with_namespace(ByteSequence::from("tenant/"));
+    let response = tenant.kv_client().get("key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"value");
+
+    tenant.shutdown().await?;
+    assert!(matches!(
+        root.get_kv_client().get("tenant/key").await,
+        Err(Error::ClientClosed)
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_nested_namespace_strips_lease_keys() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_root_client(&server).await;
+    let app = root.with_namespace("tenant/").with_namespace("app/");
+
+    let lease = app
+        .get_lease_client()
+        .grant(Duration::from_secs(60))
+        .await?;
+    let lease_id = lease.get_ref().id;
+    let options = PutOptions::builder().lease(lease_id).build();
+    app.get_kv_client()
+        .put_with_options("key", "value", options.clone())
+        .await?;
+    root.get_kv_client()
+        .put_with_options("other", "value", options)
+        .await?;
+
+    let options = TimeToLiveOptions::builder().keys(true).build();
+    let response = app
+        .get_lease_client()
+        .time_to_live_with_options(lease_id, options.clone())
+        .await?;
+    assert_eq!(response.get_ref().keys, vec![b"key".to_vec()]);
+    let key = ByteSequence::from(response.get_ref().keys[0].clone());
+    assert_eq!(app.get_kv_client().get(key).await?.kvs()[0].lease, lease_id);
+
+    let response = root
+        .get_lease_client()
+        .time_to_live_with_options(lease_id, options)
+        .await?;
+    assert_eq!(
+        response.get_ref().keys,
+        vec![b"other".to_vec(), b"tenant/app/key".to_vec()]
+    );
+
+    Ok(())
+}
+
+fn kv(key: &str) -> KeyValue {
+    KeyValue {
+        key: key.as_bytes().to_vec(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_prefix_range_request() {
+    let namespace = Namespace::new("tenant/");
+    let range = |key: &[u8], range_end: &[u8]| {
+        let request = namespace.prefix(RangeRequest {
+            key: key.to_vec(),
+            range_end: range_end.to_vec(),
+            ..Default::default()
+        });
+        (request.key, request.range_end)
+    };
+
+    assert_eq!(range(b"key", b""), (b"tenant/key".to_vec(), vec![]));
+    assert_eq!(
+        range(b"a", b"b"),
+        (b"tenant/a".to_vec(), b"tenant/b".to_vec())
+    );
+    assert_eq!(
+        range(b"\0", b"\0"),
+        (b"tenant/\0".to_vec(), b"tenant0".to_vec())
+    );
+
+    let namespace = Namespace::new(vec![0xFF, 0xFF]);
+    let request = namespace.prefix(RangeRequest {
+        key: b"a".to_vec(),
+        range_end: vec![0],
+        ..Default::default()
+    });
+    assert_eq!(request.key, vec![0xFF, 0xFF, b'a']);
+    assert_eq!(request.range_end, vec![0]);
+}
+
+#[test]
+fn test_prefix_put_and_delete_requests() {
+    let namespace = Namespace::new("tenant/");
+
+    let request = namespace.prefix(PutRequest {
+        key: b"key".to_vec(),
+        value: b"value".to_vec(),
+        ..Default::default()
+    });
+    assert_eq!(request.key, b"tenant/key");
+    assert_eq!(request.value, b"value");
+
+    let request = namespace.prefix(DeleteOptions::default().to_request(KeyRange::prefix("dir/")));
+    assert_eq!(request.key, b"tenant/dir/");
+    assert_eq!(request.range_end, b"tenant/dir0");
+}
+
+#[test]
+fn test_prefix_txn_request() {
+    let namespace = Namespace::new("tenant/");
+    let nested = TxnRequest {
+        success: vec![
+            RequestOp::Delete {
+                key: ByteSequence::from("nested"),
+                options: None,
+            }
+            .into_pb(),
+        ],
+        ..Default::default()
+    };
+    let request = namespace.prefix(TxnRequest {
+        compare: vec![
+            Compare::version_eq("key", 1)
+                .with_range_end("kez")
+                .into_pb(),
+        ],
+        success: vec![
+            RequestOp::Put {
+                key: ByteSequence::from("key"),
+                value: ByteSequence::from("value"),
+                options: None,
+            }
+            .into_pb(),
+            PbRequestOp {
+                request: Some(request_op::Request::RequestTxn(nested)),
+            },
+        ],
+        failure: vec![
+            RequestOp::Get {
+                key: ByteSequence::from("key"),
+                options: None,
+            }
+            .into_pb(),
+        ],
+    });
+
+    assert_eq!(request.compare[0].key, b"tenant/key");
+    assert_eq!(request.compare[0].range_end, b"tenant/kez");
+    let Some(request_op::Request::RequestPut(put)) = &request.success[0].request else {
+        panic!("expected a put");
+    };
+    assert_eq!(put.key, b"tenant/key");
+    let Some(request_op::Request::RequestTxn(txn)) = &request.success[1].request else {
+        panic!("expected a txn");
+    };
+    let Some(request_op::Request::RequestDeleteRange(delete)) = &txn.success[0].request else {
+        panic!("expected a delete");
+    };
+    assert_eq!(delete.key, b"tenant/nested");
+    let Some(request_op::Request::RequestRange(range)) = &request.failure[0].request else {
+        panic!("expected a range");
+    };
+    assert_eq!(range.key, b"tenant/key");
+}
+
+#[test]
+fn test_prefix_watch_request() -> Result<(), Error> {
+    let namespace = Namespace::new("tenant/");
+    let create = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("a"))
+            .range_end(ByteSequence::from("b"))
+            .build()?,
+    );
+    let request = namespace.prefix(create.to_request());
+    let Some(RequestUnion::CreateRequest(create)) = request.request_union else {
+        panic!("expected a create request");
+    };
+    assert_eq!(create.key, b"tenant/a");
+    assert_eq!(create.range_end, b"tenant/b");
+
+    let cancel = WatchRequest {
+        request_union: Some(RequestUnion::CancelRequest(WatchCancelRequest {
+            watch_id: 1,
+        })),
+    };
+    assert_eq!(namespace.prefix(cancel.clone()), cancel);
+
+    Ok(())
+}
+
+#[test]
+fn test_strip_responses() {
+    let namespace = Namespace::new("tenant/");
+
+    let range = namespace.strip(RangeResponse {
+        kvs: vec![kv("tenant/a"), kv("tenant/b")],
+        ..Default::default()
+    });
+    assert_eq!(range.kvs, vec![kv("a"), kv("b")]);
+
+    let put = namespace.strip(PutResponse {
+        prev_kv: Some(kv("tenant/key")),
+        ..Default::default()
+    });
+    assert_eq!(put.prev_kv, Some(kv("key")));
+
+    let delete = namespace.strip(DeleteRangeResponse {
+        prev_kvs: vec![kv("tenant/key")],
+        ..Default::default()
+    });
+    assert_eq!(delete.prev_kvs, vec![kv("key")]);
+
+    let txn = namespace.strip(TxnResponse {
+        responses: vec![ResponseOp {
+            response: Some(response_op::Response::ResponseTxn(TxnResponse {
+                responses: vec![ResponseOp {
+                    response: Some(response_op::Response::ResponseRange(RangeResponse {
+                        kvs: vec![kv("tenant/nested")],
+                        ..Default::default()
+                    })),
+                }],
+                ..Default::default()
+            })),
+        }],
+        ..Default::default()
+    });
+    let Some(response_op::Response::ResponseTxn(nested)) = &txn.responses[0].response else {
+        panic!("expected a txn");
+    };
+    let Some(response_op::Response::ResponseRange(range)) = &nested.responses[0].response else {
+        panic!("expected a range");
+    };
+    assert_eq!(range.kvs, vec![kv("nested")]);
+
+    let watch = namespace.strip(WatchResponse {
+        events: vec![Event {
+            kv: Some(kv("tenant/key")),
+            prev_kv: Some(kv("tenant/key")),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    assert_eq!(watch.events[0].kv, Some(kv("key")));
+    assert_eq!(watch.events[0].prev_kv, Some(kv("key")));
+
+    assert_eq!(Namespace::default().strip(put.clone()), put);
+}
+
+async fn create_root_client(server: &MockServer) -> DefaultClient {
+    create_client(
+        ClientOptions::builder()
+            .endpoints(vec![server.endpoint()])
+            .build(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_namespaced_kv() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_root_client(&server).await;
+    let client = root.with_namespace("tenant/");
+    let mut kv_client = client.get_kv_client();
+    root.get_kv_client().put("outside", "value").await?;
+    root.get_kv_client().put("tenant0", "value").await?;
+
+    kv_client.put("dir/a", "1").await?;
+    let options = PutOptions::builder().prev_kv(true).build();
+    let response = kv_client.put_with_options("dir/a", "2", options).await?;
+    assert_eq!(response.prev_kv().unwrap().key.as_bytes(), b"dir/a");
+    assert!(etcd.get("tenant/dir/a").is_some());
+
+    let response = kv_client.get(KeyRange::prefix("dir/")).await?;
+    assert_eq!(response.kvs()[0].key.as_bytes(), b"dir/a");
+
+    let response = kv_client.get_all(None).await?;
+    let keys: Vec<&[u8]> = response.kvs().iter().map(|kv| kv.key.as_bytes()).collect();
+    assert_eq!(keys, vec![b"dir/a".as_slice()]);
+
+    let options = DeleteOptions::builder().prev_kv(true).build();
+    let response = kv_client
+        .delete_with_options(KeyRange::prefix("dir/"), options)
+        .await?;
+    assert_eq!(response.prev_kvs()[0].key.as_bytes(), b"dir/a");
+    assert!(etcd.get("tenant/dir/a").is_none());
+    assert!(etcd.get("outside").is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_txn() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_root_client(&server).await.with_namespace("tenant/");
+    let mut kv_client = client.get_kv_client();
+    kv_client.put("key", "value").await?;
+
+    let key = ByteSequence::from("key");
+    let response = kv_client
+        .txn()
+        .when(vec![Compare::value_eq(key.clone(), "value")])?
+        .then(vec![
+            RequestOp::Put {
+                key: key.clone(),
+                value: ByteSequence::from("updated"),
+                options: None,
+            },
+            RequestOp::Get { key, options: None },
+        ])?
+        .commit()
+        .await?;
+    assert!(response.get_ref().succeeded);
+    let Some(response_op::Response::ResponseRange(range)) =
+        &response.get_ref().responses[1].response
+    else {
+        panic!("expected a range");
+    };
+    assert_eq!(range.kvs[0].key, b"key");
+    assert_eq!(etcd.get("tenant/key").unwrap().value, b"updated");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_watch() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_root_client(&server).await;
+    let client = root.with_namespace("tenant/");
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("key"))
+            .prev_kv(true)
+            .build()?,
+    );
+    let mut stream = client
+        .get_watch_client()
+        .watch(request)
+        .await?
+        .into_response()
+        .into_inner();
+
+    root.get_kv_client().put("key", "outside").await?;
+    root.get_kv_client().put("tenant/key", "inside").await?;
+    client.get_kv_client().put("key", "updated").await?;
+
+    for (value, prev_value) in [("inside", None), ("updated", Some("inside"))] {
+        let response = stream.message().await?.expect("watch response");
+        let event = &response.events[0];
+        let kv = event.kv.as_ref().unwrap();
+        assert_eq!(kv.key, b"key");
+        assert_eq!(kv.value, value.as_bytes());
+        assert_eq!(
+            event
+                .prev_kv
+                .as_ref()
+                .map(|kv| (kv.key.as_slice(), kv.value.as_slice())),
+            prev_value.map(|value| (b"key".as_slice(), value.as_bytes()))
+        );
+    }
+
+    Ok(())
+}
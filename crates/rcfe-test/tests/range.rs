@@ -0,0 +1,247 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, Compare, DefaultClient,
+    DefaultClientFactory, DeleteOptions, Error, GetOptions, GetResult, KVClient, KeyRange,
+    Namespace, RequestOp, Txn, WatchClient, WatchCreateOptions, WatchRequestType, Watcher,
+};
+use rcfe_test::{MockEtcd, MockServer};
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+async fn seed(client: &DefaultClient, keys: &[&str]) -> Result<(), Error> {
+    let mut kv_client = client.get_kv_client();
+    for key in keys {
+        kv_client.put(*key, "value").await?;
+    }
+    Ok(())
+}
+
+fn keys(response: &GetResult) -> Vec<&[u8]> {
+    response.kvs().iter().map(|kv| kv.key.as_bytes()).collect()
+}
+
+#[test]
+fn test_key_range_bounds() {
+    assert_eq!(KeyRange::key("a").bounds(), (b"a".to_vec(), vec![]));
+    assert_eq!(
+        KeyRange::prefix("dir/").bounds(),
+        (b"dir/".to_vec(), b"dir0".to_vec())
+    );
+    assert_eq!(KeyRange::from_key("a").bounds(), (b"a".to_vec(), vec![0]));
+    assert_eq!(
+        KeyRange::range("a", "c").bounds(),
+        (b"a".to_vec(), b"c".to_vec())
+    );
+    assert_eq!(KeyRange::all().bounds(), (vec![0], vec![0]));
+
+    // Empty prefixes and start keys select the whole keyspace.
+    assert_eq!(KeyRange::prefix("").bounds(), KeyRange::all().bounds());
+    assert_eq!(KeyRange::from_key("").bounds(), KeyRange::all().bounds());
+
+    // A prefix of 0xFF bytes cannot be incremented and extends to the end of the keyspace.
+    assert_eq!(
+        KeyRange::prefix(vec![0xFF, 0xFF]).bounds(),
+        (vec![0xFF, 0xFF], vec![0])
+    );
+    assert_eq!(
+        KeyRange::prefix(vec![b'a', 0xFF]).bounds(),
+        (vec![b'a', 0xFF], b"b".to_vec())
+    );
+
+    assert_eq!(KeyRange::from("a"), KeyRange::key("a"));
+    assert_eq!(KeyRange::from(ByteSequence::from("a")), KeyRange::key("a"));
+}
+
+#[test]
+fn test_key_range_bounds_in_namespace() {
+    let namespace = Namespace::new("ns/");
+    assert_eq!(
+        KeyRange::key("a").bounds_in(&namespace),
+        (b"ns/a".to_vec(), vec![])
+    );
+    assert_eq!(
+        KeyRange::prefix("dir/").bounds_in(&namespace),
+        (b"ns/dir/".to_vec(), b"ns/dir0".to_vec())
+    );
+    assert_eq!(
+        KeyRange::from_key("a").bounds_in(&namespace),
+        (b"ns/a".to_vec(), b"ns0".to_vec())
+    );
+    assert_eq!(
+        KeyRange::range("a", "c").bounds_in(&namespace),
+        (b"ns/a".to_vec(), b"ns/c".to_vec())
+    );
+    assert_eq!(
+        KeyRange::all().bounds_in(&namespace),
+        (b"ns/\0".to_vec(), b"ns0".to_vec())
+    );
+    assert_eq!(
+        KeyRange::all().bounds_in(&Namespace::new(vec![0xFF])),
+        (vec![0xFF, 0], vec![0])
+    );
+    assert_eq!(
+        KeyRange::prefix("dir/").bounds_in(&Namespace::default()),
+        KeyRange::prefix("dir/").bounds()
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_options_flags_map_to_ranges() {
+    let request = GetOptions::builder()
+        .prefix(true)
+        .build()
+        .to_request("dir/");
+    assert_eq!(
+        (request.key, request.range_end),
+        KeyRange::prefix("dir/").bounds()
+    );
+
+    let request = GetOptions::builder()
+        .end_key(ByteSequence::from("c"))
+        .build()
+        .to_request("a");
+    assert_eq!(
+        (request.key, request.range_end),
+        KeyRange::range("a", "c").bounds()
+    );
+
+    let request = GetOptions::builder()
+        .end_key(ByteSequence::from("\0"))
+        .build()
+        .to_request("a");
+    assert_eq!(
+        (request.key, request.range_end),
+        KeyRange::from_key("a").bounds()
+    );
+
+    // Flags only apply to single keys, explicit ranges keep their bounds.
+    let request = GetOptions::builder()
+        .prefix(true)
+        .build()
+        .to_request(KeyRange::range("a", "c"));
+    assert_eq!(
+        (request.key, request.range_end),
+        KeyRange::range("a", "c").bounds()
+    );
+
+    let request = DeleteOptions::builder()
+        .prefix(true)
+        .build()
+        .to_request(vec![0xFF]);
+    assert_eq!((request.key, request.range_end), (vec![0xFF], vec![0]));
+}
+
+#[tokio::test]
+async fn test_get_and_delete_ranges() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    seed(&client, &["a", "b", "c", "dir/a", "dir/b"]).await?;
+    let mut kv_client = client.get_kv_client();
+
+    let response = kv_client.get(KeyRange::prefix("dir/")).await?;
+    assert_eq!(keys(&response), vec![b"dir/a".as_slice(), b"dir/b"]);
+
+    let response = kv_client.get(KeyRange::range("a", "c")).await?;
+    assert_eq!(keys(&response), vec![b"a".as_slice(), b"b"]);
+
+    let response = kv_client.get(KeyRange::from_key("c")).await?;
+    assert_eq!(keys(&response), vec![b"c".as_slice(), b"dir/a", b"dir/b"]);
+
+    let response = kv_client.get(KeyRange::all()).await?;
+    assert_eq!(response.kvs().len(), 5);
+
+    let options = GetOptions::builder().count_only(true).build();
+    let response = kv_client
+        .get_with_options(KeyRange::prefix("dir/"), options)
+        .await?;
+    assert_eq!(response.count(), 2);
+
+    let response = kv_client.delete(KeyRange::prefix("dir/")).await?;
+    assert_eq!(response.deleted(), 2);
+    let response = kv_client.delete(KeyRange::range("a", "c")).await?;
+    assert_eq!(response.deleted(), 2);
+    assert!(etcd.get("c").is_some());
+
+    let response = kv_client.delete(KeyRange::all()).await?;
+    assert_eq!(response.deleted(), 1);
+    assert!(kv_client.get_all(None).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_ranges() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let root = create_client(&server).await;
+    seed(&root, &["outside", "ns0", "ns/a", "ns/b"]).await?;
+    let client = root.with_namespace("ns/");
+    let mut kv_client = client.get_kv_client();
+
+    let response = kv_client.get(KeyRange::all()).await?;
+    assert_eq!(keys(&response), vec![b"a".as_slice(), b"b"]);
+
+    let response = kv_client.get(KeyRange::from_key("b")).await?;
+    assert_eq!(keys(&response), vec![b"b".as_slice()]);
+
+    let response = kv_client.delete(KeyRange::all()).await?;
+    assert_eq!(response.deleted(), 2);
+    assert!(etcd.get("ns0").is_some());
+    assert!(etcd.get("outside").is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compare_and_watch_ranges() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    seed(&client, &["dir/a", "dir/b"]).await?;
+
+    let compare = Compare::version_eq("", 1).with_range(KeyRange::prefix("dir/"));
+    assert_eq!(compare.key.as_bytes(), b"dir/");
+    assert_eq!(compare.range_end, Some(ByteSequence::from("dir0")));
+    let response = client
+        .get_kv_client()
+        .txn()
+        .when(vec![compare])?
+        .then(vec![RequestOp::Put {
+            key: ByteSequence::from("dir/c"),
+            value: ByteSequence::from("value"),
+            options: None,
+        }])?
+        .commit()
+        .await?;
+    assert!(response.get_ref().succeeded);
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .range(KeyRange::prefix("dir/"))
+            .build()?,
+    );
+    let mut stream = client
+        .get_watch_client()
+        .watch(request)
+        .await?
+        .into_response()
+        .into_inner();
+
+    let mut kv_client = client.get_kv_client();
+    kv_client.put("other", "value").await?;
+    kv_client.put("dir/d", "value").await?;
+    let response = stream.message().await?.expect("watch response");
+    assert_eq!(response.events[0].kv.as_ref().unwrap().key, b"dir/d");
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, DefaultClient, DefaultClientFactory,
+    DeleteOptions, DeleteResult, Error, GetResult, KVClient, KeyRange, KeyValue, PutOptions,
+    PutResult, ResponseHeader,
+    etcdserverpb::{self, DeleteRangeResponse, PutResponse, RangeResponse},
+    mvccpb,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use tonic::{Response, metadata::MetadataValue};
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+fn raw_kv(key: &str, value: &str) -> mvccpb::KeyValue {
+    mvccpb::KeyValue {
+        key: key.as_bytes().to_vec(),
+        value: value.as_bytes().to_vec(),
+        create_revision: 2,
+        mod_revision: 3,
+        version: 2,
+        lease: 7,
+    }
+}
+
+fn raw_header() -> etcdserverpb::ResponseHeader {
+    etcdserverpb::ResponseHeader {
+        cluster_id: 1,
+        member_id: 2,
+        revision: 3,
+        raft_term: 4,
+    }
+}
+
+#[test]
+fn test_get_result_from_raw() {
+    let mut raw = Response::new(RangeResponse {
+        header: Some(raw_header()),
+        kvs: vec![raw_kv("a", "1"), raw_kv("b", "2")],
+        more: true,
+        count: 5,
+    });
+    raw.metadata_mut()
+        .insert("x-trace", MetadataValue::from_static("trace"));
+
+    let result = GetResult::from(raw);
+    assert_eq!(
+        *result.header(),
+        ResponseHeader {
+            cluster_id: 1,
+            member_id: 2,
+            revision: 3,
+            raft_term: 4,
+        }
+    );
+    assert_eq!(
+        result.first(),
+        Some(&KeyValue {
+            key: ByteSequence::from("a"),
+            value: ByteSequence::from("1"),
+            create_revision: 2,
+            mod_revision: 3,
+            version: 2,
+            lease: 7,
+        })
+    );
+    assert!(result.more());
+    assert_eq!(result.count(), 5);
+    assert_eq!(result.metadata().get("x-trace").unwrap(), "trace");
+
+    // The raw response survives the round trip.
+    let raw = result.clone().into_raw();
+    assert_eq!(raw.metadata().get("x-trace").unwrap(), "trace");
+    assert_eq!(raw.get_ref().kvs, vec![raw_kv("a", "1"), raw_kv("b", "2")]);
+    assert_eq!(raw.get_ref().header, Some(raw_header()));
+
+    let keys: Vec<ByteSequence> = result.into_iter().map(|kv| kv.key).collect();
+    assert_eq!(keys, vec![ByteSequence::from("a"), ByteSequence::from("b")]);
+}
+
+#[test]
+fn test_put_and_delete_results_from_raw() {
+    let result = PutResult::from(PutResponse {
+        header: Some(raw_header()),
+        prev_kv: Some(raw_kv("a", "1")),
+    });
+    assert_eq!(result.header().member_id, 2);
+    assert_eq!(result.prev_kv().unwrap().value, ByteSequence::from("1"));
+    assert_eq!(
+        result.into_raw().into_inner().prev_kv,
+        Some(raw_kv("a", "1"))
+    );
+
+    let result = DeleteResult::from(DeleteRangeResponse {
+        header: Some(raw_header()),
+        deleted: 2,
+        prev_kvs: vec![raw_kv("a", "1")],
+    });
+    assert_eq!(result.header().revision, 3);
+    assert_eq!(result.deleted(), 2);
+    assert_eq!(result.prev_kvs()[0].key, ByteSequence::from("a"));
+    assert_eq!(result.into_raw().into_inner().deleted, 2);
+
+    // A missing header reads as zeroes.
+    let result = GetResult::from(RangeResponse::default());
+    assert_eq!(*result.header(), ResponseHeader::default());
+    assert!(result.is_empty());
+}
+
+#[tokio::test]
+async fn test_kv_results() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let put = kv_client.put("key", "1").await?;
+    assert_eq!(put.header().revision, 1);
+    assert_eq!(put.header().member_id, 1);
+    assert!(put.prev_kv().is_none());
+
+    let options = PutOptions::builder().prev_kv(true).build();
+    let put = kv_client.put_with_options("key", "2", options).await?;
+    let prev_kv = put.prev_kv().expect("previous key-value pair");
+    assert_eq!(prev_kv.key, ByteSequence::from("key"));
+    assert_eq!(prev_kv.value, ByteSequence::from("1"));
+
+    let get = kv_client.get("key").await?;
+    assert_eq!(get.header().revision, 2);
+    let kv = get.first().expect("key-value pair");
+    assert_eq!(kv.value, ByteSequence::from("2"));
+    assert_eq!(kv.version, 2);
+    assert_eq!(get.count(), 1);
+
+    let options = DeleteOptions::builder().prev_kv(true).build();
+    let delete = kv_client
+        .delete_with_options(KeyRange::all(), options)
+        .await?;
+    assert_eq!(delete.deleted(), 1);
+    assert_eq!(delete.prev_kvs()[0].value, ByteSequence::from("2"));
+    assert_eq!(delete.header().revision, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_kv_results() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await.with_namespace("tenant/");
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("key", "value").await?;
+    let get = kv_client.get("key").await?;
+    assert_eq!(get.kvs()[0].key, ByteSequence::from("key"));
+    assert_eq!(get.into_raw().get_ref().kvs[0].key, b"key");
+
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, Compare, DefaultClientFactory, Error,
+    KVClient, RequestOp, RetryPolicy, Txn,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+use tonic::Code;
+
+async fn create_client(server: &MockServer, policy: RetryPolicy) -> impl Client {
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .retry_policy(policy)
+        .build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+fn policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::builder()
+        .max_attempts(max_attempts)
+        .initial_backoff(Duration::from_millis(10))
+        .max_backoff(Duration::from_millis(50))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_get_is_retried() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("retry_key", "value").await?;
+
+    etcd.fail_next(2, Code::Unavailable);
+    let response = kv_client.get("retry_key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"value".to_vec());
+    assert_eq!(etcd.requests(), 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retries_are_bounded_by_max_attempts() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.fail_next(5, Code::Unavailable);
+    let result = kv_client.get("retry_key").await;
+    assert!(matches!(result, Err(Error::TonicStatus(status)) if status.code() == Code::Unavailable));
+    assert_eq!(etcd.requests(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_only_retryable_codes_are_retried() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.fail_next(1, Code::InvalidArgument);
+    assert!(kv_client.get("retry_key").await.is_err());
+    assert_eq!(etcd.requests(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_is_retried_during_leader_election() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .retry_policy(policy(20))
+        .require_leader()
+        .build();
+    let client = DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client");
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("retry_key", "value").await?;
+
+    // The member elects a new leader while the request is retried.
+    etcd.set_leader(false);
+    let requests = etcd.requests();
+    let elected = {
+        let etcd = etcd.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            etcd.set_leader(true);
+        })
+    };
+    let response = kv_client.get("retry_key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"value".to_vec());
+    assert!(etcd.requests() > requests + 1);
+    elected.await.unwrap();
+
+    // Without retryable `Unavailable` the no leader error is returned.
+    let policy = RetryPolicy::builder()
+        .max_attempts(3)
+        .retryable_codes([Code::DeadlineExceeded])
+        .build()?;
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .retry_policy(policy)
+        .require_leader()
+        .build();
+    let client = DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client");
+    etcd.set_leader(false);
+    let requests = etcd.requests();
+    let result = client.get_kv_client().get("retry_key").await;
+    assert!(matches!(result, Err(Error::NoLeader(_))), "{result:?}");
+    assert_eq!(etcd.requests(), requests + 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_put_is_not_retried() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.fail_next(1, Code::Unavailable);
+    assert!(kv_client.put("retry_key", "value").await.is_err());
+    assert_eq!(etcd.requests(), 1);
+    assert!(etcd.get("retry_key").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guarded_txn_is_retried() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+    let key = ByteSequence::from("retry_key");
+
+    etcd.fail_next(1, Code::Unavailable);
+    let response = kv_client
+        .txn()
+        .when(vec![Compare::version_eq(key.clone(), 0)])?
+        .then(vec![RequestOp::Put {
+            key: key.clone(),
+            value: ByteSequence::from("value"),
+            options: None,
+        }])?
+        .commit()
+        .await?;
+
+    assert!(response.get_ref().succeeded);
+    assert_eq!(etcd.requests(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unguarded_txn_is_not_retried() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.fail_next(1, Code::Unavailable);
+    let result = kv_client
+        .txn()
+        .then(vec![RequestOp::Put {
+            key: ByteSequence::from("retry_key"),
+            value: ByteSequence::from("value"),
+            options: None,
+        }])?
+        .commit()
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(etcd.requests(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_txn_writing_unguarded_keys_is_not_retried() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, policy(3)).await;
+    let mut kv_client = client.get_kv_client();
+    let put = |key: &str| RequestOp::Put {
+        key: ByteSequence::from(key),
+        value: ByteSequence::from("value"),
+        options: None,
+    };
+
+    // The compare on one key does not guard the put to another.
+    etcd.fail_next(1, Code::Unavailable);
+    let result = kv_client
+        .txn()
+        .when(vec![Compare::version_eq(ByteSequence::from("guard"), 0)])?
+        .then(vec![put("retry_key")])?
+        .commit()
+        .await;
+    assert!(result.is_err());
+    assert_eq!(etcd.requests(), 1);
+
+    // The failure branch writes without a guard.
+    etcd.fail_next(1, Code::Unavailable);
+    let result = kv_client
+        .txn()
+        .when(vec![Compare::version_eq(ByteSequence::from("retry_key"), 0)])?
+        .then(vec![put("retry_key")])?
+        .otherwise(vec![put("other_key")])?
+        .commit()
+        .await;
+    assert!(result.is_err());
+    assert_eq!(etcd.requests(), 2);
+    assert!(etcd.get("retry_key").is_none());
+    assert!(etcd.get("other_key").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_backoff_is_capped_and_jittered() {
+    let policy = RetryPolicy::builder()
+        .initial_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_millis(400))
+        .multiplier(2.0)
+        .jitter(0.5)
+        .build()
+        .unwrap();
+
+    let first = policy.backoff(1);
+    assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(150));
+
+    let capped = policy.backoff(10);
+    assert!(capped >= Duration::from_millis(200) && capped <= Duration::from_millis(600));
+}
+
+#[test]
+fn test_invalid_retry_policy() {
+    assert!(matches!(
+        RetryPolicy::builder().max_attempts(0).build(),
+        Err(Error::IllegalArgument(_))
+    ));
+    assert!(matches!(
+        RetryPolicy::builder().jitter(1.5).build(),
+        Err(Error::IllegalArgument(_))
+    ));
+}
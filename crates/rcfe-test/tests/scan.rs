@@ -0,0 +1,217 @@
+use futures_util::{StreamExt, TryStreamExt};
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, DefaultClient, DefaultClientFactory, Error,
+    GetOptions, KVClient, KeyRange, KeyValue, SortOrderOption, SortTargetOption,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::pin::pin;
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+fn keys(kvs: &[KeyValue]) -> Vec<String> {
+    kvs.iter()
+        .map(|kv| String::from_utf8_lossy(kv.key.as_bytes()).into_owned())
+        .collect()
+}
+
+async fn put_services<K: KVClient>(kv_client: &mut K, count: usize) -> Result<(), Error> {
+    for i in 0..count {
+        kv_client
+            .put(format!("services/{i:02}"), format!("value-{i}"))
+            .await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scan_pages() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    put_services(&mut kv_client, 7).await?;
+    kv_client.put("other", "value").await?;
+
+    let kvs: Vec<KeyValue> = kv_client
+        .scan(KeyRange::prefix("services/"), 3)
+        .try_collect()
+        .await?;
+    assert_eq!(
+        keys(&kvs),
+        (0..7)
+            .map(|i| format!("services/{i:02}"))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(kvs[6].value, ByteSequence::from("value-6"));
+
+    // A page size dividing the range exactly still ends the scan.
+    let kvs: Vec<KeyValue> = kv_client.scan(KeyRange::all(), 4).try_collect().await?;
+    assert_eq!(kvs.len(), 8);
+    assert_eq!(keys(&kvs)[7], "services/06");
+
+    let kvs: Vec<KeyValue> = kv_client
+        .scan(KeyRange::from_key("services/05"), 1)
+        .try_collect()
+        .await?;
+    assert_eq!(keys(&kvs), vec!["services/05", "services/06"]);
+
+    let kvs: Vec<KeyValue> = kv_client.scan("missing", 10).try_collect().await?;
+    assert!(kvs.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn test_scan_with_options() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    put_services(&mut kv_client, 5).await?;
+
+    let options = GetOptions::builder()
+        .sort_order(SortOrderOption::Descend)
+        .sort_target(SortTargetOption::Key)
+        .keys_only(true)
+        .build();
+    let kvs: Vec<KeyValue> = kv_client
+        .scan_with_options(KeyRange::prefix("services/"), 2, options)
+        .try_collect()
+        .await?;
+    assert_eq!(
+        keys(&kvs),
+        vec![
+            "services/04",
+            "services/03",
+            "services/02",
+            "services/01",
+            "services/00"
+        ]
+    );
+    assert!(kvs.iter().all(|kv| kv.value.as_bytes().is_empty()));
+
+    // The limit caps the whole scan rather than a page.
+    let options = GetOptions::builder().limit(3).build();
+    let kvs: Vec<KeyValue> = kv_client
+        .scan_with_options(KeyRange::prefix("services/"), 2, options)
+        .try_collect()
+        .await?;
+    assert_eq!(
+        keys(&kvs),
+        vec!["services/00", "services/01", "services/02"]
+    );
+
+    // The legacy prefix flag selects the range too.
+    let options = GetOptions::builder().prefix(true).build();
+    let kvs: Vec<KeyValue> = kv_client
+        .scan_with_options("services/0", 2, options)
+        .try_collect()
+        .await?;
+    assert_eq!(kvs.len(), 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scan_snapshot() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+    let mut writer = client.get_kv_client();
+
+    put_services(&mut kv_client, 4).await?;
+
+    let mut scan = pin!(kv_client.scan(KeyRange::prefix("services/"), 2));
+    let first = scan.next().await.expect("first key")?;
+    assert_eq!(first.key, ByteSequence::from("services/00"));
+
+    // Changes after the first page are invisible to the scan.
+    writer.put("services/01a", "new").await?;
+    writer.put("services/03", "updated").await?;
+    writer.delete("services/02").await?;
+
+    let rest: Vec<KeyValue> = scan.try_collect().await?;
+    assert_eq!(
+        keys(&rest),
+        vec!["services/01", "services/02", "services/03"]
+    );
+    assert_eq!(rest[2].value, ByteSequence::from("value-3"));
+
+    // An explicit revision pins the scan as well.
+    let options = GetOptions::builder().revision(2).build();
+    let kvs: Vec<KeyValue> = writer
+        .scan_with_options(KeyRange::prefix("services/"), 1, options)
+        .try_collect()
+        .await?;
+    assert_eq!(keys(&kvs), vec!["services/00", "services/01"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespaced_scan() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let tenant = client.with_namespace("tenant/");
+
+    put_services(&mut tenant.get_kv_client(), 3).await?;
+    client.get_kv_client().put("services/99", "outside").await?;
+
+    let kvs: Vec<KeyValue> = tenant
+        .get_kv_client()
+        .scan(KeyRange::all(), 1)
+        .try_collect()
+        .await?;
+    assert_eq!(
+        keys(&kvs),
+        vec!["services/00", "services/01", "services/02"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scan_invalid_arguments() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("key", "value").await?;
+
+    let result: Result<Vec<KeyValue>, Error> = kv_client.scan("key", 0).try_collect().await;
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+
+    let options = GetOptions::builder()
+        .sort_order(SortOrderOption::Ascend)
+        .sort_target(SortTargetOption::Value)
+        .build();
+    let result: Result<Vec<KeyValue>, Error> = kv_client
+        .scan_with_options("key", 10, options)
+        .try_collect()
+        .await;
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+
+    let options = GetOptions::builder().count_only(true).build();
+    let result: Result<Vec<KeyValue>, Error> = kv_client
+        .scan_with_options("key", 10, options)
+        .try_collect()
+        .await;
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+
+    Ok(())
+}
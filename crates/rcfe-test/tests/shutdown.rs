@@ -0,0 +1,179 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, DefaultClient, DefaultClientFactory, Error,
+    KVClient, KeepAliveHandler, LeaseClient, ShutdownOptions, WatchClient, WatchCreateOptions,
+    WatchRequestType, Watcher,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::{Duration, Instant};
+
+const DELAY: Duration = Duration::from_millis(300);
+
+async fn create_client(endpoint: String) -> DefaultClient {
+    let options = ClientOptions::builder().endpoints(vec![endpoint]).build();
+
+    DefaultClientFactory::new()
+        .create(options)
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_requests_fail_after_shutdown() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("shutdown_key", "value").await?;
+    client.shutdown().await?;
+
+    let requests = etcd.requests();
+    let result = kv_client.get("shutdown_key").await;
+    assert!(matches!(result, Err(Error::ClientClosed)));
+    assert_eq!(etcd.requests(), requests);
+
+    // A second shutdown has no effect.
+    client.shutdown().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shutdown_waits_for_in_flight_requests() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+
+    etcd.set_delay(Some(DELAY));
+    let worker = client.clone();
+    let in_flight =
+        tokio::spawn(async move { worker.get_kv_client().put("in_flight_key", "value").await });
+    tokio::time::sleep(DELAY / 3).await;
+
+    let started = Instant::now();
+    client.shutdown().await?;
+    assert!(started.elapsed() >= DELAY / 2);
+
+    in_flight.await.expect("request task panicked")?;
+    assert!(etcd.get("in_flight_key").is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shutdown_deadline() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+
+    etcd.set_delay(Some(DELAY * 3));
+    let worker = client.clone();
+    let _in_flight = tokio::spawn(async move { worker.get_kv_client().get("slow_key").await });
+    tokio::time::sleep(DELAY / 3).await;
+
+    let options = ShutdownOptions::builder().timeout(DELAY / 3).build();
+    let result = client.shutdown_with_options(options).await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shutdown_cancels_watchers() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+    let mut watch_client = client.get_watch_client();
+
+    let request = WatchRequestType::Create(
+        WatchCreateOptions::builder()
+            .key(ByteSequence::from("watched_key"))
+            .build()?,
+    );
+    let mut watcher = watch_client.watch(request.clone()).await?;
+    let watch_id = watcher.id();
+    let mut stream = client
+        .get_watch_client()
+        .watch(request)
+        .await?
+        .into_response()
+        .into_inner();
+
+    client.shutdown().await?;
+
+    let response = stream.message().await?.expect("cancel response");
+    assert!(response.canceled);
+    // The request side is closed, so the stream ends although the watcher is still open.
+    let end = tokio::time::timeout(DELAY, stream.message())
+        .await
+        .expect("watch stream did not end");
+    assert!(end?.is_none());
+    assert!(matches!(watcher.watch().await, Err(Error::ClientClosed)));
+    assert!(matches!(watcher.progress().await, Err(Error::ClientClosed)));
+    assert_eq!(watch_id, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shutdown_stops_keep_alives() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+    let mut lease_client = client.get_lease_client();
+
+    let lease_id = lease_client
+        .grant(Duration::from_secs(10))
+        .await?
+        .get_ref()
+        .id;
+    let mut handler = lease_client.keep_alive(lease_id).await?;
+    handler.keep_alive().await?;
+
+    client.shutdown().await?;
+
+    assert!(matches!(
+        handler.keep_alive().await,
+        Err(Error::ClientClosed)
+    ));
+    assert_eq!(etcd.leases(), vec![lease_id]);
+
+    let mut stream = handler.into_response().into_inner();
+    let end = tokio::time::timeout(DELAY, async {
+        while stream.message().await?.is_some() {}
+        Ok::<_, tonic::Status>(())
+    })
+    .await
+    .expect("keep-alive stream did not end");
+    end?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shutdown_revokes_granted_leases() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(server.endpoint()).await;
+    let mut lease_client = client.get_lease_client();
+
+    let first = lease_client
+        .grant(Duration::from_secs(10))
+        .await?
+        .get_ref()
+        .id;
+    let second = lease_client
+        .grant(Duration::from_secs(10))
+        .await?
+        .get_ref()
+        .id;
+    lease_client.revoke(first).await?;
+    assert_eq!(etcd.leases(), vec![second]);
+
+    let options = ShutdownOptions::builder().revoke_leases(true).build();
+    client.shutdown_with_options(options).await?;
+
+    assert!(etcd.leases().is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use rcfe::{
+    Client, ClientFactory, ClientOptions, DefaultClientFactory, DeleteOptions, Error, GetOptions,
+    KVClient, PutOptions,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+
+const SHORT: Duration = Duration::from_millis(100);
+const DELAY: Duration = Duration::from_millis(500);
+
+async fn create_client(server: &MockServer, request_timeout: Option<Duration>) -> impl Client {
+    let mut builder = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .connect_timeout(Duration::from_secs(5))
+        .keep_alive_interval(Duration::from_secs(10))
+        .keep_alive_timeout(Duration::from_secs(5));
+    if let Some(timeout) = request_timeout {
+        builder = builder.request_timeout(timeout);
+    }
+
+    DefaultClientFactory::new()
+        .create(builder.build())
+        .await
+        .expect("Failed to create client")
+}
+
+#[tokio::test]
+async fn test_default_request_timeout() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, Some(SHORT)).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("timeout_key", "value").await?;
+
+    etcd.set_delay(Some(DELAY));
+    let result = kv_client.get("timeout_key").await;
+    assert!(matches!(result, Err(Error::Timeout(timeout)) if timeout == SHORT));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_per_call_timeout_overrides_default() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, Some(SHORT)).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.set_delay(Some(DELAY));
+    let options = GetOptions::builder().timeout(Duration::from_secs(5)).build();
+    let response = kv_client.get_with_options("timeout_key", options).await?;
+    assert!(response.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_per_call_timeout_without_default() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server, None).await;
+    let mut kv_client = client.get_kv_client();
+
+    etcd.set_delay(Some(DELAY));
+
+    let options = PutOptions::builder().timeout(SHORT).build();
+    let result = kv_client.put_with_options("timeout_key", "value", options).await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+
+    let options = DeleteOptions::builder().timeout(SHORT).build();
+    let result = kv_client.delete_with_options("timeout_key", options).await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+
+    // Without any deadline the call waits for the slow member.
+    kv_client.put("timeout_key", "value").await?;
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use rcfe::{Client, ClientFactory, ClientOptions, DefaultClientFactory, Error, KVClient, TlsOptions};
+use rcfe_test::{MockEtcd, MockServer};
+use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+/// A locally generated CA with a server and a client certificate signed by it.
+struct TestPki {
+    ca: String,
+    server_cert: String,
+    server_key: String,
+    client_cert: String,
+    client_key: String,
+}
+
+impl TestPki {
+    fn generate(server_name: &str) -> TestPki {
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let server_cert = CertificateParams::new(vec![server_name.to_string()])
+            .unwrap()
+            .signed_by(&server_key, &ca)
+            .unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let client_cert = CertificateParams::new(vec!["client".to_string()])
+            .unwrap()
+            .signed_by(&client_key, &ca)
+            .unwrap();
+
+        TestPki {
+            ca: ca.pem(),
+            server_cert: server_cert.pem(),
+            server_key: server_key.serialize_pem(),
+            client_cert: client_cert.pem(),
+            client_key: client_key.serialize_pem(),
+        }
+    }
+
+    fn server_tls(&self) -> ServerTlsConfig {
+        ServerTlsConfig::new()
+            .identity(Identity::from_pem(&self.server_cert, &self.server_key))
+            .client_ca_root(Certificate::from_pem(&self.ca))
+    }
+}
+
+#[tokio::test]
+async fn test_mutual_tls() -> Result<(), Error> {
+    let pki = TestPki::generate("localhost");
+    let server = MockServer::start_tls(MockEtcd::new(), pki.server_tls());
+
+    let tls = TlsOptions::builder()
+        .ca_cert_pem(pki.ca.as_str())
+        .client_cert_pem(pki.client_cert.as_str())
+        .client_key_pem(pki.client_key.as_str())
+        .build()?;
+
+    let options = ClientOptions::builder()
+        .endpoints(vec![format!("https://localhost:{}", server.addr().port())])
+        .tls(tls)
+        .build();
+
+    let client = DefaultClientFactory::new().create(options).await?;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("tls_key", "tls_value").await?;
+    let response = kv_client.get("tls_key").await?;
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"tls_value".to_vec());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tls_domain_name_override() -> Result<(), Error> {
+    let pki = TestPki::generate("etcd.cluster.local");
+    let server = MockServer::start_tls(MockEtcd::new(), pki.server_tls());
+
+    let tls = TlsOptions::builder()
+        .ca_cert_pem(pki.ca.as_str())
+        .client_cert_pem(pki.client_cert.as_str())
+        .client_key_pem(pki.client_key.as_str())
+        .domain_name("etcd.cluster.local")
+        .build()?;
+
+    let options = ClientOptions::builder()
+        .endpoints(vec![format!("https://{}", server.addr())])
+        .tls(tls)
+        .build();
+
+    let client = DefaultClientFactory::new().create(options).await?;
+    let response = client.get_kv_client().get("missing").await?;
+    assert!(response.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tls_without_client_certificate_is_rejected() -> Result<(), Error> {
+    let pki = TestPki::generate("localhost");
+    let server = MockServer::start_tls(MockEtcd::new(), pki.server_tls());
+
+    let tls = TlsOptions::builder().ca_cert_pem(pki.ca.as_str()).build()?;
+
+    let options = ClientOptions::builder()
+        .endpoints(vec![format!("https://localhost:{}", server.addr().port())])
+        .tls(tls)
+        .build();
+
+    let client = DefaultClientFactory::new().create(options).await?;
+    assert!(client.get_kv_client().get("tls_key").await.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_tls_options_require_cert_and_key_together() {
+    let result = TlsOptions::builder().client_cert_pem("cert").build();
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+}
+
+#[tokio::test]
+async fn test_tls_missing_ca_file() -> Result<(), Error> {
+    let tls = TlsOptions::builder()
+        .ca_cert_file("/nonexistent/rcfe/ca.pem")
+        .build()?;
+
+    let options = ClientOptions::builder()
+        .endpoints(vec!["https://localhost:2379"])
+        .tls(tls)
+        .build();
+
+    let result = DefaultClientFactory::new().create(options).await;
+    assert!(matches!(result, Err(Error::Io(_))));
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use rcfe::{
+    BincodeCodec, BoxError, ByteSequence, Client, ClientFactory, ClientOptions, Codec,
+    DefaultClient, DefaultClientFactory, Error, JsonCodec, KVClient, KeyRange, MessagePackCodec,
+    PutOptions, TypedKv,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Service {
+    name: String,
+    replicas: u32,
+}
+
+fn service(name: &str, replicas: u32) -> Service {
+    Service {
+        name: name.to_string(),
+        replicas,
+    }
+}
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+/// Stores services as `name:replicas` strings.
+struct ServiceCodec;
+
+impl Codec<Service> for ServiceCodec {
+    fn encode(&self, value: &Service) -> Result<Vec<u8>, BoxError> {
+        Ok(format!("{}:{}", value.name, value.replicas).into_bytes())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Service, BoxError> {
+        let text = std::str::from_utf8(bytes)?;
+        let (name, replicas) = text.split_once(':').ok_or("missing separator")?;
+        Ok(service(name, replicas.parse()?))
+    }
+}
+
+async fn assert_round_trip<C: Codec<Service>>(
+    client: &DefaultClient,
+    codec: C,
+) -> Result<(), Error> {
+    let mut services = client.get_kv_client().typed(codec);
+    services.put("services/api", &service("api", 3)).await?;
+    services.put("services/web", &service("web", 2)).await?;
+
+    let api = services.get("services/api").await?.expect("api service");
+    assert_eq!(api.key, ByteSequence::from("services/api"));
+    assert_eq!(api.value, service("api", 3));
+    assert_eq!(api.version, 1);
+    assert!(api.mod_revision > 0);
+    assert!(services.get("services/missing").await?.is_none());
+
+    let all = services.get_range(KeyRange::prefix("services/")).await?;
+    let values: Vec<Service> = all.into_iter().map(|value| value.value).collect();
+    assert_eq!(values, vec![service("api", 3), service("web", 2)]);
+
+    assert_eq!(
+        services
+            .delete(KeyRange::prefix("services/"))
+            .await?
+            .deleted(),
+        2
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_typed_codecs() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+
+    assert_round_trip(&client, JsonCodec).await?;
+    assert_round_trip(&client, MessagePackCodec).await?;
+    assert_round_trip(&client, BincodeCodec).await?;
+    assert_round_trip(&client, ServiceCodec).await?;
+
+    let mut services = client.get_kv_client().typed(JsonCodec);
+    services.put("services/api", &service("api", 3)).await?;
+    assert_eq!(
+        etcd.get("services/api").unwrap().value,
+        br#"{"name":"api","replicas":3}"#
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_typed_prev_value() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut services = TypedKv::new(client.get_kv_client(), JsonCodec);
+
+    services.put("api", &service("api", 1)).await?;
+    let options = PutOptions::builder().prev_kv(true).build();
+    let result = services
+        .put_with_options("api", &service("api", 2), options)
+        .await?;
+    let prev = services.decode(result.into_prev_kv().expect("previous value"))?;
+    assert_eq!(prev.value, service("api", 1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_typed_decode_error() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut services = client.get_kv_client().typed::<Service, _>(JsonCodec);
+
+    services
+        .client_mut()
+        .put("services/raw", "not json")
+        .await?;
+    match services.get("services/raw").await {
+        Err(Error::Decode(message)) => assert!(message.contains("services/raw"), "{message}"),
+        other => panic!("expected a decode error, got {other:?}"),
+    }
+    assert!(matches!(
+        services.get_range(KeyRange::prefix("services/")).await,
+        Err(Error::Decode(_))
+    ));
+
+    // Values written with another codec do not decode either.
+    let mut bincode = client.get_kv_client().typed(BincodeCodec);
+    bincode.put("services/api", &service("api", 3)).await?;
+    assert!(matches!(
+        services.get("services/api").await,
+        Err(Error::Decode(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_typed_encode_error() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut maps = client.get_kv_client().typed(JsonCodec);
+
+    // JSON objects only have string keys.
+    let value = HashMap::from([((1, 2), String::from("value"))]);
+    assert!(matches!(
+        maps.put("map", &value).await,
+        Err(Error::Encode(_))
+    ));
+    assert!(etcd.get("map").is_none());
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use rcfe::{Client, ClientFactory, ClientOptions, DefaultClientFactory, Error, KVClient};
+use rcfe_test::{MockEtcd, MockServer};
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+const INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns a socket path that is unique to this test run.
+fn socket_path() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("rcfe-{}-{id}.sock", std::process::id()))
+}
+
+async fn create_client(options: ClientOptions) -> Result<impl Client, Error> {
+    DefaultClientFactory::new().create(options).await
+}
+
+#[tokio::test]
+async fn test_put_and_get_over_unix_socket() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start_unix(etcd.router(), socket_path());
+    assert!(server.endpoint().starts_with("unix://"));
+
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .build();
+    let client = create_client(options).await?;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("unix_key", "unix_value").await?;
+    let response = kv_client.get("unix_key").await?;
+
+    assert_eq!(response.kvs().len(), 1);
+    assert_eq!(response.kvs()[0].value.as_bytes(), b"unix_value".to_vec());
+    assert!(etcd.requests() >= 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unix_socket_is_health_checked() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start_unix(etcd.router(), socket_path());
+
+    let options = ClientOptions::builder()
+        .endpoints(vec![server.endpoint()])
+        .health_check_interval(INTERVAL)
+        .build();
+    let client = create_client(options).await?;
+
+    for _ in 0..40 {
+        if client.endpoint_statuses()[0].last_checked.is_some() {
+            break;
+        }
+        tokio::time::sleep(INTERVAL).await;
+    }
+
+    let statuses = client.endpoint_statuses();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].endpoint, server.endpoint());
+    assert!(statuses[0].healthy);
+    assert!(statuses[0].last_checked.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unix_socket_cannot_be_combined_with_other_endpoints() {
+    let options = ClientOptions::builder()
+        .endpoints(vec!["unix:///tmp/rcfe.sock", "http://127.0.0.1:2379"])
+        .build();
+
+    let result = create_client(options).await;
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+}
+
+#[tokio::test]
+async fn test_unix_socket_does_not_support_member_discovery() {
+    let options = ClientOptions::builder()
+        .endpoints(vec!["unix:///tmp/rcfe.sock"])
+        .auto_sync_interval(INTERVAL)
+        .build();
+
+    let result = create_client(options).await;
+    assert!(matches!(result, Err(Error::IllegalArgument(_))));
+}
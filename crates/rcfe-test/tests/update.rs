@@ -0,0 +1,191 @@
+use rcfe::{
+    ByteSequence, Client, ClientFactory, ClientOptions, DefaultClient, DefaultClientFactory, Error,
+    KVClient, KeyValue, PutOptions, RetryPolicy, UpdateOutcome,
+};
+use rcfe_test::{MockEtcd, MockServer};
+use std::time::Duration;
+
+async fn create_client(server: &MockServer) -> DefaultClient {
+    DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .build(),
+        )
+        .await
+        .expect("Failed to create client")
+}
+
+fn increment(current: Option<&KeyValue>) -> Option<String> {
+    let count: u64 = current
+        .map(|kv| {
+            String::from_utf8_lossy(kv.value.as_bytes())
+                .parse()
+                .unwrap()
+        })
+        .unwrap_or(0);
+    Some((count + 1).to_string())
+}
+
+fn policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::builder()
+        .max_attempts(max_attempts)
+        .initial_backoff(Duration::from_millis(1))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_update() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let created = kv_client.update("counter", increment).await?;
+    assert!(created.is_updated());
+    let created = created.into_kv().unwrap();
+    assert_eq!(created.value, ByteSequence::from("1"));
+    assert_eq!(created.version, 1);
+    assert_eq!(created.create_revision, created.mod_revision);
+
+    let updated = kv_client.update("counter", increment).await?;
+    let updated = updated.kv().unwrap();
+    assert_eq!(updated.key, ByteSequence::from("counter"));
+    assert_eq!(updated.value, ByteSequence::from("2"));
+    assert_eq!(updated.version, 2);
+    assert_eq!(updated.create_revision, created.create_revision);
+
+    let stored = etcd.get("counter").unwrap();
+    assert_eq!(stored.mod_revision, updated.mod_revision);
+    assert_eq!(stored.version, updated.version);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_unchanged() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let outcome = kv_client.update("missing", |_| None::<String>).await?;
+    assert_eq!(outcome, UpdateOutcome::Unchanged(None));
+    assert!(etcd.get("missing").is_none());
+
+    kv_client.put("config", "done").await?;
+    let revision = etcd.revision();
+    let outcome = kv_client
+        .update("config", |current| {
+            let current = current?;
+            (current.value.as_bytes() != b"done").then_some("done")
+        })
+        .await?;
+    assert!(!outcome.is_updated());
+    assert_eq!(outcome.kv().unwrap().value, ByteSequence::from("done"));
+    assert_eq!(etcd.revision(), revision);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_retries_conflicts() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("counter", "1").await?;
+
+    // A concurrent writer bumps the counter after the first read.
+    let mut calls = 0;
+    let outcome = kv_client
+        .update_with_policy("counter", policy(3), |current| {
+            calls += 1;
+            if calls == 1 {
+                etcd.put("counter", "10");
+            }
+            increment(current)
+        })
+        .await?;
+    assert_eq!(calls, 2);
+    assert_eq!(outcome.kv().unwrap().value, ByteSequence::from("11"));
+    assert_eq!(etcd.get("counter").unwrap().value, b"11");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_conflict_error() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let mut calls = 0;
+    let result = kv_client
+        .update_with_policy("counter", policy(3), |current| {
+            calls += 1;
+            etcd.put("counter", format!("{}", 100 + calls));
+            increment(current)
+        })
+        .await;
+    assert!(matches!(result, Err(Error::Conflict(message)) if message.contains("counter")));
+    assert_eq!(calls, 3);
+    assert_eq!(etcd.get("counter").unwrap().value, b"103");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_keeps_lease() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = create_client(&server).await;
+    let mut kv_client = client.get_kv_client();
+
+    let options = PutOptions::builder().lease(42).build();
+    kv_client.put_with_options("session", "1", options).await?;
+
+    let outcome = kv_client.update("session", increment).await?;
+    assert_eq!(outcome.kv().unwrap().lease, 42);
+    assert_eq!(etcd.get("session").unwrap().lease, 42);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_is_applied_once_when_response_is_lost() -> Result<(), Error> {
+    let etcd = MockEtcd::new();
+    let server = MockServer::start(etcd.router());
+    let client = DefaultClientFactory::new()
+        .create(
+            ClientOptions::builder()
+                .endpoints(vec![server.endpoint()])
+                .retry_policy(policy(3))
+                .build(),
+        )
+        .await
+        .expect("Failed to create client");
+    let mut kv_client = client.get_kv_client();
+
+    kv_client.put("counter", "1").await?;
+
+    // The increment is applied, but its response never arrives.
+    etcd.drop_next_responses(1);
+    let mut calls = 0;
+    let result = kv_client
+        .update_with_policy("counter", policy(3), |current| {
+            calls += 1;
+            increment(current)
+        })
+        .await;
+    assert!(
+        matches!(result, Err(Error::TonicStatus(status)) if status.code() == tonic::Code::Unavailable)
+    );
+    assert_eq!(calls, 1);
+    assert_eq!(etcd.get("counter").unwrap().value, b"2");
+
+    Ok(())
+}
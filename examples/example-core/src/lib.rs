@@ -7,7 +7,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use dotenvy::dotenv;
-use rcfe::{Client, DefaultClient};
+use rcfe::{Client, DefaultClient, DynClient};
 use serde::Serialize;
 use std::sync::{Arc, Once};
 
@@ -18,19 +18,18 @@ pub use html::{HtmlMetadata, HtmlTemplate, I18nMetadata};
 pub use i18n::{Lang, t, DEFAULT_LANG};
 
 #[derive(Clone)]
-pub struct AppState<C>
-where
-    C: Client,
-{
-    pub client: C,
+pub struct AppState {
+    pub client: Arc<dyn DynClient>,
 }
 
-impl<C> AppState<C>
-where
-    C: Client,
-{
-    pub fn new(client: C) -> Self {
-        AppState { client }
+impl AppState {
+    pub fn new<C>(client: C) -> Self
+    where
+        C: Client + Send + Sync + 'static,
+    {
+        AppState {
+            client: Arc::new(client),
+        }
     }
 }
 
@@ -3,8 +3,10 @@ use axum::{Form, extract::Path, extract::State, response::IntoResponse};
 use example_core::{
     AppError, DEFAULT_LANG, DefaultAppState, HtmlMetadata, HtmlTemplate, I18nMetadata, Lang,
 };
-use rcfe::mvccpb::KeyValue;
-use rcfe::{Client, DefaultClient, GetOptions, KVClient, SortOrderOption, SortTargetOption};
+use rcfe::{
+    Client, DefaultClient, GetOptions, KVClient, KeyRange, KeyValue, SortOrderOption,
+    SortTargetOption,
+};
 use serde::{Deserialize, Serialize};
 use serde_with::{NoneAsEmptyString, serde_as};
 use tracing::error;
@@ -71,21 +73,19 @@ pub struct RangeForm {
     prefix: Option<bool>,
 }
 
+impl RangeForm {
+    fn key_range(&self, key: &str) -> KeyRange {
+        match (self.prefix, self.range_end.as_deref()) {
+            (Some(true), _) => KeyRange::prefix(key),
+            (_, Some(range_end)) if !range_end.is_empty() => KeyRange::range(key, range_end),
+            _ => KeyRange::key(key),
+        }
+    }
+}
+
 impl Into<GetOptions> for RangeForm {
     fn into(self) -> GetOptions {
         let mut builder = rcfe::GetOptionsBuilder::default();
-        if let Some(ref range_end) = self.range_end
-            && !self.query_all.unwrap_or(false)
-        {
-            builder = builder.end_key(range_end.as_str());
-        }
-
-        if let Some(prefixed) = self.prefix
-            && !self.query_all.unwrap_or(false)
-        {
-            builder = builder.prefix(prefixed);
-        }
-
         if let Some(limit) = self.limit
             && limit > 0
         {
@@ -376,16 +376,16 @@ where
         && query_all
     {
         let response = client.get_all(Some(form.clone().into())).await?;
-        let response = response.into_inner();
-        let kvs = response.kvs;
-        (Some(kvs_to_results(kvs)), response.count)
+        let total = response.count();
+        (Some(kvs_to_results(response.into_kvs())), total)
     } else {
         if let Some(key) = form.key.clone() {
             let options: GetOptions = form.clone().into();
-            let response = client.get_with_options(key.as_str(), options).await?;
-            let response = response.into_inner();
-            let kvs = response.kvs;
-            (Some(kvs_to_results(kvs)), response.count)
+            let response = client
+                .get_with_options(form.key_range(key.as_str()), options)
+                .await?;
+            let total = response.count();
+            (Some(kvs_to_results(response.into_kvs())), total)
         } else {
             (None, 0)
         }
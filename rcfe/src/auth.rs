@@ -0,0 +1,103 @@
+use crate::{
+    Credentials, Error, GrpcAuthClient, context::GrpcChannel, etcdserverpb::AuthenticateRequest,
+};
+use std::sync::{Arc, RwLock};
+use tonic::{
+    Code, Request, Status, metadata::AsciiMetadataValue, service::Interceptor,
+};
+
+/// Metadata key etcd reads the auth token from.
+const TOKEN_METADATA_KEY: &str = "token";
+
+type SharedToken = Arc<RwLock<Option<AsciiMetadataValue>>>;
+
+/// Authenticates with the cluster and caches the token shared by all service clients.
+#[derive(Clone, Debug)]
+pub(crate) struct Authenticator {
+    credentials: Credentials,
+    client: GrpcAuthClient<GrpcChannel>,
+    token: SharedToken,
+    // Serializes (re-)authentication so concurrent failures trigger a single Authenticate call.
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Authenticator {
+    /// Creates an authenticator sending its requests over `channel`, which must not
+    /// carry the token of this authenticator.
+    pub(crate) fn new(credentials: Credentials, channel: GrpcChannel) -> Self {
+        Authenticator {
+            credentials,
+            client: GrpcAuthClient::new(channel),
+            token: Arc::new(RwLock::new(None)),
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Returns the current token, if authenticated.
+    pub(crate) fn token(&self) -> Option<AsciiMetadataValue> {
+        self.token.read().unwrap().clone()
+    }
+
+    /// Authenticates with the cluster and stores the returned token.
+    pub(crate) async fn authenticate(&self) -> Result<(), Error> {
+        let _guard = self.lock.lock().await;
+        self.request_token().await
+    }
+
+    /// Re-authenticates unless another caller already replaced the stale token.
+    pub(crate) async fn refresh(&self, stale: Option<AsciiMetadataValue>) -> Result<(), Error> {
+        let _guard = self.lock.lock().await;
+        if self.token() != stale {
+            return Ok(());
+        }
+        self.request_token().await
+    }
+
+    /// Creates an interceptor injecting the token into every request.
+    pub(crate) fn interceptor(&self) -> AuthInterceptor {
+        AuthInterceptor {
+            token: Some(self.token.clone()),
+        }
+    }
+
+    async fn request_token(&self) -> Result<(), Error> {
+        let request = AuthenticateRequest {
+            name: self.credentials.user().to_string(),
+            password: self.credentials.password().to_string(),
+        };
+
+        let response = self.client.clone().authenticate(request).await?;
+        let token = AsciiMetadataValue::try_from(response.into_inner().token)
+            .map_err(|e| Error::AuthError(format!("invalid token returned by server: {}", e)))?;
+
+        *self.token.write().unwrap() = Some(token);
+        Ok(())
+    }
+}
+
+/// Injects the auth token, if any, as gRPC metadata into every request.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AuthInterceptor {
+    token: Option<SharedToken>,
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = self.token.as_ref().and_then(|t| t.read().unwrap().clone()) {
+            request.metadata_mut().insert(TOKEN_METADATA_KEY, token);
+        }
+        Ok(request)
+    }
+}
+
+/// Returns true if the server rejected the request because the token is missing, invalid or expired.
+pub(crate) fn is_invalid_token(status: &Status) -> bool {
+    match status.code() {
+        Code::Unauthenticated => status.message().contains("invalid auth token"),
+        Code::InvalidArgument => {
+            status.message().contains("user name is empty")
+                || status.message().contains("revision of auth store is old")
+        }
+        _ => false,
+    }
+}
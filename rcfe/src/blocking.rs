@@ -0,0 +1,348 @@
+//! Blocking client for synchronous code.
+//!
+//! [`BlockingClient`] owns a Tokio runtime and drives the asynchronous [`DefaultClient`] on it,
+//! so it can be used from code that does not run within a runtime, e.g. CLI tools and build scripts.
+//! Calling any method from within a Tokio runtime panics.
+
+use crate::{
+    ByteSequence, Client, ClientOptions, CompactOptions, CompactionResponse, Compare,
+    DefaultClient, DefaultTxn, DeleteOptions, DeleteResult, Error, GetOptions, GetResult,
+    GrantOptions, HealthReport, KVClient, KeepAliveHandler, KeyRange, LeaseClient,
+    LeaseGrantResponse, LeaseKeepAliveResponse, LeaseRevokeResponse, LeaseTimeToLiveResponse,
+    PutOptions, PutResult, RequestOp, ShutdownOptions, TimeToLiveOptions, Txn, TxnResponse,
+    WatchRequestType, WatchResponse, Watcher, lease::DefaultKeepAliveHandler,
+    watch::DefaultWatcher,
+};
+use std::{sync::Arc, time::Duration};
+use tokio::runtime::{Builder, Runtime};
+use tonic::Response;
+
+/// Synchronous client mirroring the KV, lease and watch operations of [`DefaultClient`].
+/// Clones share the runtime and the connection.
+#[derive(Clone)]
+pub struct BlockingClient {
+    runtime: Arc<Runtime>,
+    client: DefaultClient,
+}
+
+impl BlockingClient {
+    /// Starts a runtime and connects a client to the cluster.
+    /// # Arguments
+    /// * `opts` - The options of the client.
+    pub fn connect(opts: ClientOptions) -> Result<Self, Error> {
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("rcfe-blocking")
+            .enable_all()
+            .build()?;
+        let client = runtime.block_on(DefaultClient::connect(opts))?;
+        Ok(BlockingClient {
+            runtime: Arc::new(runtime),
+            client,
+        })
+    }
+
+    /// Returns the options of the client.
+    pub fn options(&self) -> &ClientOptions {
+        self.client.get_options()
+    }
+
+    /// Derives a client scoped to a nested namespace, see [`Client::with_namespace`].
+    /// The derived client shares the runtime and the connection.
+    pub fn with_namespace(&self, namespace: impl Into<ByteSequence>) -> Self {
+        BlockingClient {
+            runtime: self.runtime.clone(),
+            client: self.client.with_namespace(namespace),
+        }
+    }
+
+    /// Retrieves the key-value pairs of a key or range.
+    pub fn get<K>(&self, key: K) -> Result<GetResult, Error>
+    where
+        K: Into<KeyRange> + Send,
+    {
+        self.runtime.block_on(self.client.get_kv_client().get(key))
+    }
+
+    /// Retrieves the key-value pairs matching the key and options.
+    pub fn get_with_options<K>(
+        &self,
+        key: K,
+        options: GetOptions,
+    ) -> Result<GetResult, Error>
+    where
+        K: Into<KeyRange> + Send,
+    {
+        self.runtime
+            .block_on(self.client.get_kv_client().get_with_options(key, options))
+    }
+
+    /// Retrieves all key-value pairs in the store.
+    pub fn get_all(&self, options: Option<GetOptions>) -> Result<GetResult, Error> {
+        self.runtime
+            .block_on(self.client.get_kv_client().get_all(options))
+    }
+
+    /// Puts a key-value pair into the store.
+    pub fn put<K, V>(&self, key: K, value: V) -> Result<PutResult, Error>
+    where
+        K: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        self.runtime
+            .block_on(self.client.get_kv_client().put(key, value))
+    }
+
+    /// Puts a key-value pair into the store with the given options.
+    pub fn put_with_options<K, V>(
+        &self,
+        key: K,
+        value: V,
+        options: PutOptions,
+    ) -> Result<PutResult, Error>
+    where
+        K: Into<ByteSequence> + Send,
+        V: Into<ByteSequence> + Send,
+    {
+        self.runtime.block_on(
+            self.client
+                .get_kv_client()
+                .put_with_options(key, value, options),
+        )
+    }
+
+    /// Deletes the keys of a key or range from the store.
+    pub fn delete(
+        &self,
+        key: impl Into<KeyRange>,
+    ) -> Result<DeleteResult, Error> {
+        self.runtime
+            .block_on(self.client.get_kv_client().delete(key.into()))
+    }
+
+    /// Deletes the keys matching the key and options.
+    pub fn delete_with_options(
+        &self,
+        key: impl Into<KeyRange>,
+        options: DeleteOptions,
+    ) -> Result<DeleteResult, Error> {
+        self.runtime.block_on(
+            self.client
+                .get_kv_client()
+                .delete_with_options(key.into(), options),
+        )
+    }
+
+    /// Compacts the key-value store up to the revision.
+    pub fn compact(&self, revision: i64) -> Result<Response<CompactionResponse>, Error> {
+        self.compact_with_options(revision, CompactOptions::default())
+    }
+
+    /// Compacts the key-value store up to the revision with the given options.
+    pub fn compact_with_options(
+        &self,
+        revision: i64,
+        options: CompactOptions,
+    ) -> Result<Response<CompactionResponse>, Error> {
+        self.runtime.block_on(
+            self.client
+                .get_kv_client()
+                .compact_with_options(revision, options),
+        )
+    }
+
+    /// Creates a new transaction.
+    pub fn txn(&self) -> BlockingTxn {
+        BlockingTxn {
+            runtime: self.runtime.clone(),
+            txn: self.client.kv_client.new_txn(),
+        }
+    }
+
+    /// Grants a lease with the TTL.
+    pub fn grant(&self, ttl: Duration) -> Result<Response<LeaseGrantResponse>, Error> {
+        self.runtime
+            .block_on(self.client.get_lease_client().grant(ttl))
+    }
+
+    /// Grants a lease with the TTL and options.
+    pub fn grant_with_options(
+        &self,
+        ttl: Duration,
+        options: GrantOptions,
+    ) -> Result<Response<LeaseGrantResponse>, Error> {
+        self.runtime.block_on(
+            self.client
+                .get_lease_client()
+                .grant_with_options(ttl, options),
+        )
+    }
+
+    /// Revokes a lease, deleting the keys attached to it.
+    pub fn revoke(&self, lease_id: i64) -> Result<Response<LeaseRevokeResponse>, Error> {
+        self.runtime
+            .block_on(self.client.get_lease_client().revoke(lease_id))
+    }
+
+    /// Retrieves the TTL information of a lease.
+    pub fn time_to_live(&self, lease_id: i64) -> Result<Response<LeaseTimeToLiveResponse>, Error> {
+        self.time_to_live_with_options(lease_id, TimeToLiveOptions::default())
+    }
+
+    /// Retrieves the TTL information of a lease with the given options.
+    pub fn time_to_live_with_options(
+        &self,
+        lease_id: i64,
+        options: TimeToLiveOptions,
+    ) -> Result<Response<LeaseTimeToLiveResponse>, Error> {
+        self.runtime.block_on(
+            self.client
+                .get_lease_client()
+                .time_to_live_with_options(lease_id, options),
+        )
+    }
+
+    /// Opens a keep-alive stream for a lease.
+    pub fn keep_alive(&self, lease_id: i64) -> Result<BlockingKeepAlive, Error> {
+        let handler = self
+            .runtime
+            .block_on(self.client.lease_client.keep_alive_handler(lease_id))?;
+        Ok(BlockingKeepAlive {
+            runtime: self.runtime.clone(),
+            handler,
+        })
+    }
+
+    /// Opens a watch stream for the request.
+    /// The responses of the stream are delivered by iterating the returned watcher.
+    pub fn watch(&self, request: WatchRequestType) -> Result<BlockingWatcher, Error> {
+        let watcher = self
+            .runtime
+            .block_on(self.client.watch_client.watcher(request))?;
+        Ok(BlockingWatcher {
+            runtime: self.runtime.clone(),
+            watcher,
+        })
+    }
+
+    /// Checks the health of every endpoint of the cluster.
+    pub fn health(&self) -> Result<HealthReport, Error> {
+        self.runtime.block_on(self.client.health())
+    }
+
+    /// Shuts the client down gracefully with the default options.
+    pub fn shutdown(&self) -> Result<(), Error> {
+        self.shutdown_with_options(ShutdownOptions::default())
+    }
+
+    /// Shuts the client down gracefully, see [`Client::shutdown_with_options`].
+    pub fn shutdown_with_options(&self, options: ShutdownOptions) -> Result<(), Error> {
+        self.runtime
+            .block_on(self.client.shutdown_with_options(options))
+    }
+}
+
+/// Blocking transaction created by [`BlockingClient::txn`].
+pub struct BlockingTxn {
+    runtime: Arc<Runtime>,
+    txn: DefaultTxn,
+}
+
+impl BlockingTxn {
+    /// Adds the comparisons evaluated by the transaction.
+    pub fn when<I, P>(&mut self, compares: I) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Compare>,
+    {
+        self.txn.when(compares)?;
+        Ok(self)
+    }
+
+    /// Adds the operations executed if all comparisons succeed.
+    pub fn then<I, P>(&mut self, ops: I) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<RequestOp>,
+    {
+        self.txn.then(ops)?;
+        Ok(self)
+    }
+
+    /// Adds the operations executed if any comparison fails.
+    pub fn otherwise<I, P>(&mut self, ops: I) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<RequestOp>,
+    {
+        self.txn.otherwise(ops)?;
+        Ok(self)
+    }
+
+    /// Commits the transaction.
+    pub fn commit(&mut self) -> Result<Response<TxnResponse>, Error> {
+        self.runtime.block_on(self.txn.commit())
+    }
+}
+
+/// Blocking keep-alive stream created by [`BlockingClient::keep_alive`].
+pub struct BlockingKeepAlive {
+    runtime: Arc<Runtime>,
+    handler: DefaultKeepAliveHandler,
+}
+
+impl BlockingKeepAlive {
+    /// Returns the ID of the lease.
+    pub fn lease_id(&self) -> i64 {
+        self.handler.lease_id()
+    }
+
+    /// Refreshes the lease and waits for the response of the server.
+    pub fn keep_alive(&mut self) -> Result<LeaseKeepAliveResponse, Error> {
+        self.runtime.block_on(async {
+            self.handler.keep_alive().await?;
+            self.handler
+                .message()
+                .await?
+                .ok_or_else(|| Error::KeepAliveError("keep-alive stream closed".to_string()))
+        })
+    }
+}
+
+/// Blocking watch stream created by [`BlockingClient::watch`].
+/// Iterating the watcher blocks until the next response of the server,
+/// the iteration ends once the stream is closed.
+pub struct BlockingWatcher {
+    runtime: Arc<Runtime>,
+    watcher: DefaultWatcher,
+}
+
+impl BlockingWatcher {
+    /// Returns the ID of the watch.
+    pub fn id(&self) -> i64 {
+        self.watcher.id()
+    }
+
+    /// Returns the request the watch was created with.
+    pub fn request(&self) -> &WatchRequestType {
+        self.watcher.request()
+    }
+
+    /// Requests a progress notification of the watch.
+    pub fn progress(&mut self) -> Result<(), Error> {
+        self.runtime.block_on(self.watcher.progress())
+    }
+
+    /// Cancels the watch. The server answers with a canceled response, after which the stream ends.
+    pub fn cancel(&mut self) -> Result<(), Error> {
+        self.runtime.block_on(self.watcher.cancel())
+    }
+}
+
+impl Iterator for BlockingWatcher {
+    type Item = Result<WatchResponse, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.watcher.message()).transpose()
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{ClientOptions, Error, TlsOptions};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
+
+const HTTPS_SCHEME: &str = "https";
+
+/// Authority sent to servers listening on a unix domain socket.
+const UNIX_AUTHORITY: &str = "http://localhost";
+
+/// Returns the socket path of a `unix://` endpoint.
+pub(crate) fn unix_socket_path(uri: &str) -> Option<&str> {
+    uri.strip_prefix("unix://")
+        .or_else(|| uri.strip_prefix("unix:"))
+}
+
+/// Builds a single endpoint, applying the connection settings and enabling TLS for `https://` URIs.
+pub(crate) fn build_endpoint(uri: &str, opts: &ClientOptions) -> Result<Endpoint, Error> {
+    let mut endpoint = match unix_socket_path(uri) {
+        Some(_) => Endpoint::from_static(UNIX_AUTHORITY),
+        None => Channel::from_shared(uri.to_string())?,
+    };
+
+    if let Some(timeout) = opts.connect_timeout() {
+        endpoint = endpoint.connect_timeout(timeout);
+    }
+
+    if let Some(interval) = opts.keep_alive_interval() {
+        endpoint = endpoint
+            .http2_keep_alive_interval(interval)
+            .keep_alive_while_idle(true);
+    }
+
+    if let Some(timeout) = opts.keep_alive_timeout() {
+        endpoint = endpoint.keep_alive_timeout(timeout);
+    }
+
+    if endpoint.uri().scheme_str() != Some(HTTPS_SCHEME) {
+        return Ok(endpoint);
+    }
+
+    let tls = match opts.tls() {
+        Some(tls) => tls_config(tls)?,
+        None => ClientTlsConfig::new().with_native_roots(),
+    };
+
+    Ok(endpoint.tls_config(tls)?)
+}
+
+/// Creates a lazily connecting channel to the endpoint, dialing `unix://` URIs
+/// through [`UnixConnector`] instead of TCP.
+pub(crate) fn connect_lazy(uri: &str, endpoint: &Endpoint) -> Result<Channel, Error> {
+    match unix_socket_path(uri) {
+        Some(path) => connect_unix_lazy(endpoint, path),
+        None => Ok(endpoint.connect_lazy()),
+    }
+}
+
+#[cfg(unix)]
+fn connect_unix_lazy(endpoint: &Endpoint, path: &str) -> Result<Channel, Error> {
+    Ok(endpoint.connect_with_connector_lazy(unix::UnixConnector::new(path)))
+}
+
+#[cfg(not(unix))]
+fn connect_unix_lazy(_endpoint: &Endpoint, _path: &str) -> Result<Channel, Error> {
+    Err(Error::IllegalArgument(String::from(
+        "unix socket endpoints are only supported on unix platforms",
+    )))
+}
+
+/// Converts the TLS options into a tonic TLS configuration.
+fn tls_config(tls: &TlsOptions) -> Result<ClientTlsConfig, Error> {
+    let mut config = ClientTlsConfig::new();
+
+    config = match tls.ca_cert() {
+        Some(ca_cert) => config.ca_certificate(Certificate::from_pem(ca_cert.load()?)),
+        None => config.with_native_roots(),
+    };
+
+    if let (Some(cert), Some(key)) = (tls.client_cert(), tls.client_key()) {
+        config = config.identity(Identity::from_pem(cert.load()?, key.load()?));
+    }
+
+    if let Some(domain_name) = tls.domain_name() {
+        config = config.domain_name(domain_name);
+    }
+
+    Ok(config)
+}
+
+#[cfg(unix)]
+mod unix {
+    use hyper_util::rt::TokioIo;
+    use std::{
+        future::Future,
+        io,
+        path::PathBuf,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use tokio::net::UnixStream;
+    use tonic::codegen::{Service, http::Uri};
+
+    /// Connects to a unix domain socket, ignoring the URI of the endpoint.
+    #[derive(Clone, Debug)]
+    pub(super) struct UnixConnector {
+        path: PathBuf,
+    }
+
+    impl UnixConnector {
+        pub(super) fn new<P: Into<PathBuf>>(path: P) -> Self {
+            UnixConnector { path: path.into() }
+        }
+    }
+
+    impl Service<Uri> for UnixConnector {
+        type Response = TokioIo<UnixStream>;
+        type Error = io::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _uri: Uri) -> Self::Future {
+            let path = self.path.clone();
+            Box::pin(async move { Ok(TokioIo::new(UnixStream::connect(path).await?)) })
+        }
+    }
+}
@@ -1,51 +1,104 @@
 use crate::{
-    Client, ClientOptions, Error, KVClient, KVOptions, LeaseClient, LeaseClientOptions,
-    WatchClient, WatchClientOptions, kv::DefaultKVClient, lease::DefaultLeaseClient,
+    ByteSequence, Client, ClientOptions, EndpointStatus, Error,
+    GrpcClusterClient, HealthReport, KVClient, KVOptions, LeaseClient, LeaseClientOptions, NamespaceBuilder, Namespaceable, ShutdownOptions, WatchClient, WatchClientOptions,
+    auth::Authenticator,
+    context::{ClientContext, GrpcChannel},
+    endpoint::EndpointManager,
+    kv::DefaultKVClient,
+    lease::DefaultLeaseClient,
     watch::DefaultWatchClient,
 };
-use tonic::transport::Channel;
+use std::time::Instant;
 
 #[derive(Clone)]
 pub struct DefaultClient {
     options: ClientOptions,
-    kv_client: DefaultKVClient,
-    lease_client: DefaultLeaseClient,
-    watch_client: DefaultWatchClient,
+    context: ClientContext,
+    endpoints: EndpointManager,
+    cluster_client: GrpcClusterClient<GrpcChannel>,
+    pub(crate) kv_client: DefaultKVClient,
+    pub(crate) lease_client: DefaultLeaseClient,
+    pub(crate) watch_client: DefaultWatchClient,
 }
 
 impl DefaultClient {
+    /// Creates a client without contacting the cluster.
+    /// If credentials are configured, the client authenticates lazily on the first request.
+    /// If member discovery is enabled, members are first discovered after one auto sync interval.
+    /// Must be called within a Tokio runtime, which runs the endpoint health checks.
     pub fn new(opts: ClientOptions) -> Result<Self, Error> {
-        let endpoints = opts
-            .endpoints()
-            .clone()
-            .into_iter()
-            .map(Channel::from_shared)
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter();
+        let (channel, endpoints) = EndpointManager::new(&opts)?;
+        if let Some(interval) = opts.health_check_interval() {
+            endpoints.spawn_health_check(interval, opts.health_probe_timeout());
+        }
 
-        let channel = Channel::balance_list(endpoints);
+        // Authenticate requests pass through the middleware, without a token of their own.
+        let unauthenticated = ClientContext::new(&opts, None);
+        let authenticator = opts.credentials().map(|credentials| {
+            Authenticator::new(credentials.clone(), unauthenticated.channel(channel.clone()))
+        });
+        let context = ClientContext::new(&opts, authenticator);
+
+        let cluster_client = GrpcClusterClient::new(context.channel(channel.clone()));
+        if let Some(interval) = opts.auto_sync_interval() {
+            endpoints.spawn_member_sync(interval, cluster_client.clone(), context.clone());
+        }
 
         Ok(DefaultClient {
-            options: opts,
-            kv_client: DefaultKVClient::new(KVOptions::builder().channel(channel.clone()).build()?),
+            kv_client: DefaultKVClient::new(
+                KVOptions::builder()
+                    .channel(channel.clone())
+                    .namespace(opts.namespace())
+                    .build()?,
+                context.clone(),
+            ),
             lease_client: DefaultLeaseClient::new(
                 LeaseClientOptions::builder()
                     .channel(channel.clone())
+                    .namespace(opts.namespace())
                     .build()?,
+                context.clone(),
             ),
             watch_client: DefaultWatchClient::new(
-                WatchClientOptions::builder().channel(channel).build()?,
+                WatchClientOptions::builder()
+                    .channel(channel)
+                    .namespace(opts.namespace())
+                    .build()?,
+                context.clone(),
             ),
+            options: opts,
+            context,
+            endpoints,
+            cluster_client,
         })
     }
+
+    /// Creates a client and authenticates with the cluster if credentials are configured.
+    /// If member discovery is enabled, the endpoints are replaced with the discovered members.
+    pub async fn connect(opts: ClientOptions) -> Result<Self, Error> {
+        let client = Self::new(opts)?;
+        client.context.authenticate().await?;
+        if client.options.auto_sync_interval().is_some() {
+            client.sync_members().await?;
+        }
+        Ok(client)
+    }
+
+    /// Discovers the members of the cluster and replaces the endpoints with their client URLs.
+    pub async fn sync_members(&self) -> Result<(), Error> {
+        self.endpoints
+            .sync_members(&self.cluster_client, &self.context)
+            .await
+    }
 }
 
+#[tonic::async_trait]
 impl Client for DefaultClient {
     fn get_options(&self) -> &ClientOptions {
         &self.options
     }
 
-    fn get_kv_client(&self) -> impl KVClient {
+    fn get_kv_client(&self) -> impl KVClient + Clone {
         self.kv_client.clone()
     }
 
@@ -56,4 +109,53 @@ impl Client for DefaultClient {
     fn get_watch_client(&self) -> impl WatchClient {
         self.watch_client.clone()
     }
+
+    fn with_namespace<N>(&self, namespace: N) -> Self
+    where
+        N: Into<ByteSequence>,
+    {
+        let namespace = namespace.into();
+        let namespace = match self.options.namespace() {
+            Some(mut current) => current.append(&namespace),
+            None => namespace,
+        };
+        DefaultClient {
+            options: self.options.with_namespace(Some(namespace.clone())),
+            kv_client: self.kv_client.with_namespace(Some(namespace.clone())),
+            lease_client: self.lease_client.with_namespace(Some(namespace.clone())),
+            watch_client: self.watch_client.with_namespace(Some(namespace)),
+            ..self.clone()
+        }
+    }
+
+    fn endpoint_statuses(&self) -> Vec<EndpointStatus> {
+        self.endpoints.statuses()
+    }
+
+    async fn health(&self) -> Result<HealthReport, Error> {
+        self.context.lifecycle().check_open()?;
+        Ok(self.endpoints.check(self.options.health_probe_timeout()).await)
+    }
+
+    async fn shutdown_with_options(&self, options: ShutdownOptions) -> Result<(), Error> {
+        let lifecycle = self.context.lifecycle();
+        if !lifecycle.close() {
+            return Ok(());
+        }
+        let deadline = Instant::now() + options.timeout;
+
+        self.endpoints.close();
+        lifecycle.close_streams();
+        let idle = lifecycle.wait_idle(options.timeout).await;
+
+        let revoked = match options.revoke_leases {
+            true => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                self.lease_client.revoke_granted(remaining).await
+            }
+            false => Ok(()),
+        };
+
+        idle.and(revoked)
+    }
 }
@@ -0,0 +1,308 @@
+use crate::{
+    ClientMiddleware, ClientOptions, CompressionEncoding, Error, GrpcKVClient, GrpcLeaseClient,
+    GrpcService, MiddlewareError, RetryPolicy,
+    auth::{AuthInterceptor, Authenticator, is_invalid_token},
+    lifecycle::Lifecycle,
+};
+use prost::Message;
+use std::{sync::Arc, time::Duration};
+use tonic::{
+    Request, Status,
+    body::Body,
+    client::Grpc,
+    metadata::AsciiMetadataValue,
+    service::{Interceptor, interceptor::InterceptedService},
+    transport::Channel,
+};
+use tower::{ServiceExt, util::BoxCloneSyncService};
+
+/// Metadata key etcd reads the leader requirement from.
+const REQUIRE_LEADER_METADATA_KEY: &str = "hasleader";
+
+/// Channel type used by the generated gRPC clients, with the client metadata injected
+/// and the middleware of the client options applied.
+pub(crate) type GrpcChannel = GrpcService;
+
+/// Injects the auth token and, if required, the leader requirement into every request.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ClientInterceptor {
+    auth: AuthInterceptor,
+    require_leader: bool,
+}
+
+impl Interceptor for ClientInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let mut request = self.auth.call(request)?;
+        // A leader requirement set on the request itself takes precedence.
+        if self.require_leader && !request.metadata().contains_key(REQUIRE_LEADER_METADATA_KEY) {
+            request
+                .metadata_mut()
+                .insert(REQUIRE_LEADER_METADATA_KEY, require_leader_value(true));
+        }
+        Ok(request)
+    }
+}
+
+/// Sets whether the request requires the member to have a leader,
+/// overriding the client options.
+pub(crate) fn set_require_leader<T>(request: &mut Request<T>, require_leader: bool) {
+    request
+        .metadata_mut()
+        .insert(REQUIRE_LEADER_METADATA_KEY, require_leader_value(require_leader));
+}
+
+/// etcd only requires a leader if the metadata value is `true`.
+fn require_leader_value(require_leader: bool) -> AsciiMetadataValue {
+    match require_leader {
+        true => AsciiMetadataValue::from_static("true"),
+        false => AsciiMetadataValue::from_static("false"),
+    }
+}
+
+/// Whether a request may be sent again after a retryable failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Idempotency {
+    /// Sending the request twice has the same effect as sending it once.
+    Idempotent,
+    /// The request is attempted once.
+    NonIdempotent,
+}
+
+/// Message settings of the generated gRPC clients.
+pub(crate) trait GrpcClientConfig: Sized {
+    fn max_decoding_message_size(self, limit: usize) -> Self;
+    fn max_encoding_message_size(self, limit: usize) -> Self;
+    fn send_compressed(self, encoding: CompressionEncoding) -> Self;
+    fn accept_compressed(self, encoding: CompressionEncoding) -> Self;
+}
+
+macro_rules! impl_grpc_client_config {
+    ($($client:ident),*) => {
+        $(
+            impl GrpcClientConfig for $client<GrpcChannel> {
+                fn max_decoding_message_size(self, limit: usize) -> Self {
+                    $client::max_decoding_message_size(self, limit)
+                }
+
+                fn max_encoding_message_size(self, limit: usize) -> Self {
+                    $client::max_encoding_message_size(self, limit)
+                }
+
+                fn send_compressed(self, encoding: CompressionEncoding) -> Self {
+                    $client::send_compressed(self, encoding)
+                }
+
+                fn accept_compressed(self, encoding: CompressionEncoding) -> Self {
+                    $client::accept_compressed(self, encoding)
+                }
+            }
+        )*
+    };
+}
+
+impl_grpc_client_config!(GrpcKVClient, GrpcLeaseClient, Grpc);
+
+/// State shared by all service clients created from one client.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ClientContext {
+    authenticator: Option<Authenticator>,
+    request_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    max_decoding_message_size: Option<usize>,
+    max_encoding_message_size: Option<usize>,
+    compression: Option<CompressionEncoding>,
+    require_leader: bool,
+    middleware: ClientMiddleware,
+    lifecycle: Arc<Lifecycle>,
+}
+
+impl ClientContext {
+    pub(crate) fn new(opts: &ClientOptions, authenticator: Option<Authenticator>) -> Self {
+        ClientContext {
+            authenticator,
+            request_timeout: opts.request_timeout(),
+            retry_policy: opts.retry_policy().cloned(),
+            max_decoding_message_size: opts.max_decoding_message_size(),
+            max_encoding_message_size: opts.max_encoding_message_size(),
+            compression: opts.compression(),
+            require_leader: opts.require_leader(),
+            middleware: opts.middleware().clone(),
+            lifecycle: Arc::default(),
+        }
+    }
+
+    /// Returns the lifecycle shared by all service clients of this context.
+    pub(crate) fn lifecycle(&self) -> &Arc<Lifecycle> {
+        &self.lifecycle
+    }
+
+    /// Authenticates with the cluster if credentials are configured,
+    /// bounded by the default request timeout.
+    pub(crate) async fn authenticate(&self) -> Result<(), Error> {
+        let Some(authenticator) = &self.authenticator else {
+            return Ok(());
+        };
+
+        match self.request_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, authenticator.authenticate())
+                .await
+                .map_err(|_| Error::Timeout(timeout))?,
+            None => authenticator.authenticate().await,
+        }
+    }
+
+    /// Wraps the channel with the interceptor of this context and then with the middleware,
+    /// so that metadata set by the middleware is seen by the interceptor.
+    pub(crate) fn channel(&self, channel: Channel) -> GrpcChannel {
+        let auth = self
+            .authenticator
+            .as_ref()
+            .map(Authenticator::interceptor)
+            .unwrap_or_default();
+        let interceptor = ClientInterceptor {
+            auth,
+            require_leader: self.require_leader,
+        };
+        let service = InterceptedService::new(channel, interceptor)
+            .map_response(|response| response.map(Body::new))
+            .map_err(MiddlewareError::new);
+        self.middleware.apply(BoxCloneSyncService::new(service))
+    }
+
+    /// Applies the message size limits and compression of this context to a gRPC client.
+    pub(crate) fn configure<C: GrpcClientConfig>(&self, mut client: C) -> C {
+        if let Some(limit) = self.max_decoding_message_size {
+            client = client.max_decoding_message_size(limit);
+        }
+
+        if let Some(limit) = self.max_encoding_message_size {
+            client = client.max_encoding_message_size(limit);
+        }
+
+        if let Some(encoding) = self.compression {
+            client = client.send_compressed(encoding).accept_compressed(encoding);
+        }
+
+        client
+    }
+
+    /// Rejects a request larger than the encoding limit before it is sent. Past the limit,
+    /// tonic fails the call while the request is already being streamed, and the server may
+    /// answer by closing the connection shared with other calls.
+    pub(crate) fn check_encoded_len<M: Message>(&self, message: &M) -> Result<(), Error> {
+        let (Some(limit), len) = (self.max_encoding_message_size, message.encoded_len()) else {
+            return Ok(());
+        };
+        match len > limit {
+            true => Err(Error::TonicStatus(Status::out_of_range(format!(
+                "Error, encoded message length too large: found {len} bytes, the limit is: {limit} bytes"
+            )))),
+            false => Ok(()),
+        }
+    }
+
+    /// Runs a gRPC call bounded by the default request timeout.
+    pub(crate) async fn call<T, E, F, Fut>(&self, idempotency: Idempotency, f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<Error>,
+    {
+        self.call_with_timeout(None, idempotency, f).await
+    }
+
+    /// Runs a gRPC call bounded by `timeout`, falling back to the default request timeout.
+    /// Authentication and all retries count against the same deadline.
+    /// # Errors
+    /// Returns an `Error::ClientClosed` without sending the request if the client was shut down.
+    pub(crate) async fn call_with_timeout<T, E, F, Fut>(
+        &self,
+        timeout: Option<Duration>,
+        idempotency: Idempotency,
+        f: F,
+    ) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<Error>,
+    {
+        let _guard = self.lifecycle.begin_call()?;
+        self.call_unguarded(timeout, idempotency, f).await
+    }
+
+    /// Runs a gRPC call like [`call_with_timeout`](Self::call_with_timeout), even if the
+    /// client was shut down. Only used to clean up while shutting down.
+    pub(crate) async fn call_unguarded<T, E, F, Fut>(
+        &self,
+        timeout: Option<Duration>,
+        idempotency: Idempotency,
+        f: F,
+    ) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<Error>,
+    {
+        match timeout.or(self.request_timeout) {
+            Some(timeout) => tokio::time::timeout(timeout, self.retrying(idempotency, f))
+                .await
+                .map_err(|_| Error::Timeout(timeout))?,
+            None => self.retrying(idempotency, f).await,
+        }
+    }
+
+    /// Runs a gRPC call, retrying idempotent calls with backoff as long as the
+    /// retry policy allows it. A member without a leader fails with `Unavailable`,
+    /// so `Error::NoLeader` is retried like that code.
+    async fn retrying<T, E, F, Fut>(&self, idempotency: Idempotency, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<Error>,
+    {
+        let policy = match (&self.retry_policy, idempotency) {
+            (Some(policy), Idempotency::Idempotent) => policy,
+            _ => return self.authorized(&mut f).await,
+        };
+
+        let mut attempt = 1;
+        loop {
+            match self.authorized(&mut f).await {
+                Err(Error::TonicStatus(status) | Error::NoLeader(status))
+                    if attempt < policy.max_attempts() && policy.is_retryable(&status) =>
+                {
+                    tokio::time::sleep(policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Runs a gRPC call, authenticating first if no token is cached yet and
+    /// retrying once with a fresh token if the server rejects the current one.
+    async fn authorized<T, E, F, Fut>(&self, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<Error>,
+    {
+        let Some(authenticator) = &self.authenticator else {
+            return f().await.map_err(Into::into);
+        };
+
+        let mut token = authenticator.token();
+        if token.is_none() {
+            authenticator.authenticate().await?;
+            token = authenticator.token();
+        }
+
+        match f().await.map_err(Into::into) {
+            Err(Error::TonicStatus(status)) if is_invalid_token(&status) => {
+                authenticator.refresh(token).await?;
+                f().await.map_err(Into::into)
+            }
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use crate::{
+    ClientOptions, EndpointHealth, EndpointStatus, Error, GrpcClusterClient, GrpcMaintenanceClient,
+    HealthReport,
+    channel::{build_endpoint, connect_lazy, unix_socket_path},
+    context::{ClientContext, GrpcChannel, Idempotency, set_require_leader},
+    etcdserverpb::{MemberListRequest, StatusRequest},
+};
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::mpsc::Sender,
+    task::JoinSet,
+    time::MissedTickBehavior,
+};
+use tonic::{
+    Request,
+    transport::{Channel, Endpoint, channel::Change},
+};
+
+/// Minimum capacity of the queue of endpoint changes consumed by the balancer.
+const CHANGE_CAPACITY: usize = 64;
+
+/// Tracks the health of the endpoints and keeps the endpoints the balanced
+/// channel routes requests to in sync with it.
+#[derive(Clone, Debug)]
+pub(crate) struct EndpointManager {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    options: ClientOptions,
+    /// Applies the middleware of the client options to the probes.
+    context: ClientContext,
+    /// Changes the endpoints of the balanced channel, `None` for a unix socket
+    /// endpoint which is connected to directly.
+    sender: Option<Sender<Change<String, Endpoint>>>,
+    members: Mutex<Vec<Member>>,
+    /// Stops the background health checks and member discovery.
+    closed: AtomicBool,
+}
+
+/// An endpoint with a dedicated connection used for health checks,
+/// so that probes are not routed to another endpoint by the balancer.
+#[derive(Debug)]
+struct Member {
+    endpoint: Endpoint,
+    probe: Channel,
+    status: EndpointStatus,
+}
+
+impl Member {
+    fn new(uri: &str, opts: &ClientOptions) -> Result<Self, Error> {
+        let endpoint = build_endpoint(uri, opts)?;
+        Ok(Member {
+            probe: connect_lazy(uri, &endpoint)?,
+            status: EndpointStatus::new(uri),
+            endpoint,
+        })
+    }
+
+    fn insert(&self) -> Change<String, Endpoint> {
+        Change::Insert(self.status.endpoint.clone(), self.endpoint.clone())
+    }
+}
+
+impl EndpointManager {
+    /// Creates the balanced channel over all endpoints of the client options.
+    /// A unix socket endpoint is connected to directly, it cannot be balanced with others.
+    pub(crate) fn new(opts: &ClientOptions) -> Result<(Channel, EndpointManager), Error> {
+        if opts.endpoints().iter().any(|uri| unix_socket_path(uri).is_some()) {
+            return Self::unix(opts);
+        }
+
+        let (channel, sender) =
+            Channel::balance_channel(CHANGE_CAPACITY.max(opts.endpoints().len()));
+
+        let members = opts
+            .endpoints()
+            .iter()
+            .map(|uri| Member::new(uri, opts))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for member in &members {
+            sender
+                .try_send(member.insert())
+                .map_err(|e| Error::Other(e.to_string()))?;
+        }
+
+        let manager = EndpointManager {
+            inner: Arc::new(Inner {
+                options: opts.clone(),
+                context: ClientContext::new(opts, None),
+                sender: Some(sender),
+                members: Mutex::new(members),
+                closed: AtomicBool::new(false),
+            }),
+        };
+
+        Ok((channel, manager))
+    }
+
+    /// Creates a channel over a single unix socket endpoint.
+    fn unix(opts: &ClientOptions) -> Result<(Channel, EndpointManager), Error> {
+        let [uri] = opts.endpoints().as_slice() else {
+            return Err(Error::IllegalArgument(String::from(
+                "a unix socket endpoint cannot be combined with other endpoints",
+            )));
+        };
+
+        if opts.auto_sync_interval().is_some() {
+            return Err(Error::IllegalArgument(String::from(
+                "member discovery is not supported with a unix socket endpoint",
+            )));
+        }
+
+        let member = Member::new(uri, opts)?;
+        let channel = member.probe.clone();
+
+        let manager = EndpointManager {
+            inner: Arc::new(Inner {
+                options: opts.clone(),
+                context: ClientContext::new(opts, None),
+                sender: None,
+                members: Mutex::new(vec![member]),
+                closed: AtomicBool::new(false),
+            }),
+        };
+
+        Ok((channel, manager))
+    }
+
+    /// Stops the background tasks of this manager after their current round.
+    pub(crate) fn close(&self) {
+        self.inner.closed.store(true, Ordering::Release);
+    }
+
+    /// Returns the current status of every endpoint.
+    pub(crate) fn statuses(&self) -> Vec<EndpointStatus> {
+        let members = self.inner.members.lock().unwrap();
+        members.iter().map(|member| member.status.clone()).collect()
+    }
+
+    /// Replaces the endpoints with the given ones, keeping the status of known endpoints.
+    /// # Errors
+    /// Returns an error if an endpoint is invalid, in which case the endpoints are unchanged.
+    pub(crate) fn set_endpoints(&self, uris: &[String]) -> Result<(), Error> {
+        let Some(sender) = &self.inner.sender else {
+            return Err(Error::IllegalArgument(String::from(
+                "the endpoint of a unix socket cannot be changed",
+            )));
+        };
+
+        if uris.iter().any(|uri| unix_socket_path(uri).is_some()) {
+            return Err(Error::IllegalArgument(String::from(
+                "a unix socket endpoint cannot be combined with other endpoints",
+            )));
+        }
+
+        let mut members = self.inner.members.lock().unwrap();
+
+        let added = uris
+            .iter()
+            .filter(|uri| !members.iter().any(|member| &member.status.endpoint == *uri))
+            .map(|uri| Member::new(uri, &self.inner.options))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let removed: Vec<String> = members
+            .iter()
+            .filter(|member| member.status.in_rotation && !uris.contains(&member.status.endpoint))
+            .map(|member| member.status.endpoint.clone())
+            .collect();
+
+        // Only this manager sends changes and it holds the lock, so the capacity cannot shrink.
+        if sender.capacity() < added.len() + removed.len() {
+            return Err(Error::Other(String::from("too many pending endpoint changes")));
+        }
+
+        for endpoint in removed {
+            let _ = sender.try_send(Change::Remove(endpoint));
+        }
+        members.retain(|member| uris.contains(&member.status.endpoint));
+
+        for member in added {
+            let _ = sender.try_send(member.insert());
+            members.push(member);
+        }
+
+        self.rebalance(&mut members);
+        Ok(())
+    }
+
+    /// Replaces the endpoints with the client URLs of the voting members of the cluster.
+    /// Learners are skipped as they cannot serve most requests. The endpoints are kept
+    /// if the cluster reports no member with client URLs.
+    pub(crate) async fn sync_members(
+        &self,
+        cluster: &GrpcClusterClient<GrpcChannel>,
+        context: &ClientContext,
+    ) -> Result<(), Error> {
+        let response = context
+            .call(Idempotency::Idempotent, || {
+                let mut cluster = cluster.clone();
+                async move { cluster.member_list(MemberListRequest {}).await }
+            })
+            .await?;
+
+        let mut endpoints: Vec<String> = Vec::new();
+        for member in response.into_inner().members {
+            if member.is_learner {
+                continue;
+            }
+            for url in member.client_ur_ls {
+                if !endpoints.contains(&url) {
+                    endpoints.push(url);
+                }
+            }
+        }
+
+        if endpoints.is_empty() {
+            return Ok(());
+        }
+        self.set_endpoints(&endpoints)
+    }
+
+    /// Discovers the members every `interval` until the client is dropped or shut down.
+    pub(crate) fn spawn_member_sync(
+        &self,
+        interval: Duration,
+        cluster: GrpcClusterClient<GrpcChannel>,
+        context: ClientContext,
+    ) {
+        let inner = Arc::downgrade(&self.inner);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately, connecting already synced the members.
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                let Some(inner) = inner.upgrade().filter(|i| !i.closed.load(Ordering::Acquire))
+                else {
+                    break;
+                };
+                // A failed sync keeps the current endpoints, the next round tries again.
+                let _ = EndpointManager { inner }
+                    .sync_members(&cluster, &context)
+                    .await;
+            }
+        });
+    }
+
+    /// Health checks the endpoints every `interval` until the client is dropped or shut down,
+    /// waiting at most `timeout` for each endpoint.
+    pub(crate) fn spawn_health_check(&self, interval: Duration, timeout: Duration) {
+        let inner = Arc::downgrade(&self.inner);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately, endpoints start out healthy.
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                let Some(inner) = inner.upgrade().filter(|i| !i.closed.load(Ordering::Acquire))
+                else {
+                    break;
+                };
+                EndpointManager { inner }.check(timeout).await;
+            }
+        });
+    }
+
+    /// Health checks all endpoints concurrently and updates the balanced channel.
+    /// Returns the health of every endpoint, in the order of the endpoints.
+    pub(crate) async fn check(&self, timeout: Duration) -> HealthReport {
+        let probes: Vec<(String, GrpcChannel)> = {
+            let members = self.inner.members.lock().unwrap();
+            members
+                .iter()
+                .map(|member| {
+                    let probe = self.inner.context.channel(member.probe.clone());
+                    (member.status.endpoint.clone(), probe)
+                })
+                .collect()
+        };
+
+        let mut checks = JoinSet::new();
+        for (index, (endpoint, probe)) in probes.into_iter().enumerate() {
+            checks.spawn(async move { (index, probe_status(endpoint, probe, timeout).await) });
+        }
+        let mut results = checks.join_all().await;
+        results.sort_by_key(|(index, _)| *index);
+        let endpoints: Vec<EndpointHealth> =
+            results.into_iter().map(|(_, health)| health).collect();
+
+        let now = Instant::now();
+        let mut members = self.inner.members.lock().unwrap();
+        for health in &endpoints {
+            let Some(member) = members.iter_mut().find(|m| m.status.endpoint == health.endpoint)
+            else {
+                continue;
+            };
+            member.status.last_checked = Some(now);
+            member.status.healthy = health.is_healthy();
+            member.status.last_error = health.error.clone();
+        }
+
+        self.rebalance(&mut members);
+        HealthReport { endpoints }
+    }
+
+    /// Routes requests to the healthy endpoints only. If no endpoint is healthy all of
+    /// them stay in rotation, so that requests fail with the actual error instead of
+    /// waiting for an endpoint to become available.
+    fn rebalance(&self, members: &mut [Member]) {
+        let Some(sender) = &self.inner.sender else {
+            return;
+        };
+        let any_healthy = members.iter().any(|member| member.status.healthy);
+
+        for member in members {
+            let in_rotation = member.status.healthy || !any_healthy;
+            if in_rotation == member.status.in_rotation {
+                continue;
+            }
+
+            let key = member.status.endpoint.clone();
+            let change = match in_rotation {
+                true => Change::Insert(key, member.endpoint.clone()),
+                false => Change::Remove(key),
+            };
+
+            // A full queue leaves the endpoint as it is until the next check.
+            if sender.try_send(change).is_ok() {
+                member.status.in_rotation = in_rotation;
+            }
+        }
+    }
+}
+
+/// Checks a single endpoint through the Maintenance `Status` RPC.
+/// A member without a leader cannot serve linearizable requests and counts as unhealthy.
+async fn probe_status(endpoint: String, probe: GrpcChannel, timeout: Duration) -> EndpointHealth {
+    let mut client = GrpcMaintenanceClient::new(probe);
+    let mut request = Request::new(StatusRequest {});
+    // The leader of the member is read from the response, even if the client requires one.
+    set_require_leader(&mut request, false);
+    let started = Instant::now();
+    let result = tokio::time::timeout(timeout, client.status(request)).await;
+    let latency = started.elapsed();
+
+    let response = match result {
+        Ok(Ok(response)) => response.into_inner(),
+        Ok(Err(status)) => return unreachable(endpoint, latency, status.to_string()),
+        Err(_) => {
+            let error = format!("health check timed out after {timeout:?}");
+            return unreachable(endpoint, latency, error);
+        }
+    };
+
+    EndpointHealth {
+        endpoint,
+        member_id: response.header.map_or(0, |header| header.member_id),
+        leader: response.leader,
+        raft_term: response.raft_term,
+        raft_index: response.raft_index,
+        raft_applied_index: response.raft_applied_index,
+        db_size: response.db_size,
+        db_size_in_use: response.db_size_in_use,
+        version: response.version,
+        is_learner: response.is_learner,
+        alarms: response.errors,
+        latency,
+        error: (response.leader == 0).then(|| String::from("member has no leader")),
+    }
+}
+
+/// The health of an endpoint whose `Status` request failed.
+fn unreachable(endpoint: String, latency: Duration, error: String) -> EndpointHealth {
+    EndpointHealth {
+        endpoint,
+        latency,
+        error: Some(error),
+        ..Default::default()
+    }
+}
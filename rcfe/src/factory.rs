@@ -13,6 +13,6 @@ impl DefaultClientFactory {
 #[async_trait]
 impl ClientFactory<DefaultClient> for DefaultClientFactory {
     async fn create(&self, opts: ClientOptions) -> Result<DefaultClient, Error> {
-        Ok(DefaultClient::new(opts)?)
+        DefaultClient::connect(opts).await
     }
 }
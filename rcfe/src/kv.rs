@@ -1,21 +1,44 @@
 use crate::{
-    ByteSequence, CompactOptions, CompactionResponse, DefaultTxn, DeleteOptions,
-    DeleteRangeResponse, Error, GetOptions, GrpcKVClient, KVClient, KVOptions, PutOptions,
-    PutResponse, RangeResponse, Txn,
+    ByteSequence, CompactOptions, CompactionResponse, DefaultTxn, DeleteOptions, DeleteResult,
+    Error, GetOptions, GetResult, GrpcKVClient, KVClient, KVOptions, KeyRange, Namespace,
+    Namespaceable, PutOptions, PutResult, Txn,
+    context::{ClientContext, GrpcChannel, Idempotency},
 };
-use tonic::{Response, transport::Channel};
+use tonic::Response;
 
 #[derive(Clone)]
 pub struct DefaultKVClient {
     options: KVOptions,
-    inner: GrpcKVClient<Channel>,
+    namespace: Namespace,
+    context: ClientContext,
+    inner: GrpcKVClient<GrpcChannel>,
 }
 
 impl DefaultKVClient {
-    pub fn new(opts: KVOptions) -> Self {
+    pub(crate) fn new(opts: KVOptions, context: ClientContext) -> Self {
         DefaultKVClient {
+            namespace: Namespace::from(opts.namespace()),
             options: opts.clone(),
-            inner: GrpcKVClient::new(opts.channel()),
+            inner: context.configure(GrpcKVClient::new(context.channel(opts.channel()))),
+            context,
+        }
+    }
+
+    /// Creates a new transaction sharing the channel of the client.
+    pub(crate) fn new_txn(&self) -> DefaultTxn {
+        DefaultTxn::new(
+            self.inner.clone(),
+            self.namespace.clone(),
+            self.context.clone(),
+        )
+    }
+
+    /// Derives a client using the namespace, sharing the channel of this client.
+    pub(crate) fn with_namespace(&self, namespace: Option<ByteSequence>) -> Self {
+        DefaultKVClient {
+            namespace: Namespace::from(namespace.clone()),
+            options: self.options.with_namespace(namespace),
+            ..self.clone()
         }
     }
 }
@@ -28,20 +51,39 @@ impl KVClient for DefaultKVClient {
         options: CompactOptions,
     ) -> Result<Response<CompactionResponse>, Error> {
         let request = options.to_request(revision);
-        Ok(self.inner.compact(request).await?)
+        self.context.check_encoded_len(&request)?;
+        self.context
+            .call(Idempotency::NonIdempotent, || {
+                let mut inner = self.inner.clone();
+                async move { inner.compact(request).await }
+            })
+            .await
     }
 
     fn txn(&mut self) -> impl Txn {
-        DefaultTxn::new(self.inner.clone())
+        self.new_txn()
     }
 
-    async fn delete_with_options(
+    async fn delete_with_options<K>(
         &mut self,
-        key: ByteSequence,
+        key: K,
         options: DeleteOptions,
-    ) -> Result<Response<DeleteRangeResponse>, Error> {
-        let request = options.to_request(&key);
-        Ok(self.inner.delete_range(request).await?)
+    ) -> Result<DeleteResult, Error>
+    where
+        K: Into<KeyRange> + Send,
+    {
+        let timeout = options.timeout;
+        let request = self.namespace.prefix(options.to_request(key));
+        self.context.check_encoded_len(&request)?;
+        let response = self
+            .context
+            .call_with_timeout(timeout, Idempotency::NonIdempotent, || {
+                let mut inner = self.inner.clone();
+                let request = request.clone();
+                async move { inner.delete_range(request).await }
+            })
+            .await?;
+        Ok(response.map(|response| self.namespace.strip(response)).into())
     }
 
     async fn put_with_options<K, V>(
@@ -49,25 +91,45 @@ impl KVClient for DefaultKVClient {
         key: K,
         value: V,
         options: PutOptions,
-    ) -> Result<Response<PutResponse>, Error>
+    ) -> Result<PutResult, Error>
     where
         K: Into<ByteSequence> + Send,
         V: Into<ByteSequence> + Send,
     {
-        let request = options.to_request(key, value);
-        Ok(self.inner.put(request).await?)
+        let timeout = options.timeout;
+        let request = self.namespace.prefix(options.to_request(key, value));
+        self.context.check_encoded_len(&request)?;
+        let response = self
+            .context
+            .call_with_timeout(timeout, Idempotency::NonIdempotent, || {
+                let mut inner = self.inner.clone();
+                let request = request.clone();
+                async move { inner.put(request).await }
+            })
+            .await?;
+        Ok(response.map(|response| self.namespace.strip(response)).into())
     }
 
     async fn get_with_options<K>(
         &mut self,
         key: K,
         options: GetOptions,
-    ) -> Result<Response<RangeResponse>, Error>
+    ) -> Result<GetResult, Error>
     where
-        K: Into<ByteSequence> + Send,
+        K: Into<KeyRange> + Send,
     {
-        let request = options.to_request(&key.into());
-        Ok(self.inner.range(request).await?)
+        let timeout = options.timeout;
+        let request = self.namespace.prefix(options.to_request(key));
+        self.context.check_encoded_len(&request)?;
+        let response = self
+            .context
+            .call_with_timeout(timeout, Idempotency::Idempotent, || {
+                let mut inner = self.inner.clone();
+                let request = request.clone();
+                async move { inner.range(request).await }
+            })
+            .await?;
+        Ok(response.map(|response| self.namespace.strip(response)).into())
     }
 
     fn options(&self) -> &KVOptions {
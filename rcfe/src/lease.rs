@@ -1,18 +1,21 @@
 use crate::{
-    Error, GrantOptions, GrpcLeaseClient, KeepAliveHandler, LeaseClient, LeaseClientOptions,
-    LeaseGrantResponse, LeaseKeepAliveRequest, LeaseKeepAliveResponse, LeaseRevokeRequest,
-    LeaseRevokeResponse, LeaseTimeToLiveResponse, TimeToLiveOptions,
+    ByteSequence, Error, GrantOptions, GrpcLeaseClient, KeepAliveHandler, LeaseClient,
+    LeaseClientOptions, LeaseGrantResponse, LeaseKeepAliveRequest, LeaseKeepAliveResponse,
+    LeaseRevokeRequest, LeaseRevokeResponse, LeaseTimeToLiveResponse, Namespace, Namespaceable,
+    TimeToLiveOptions,
+    context::{ClientContext, GrpcChannel, Idempotency},
+    lifecycle::Lifecycle,
 };
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tonic::{
     Request, Response, Streaming, async_trait, codegen::tokio_stream::wrappers::ReceiverStream,
-    transport::Channel,
 };
 
 pub struct DefaultKeepAliveHandler {
     lease_id: i64,
     sender: tokio::sync::mpsc::Sender<LeaseKeepAliveRequest>,
     response: Response<Streaming<LeaseKeepAliveResponse>>,
+    lifecycle: Arc<Lifecycle>,
 }
 
 impl DefaultKeepAliveHandler {
@@ -20,11 +23,23 @@ impl DefaultKeepAliveHandler {
         lease_id: i64,
         sender: tokio::sync::mpsc::Sender<LeaseKeepAliveRequest>,
         response: Response<Streaming<LeaseKeepAliveResponse>>,
+        lifecycle: Arc<Lifecycle>,
     ) -> Self {
         DefaultKeepAliveHandler {
             lease_id,
             sender,
             response,
+            lifecycle,
+        }
+    }
+
+    /// Receives the next response of the keep-alive stream, `None` once the stream is closed
+    /// or the client was shut down.
+    pub(crate) async fn message(&mut self) -> Result<Option<LeaseKeepAliveResponse>, Error> {
+        tokio::select! {
+            biased;
+            _ = self.lifecycle.streams_closed() => Ok(None),
+            message = self.response.get_mut().message() => Ok(message?),
         }
     }
 }
@@ -40,6 +55,7 @@ impl KeepAliveHandler for DefaultKeepAliveHandler {
     }
 
     async fn keep_alive(&mut self) -> Result<(), Error> {
+        self.lifecycle.check_open()?;
         Ok(self
             .sender
             .send(LeaseKeepAliveRequest { id: self.lease_id })
@@ -50,14 +66,61 @@ impl KeepAliveHandler for DefaultKeepAliveHandler {
 
 #[derive(Clone)]
 pub struct DefaultLeaseClient {
-    inner: GrpcLeaseClient<Channel>,
+    namespace: Namespace,
+    context: ClientContext,
+    inner: GrpcLeaseClient<GrpcChannel>,
 }
 
 impl DefaultLeaseClient {
-    pub fn new(options: LeaseClientOptions) -> Self {
+    pub(crate) fn new(options: LeaseClientOptions, context: ClientContext) -> Self {
         DefaultLeaseClient {
-            inner: GrpcLeaseClient::new(options.channel().clone()),
+            namespace: Namespace::from(options.namespace()),
+            inner: context.configure(GrpcLeaseClient::new(
+                context.channel(options.channel().clone()),
+            )),
+            context,
+        }
+    }
+
+    /// Derives a client using the namespace, sharing the channel of this client.
+    pub(crate) fn with_namespace(&self, namespace: Option<ByteSequence>) -> Self {
+        DefaultLeaseClient {
+            namespace: Namespace::from(namespace),
+            ..self.clone()
+        }
+    }
+
+    /// Revokes the leases granted through the client, bounded by `timeout`.
+    /// All leases are attempted, the first error is returned.
+    pub(crate) async fn revoke_granted(&self, timeout: Duration) -> Result<(), Error> {
+        let mut result = Ok(());
+        for lease_id in self.context.lifecycle().take_leases() {
+            let revoked = self
+                .context
+                .call_unguarded(Some(timeout), Idempotency::NonIdempotent, || {
+                    let mut inner = self.inner.clone();
+                    let request = Request::new(LeaseRevokeRequest { id: lease_id });
+                    async move { inner.lease_revoke(request).await }
+                })
+                .await;
+            if let (Err(e), Ok(())) = (revoked, &result) {
+                result = Err(e);
+            }
         }
+        result
+    }
+
+    /// Opens a keep-alive stream for the lease.
+    pub(crate) async fn keep_alive_handler(
+        &self,
+        lease_id: i64,
+    ) -> Result<DefaultKeepAliveHandler, Error> {
+        self.context
+            .call(Idempotency::Idempotent, || {
+                let inner = self.inner.clone();
+                open_keep_alive(inner, lease_id, self.context.lifecycle().clone())
+            })
+            .await
     }
 }
 
@@ -68,51 +131,33 @@ impl LeaseClient for DefaultLeaseClient {
         ttl: Duration,
         options: GrantOptions,
     ) -> Result<Response<LeaseGrantResponse>, Error> {
-        Ok(self.inner.lease_grant(options.to_request(&ttl)).await?)
+        let request = options.to_request(&ttl);
+        let response = self
+            .context
+            .call(Idempotency::NonIdempotent, || {
+                let mut inner = self.inner.clone();
+                async move { inner.lease_grant(request).await }
+            })
+            .await?;
+        self.context.lifecycle().lease_granted(response.get_ref().id);
+        Ok(response)
     }
 
     async fn revoke(&self, lease_id: i64) -> Result<Response<LeaseRevokeResponse>, Error> {
-        let request = Request::new(LeaseRevokeRequest { id: lease_id });
-        Ok(self.inner.clone().lease_revoke(request).await?)
-    }
-
-    async fn keep_alive(&mut self, lease_id: i64) -> Result<impl KeepAliveHandler, Error> {
-        let (tx, rx) = tokio::sync::mpsc::channel::<LeaseKeepAliveRequest>(8);
-
-        // 先尝试发送第一条（如果失败，直接返回错误）
-        tx.send(LeaseKeepAliveRequest { id: lease_id })
-            .await
-            .map_err(|e| Error::KeepAliveError(e.to_string()))?;
-
-        let request_stream = ReceiverStream::new(rx);
         let response = self
-            .inner
-            .lease_keep_alive(Request::new(request_stream))
+            .context
+            .call(Idempotency::NonIdempotent, || {
+                let mut inner = self.inner.clone();
+                let request = Request::new(LeaseRevokeRequest { id: lease_id });
+                async move { inner.lease_revoke(request).await }
+            })
             .await?;
+        self.context.lifecycle().lease_revoked(lease_id);
+        Ok(response)
+    }
 
-        let mut streaming = response.into_inner();
-
-        let id = match streaming.message().await? {
-            None => {
-                return Err(Error::KeepAliveError(
-                    "Failed to create keep-alive stream: no response received".to_string(),
-                ));
-            }
-            Some(resp) => {
-                if resp.id != lease_id {
-                    return Err(Error::KeepAliveError(
-                        "Failed to create keep-alive stream: lease ID mismatch".to_string(),
-                    ));
-                }
-                resp.id
-            }
-        };
-
-        Ok(DefaultKeepAliveHandler::new(
-            id,
-            tx,
-            Response::new(streaming),
-        ))
+    async fn keep_alive(&mut self, lease_id: i64) -> Result<impl KeepAliveHandler, Error> {
+        self.keep_alive_handler(lease_id).await
     }
 
     async fn time_to_live_with_options(
@@ -121,6 +166,59 @@ impl LeaseClient for DefaultLeaseClient {
         options: TimeToLiveOptions,
     ) -> Result<Response<LeaseTimeToLiveResponse>, Error> {
         let request = options.to_request(lease_id);
-        Ok(self.inner.lease_time_to_live(request).await?)
+        let response = self
+            .context
+            .call(Idempotency::Idempotent, || {
+                let mut inner = self.inner.clone();
+                async move { inner.lease_time_to_live(request).await }
+            })
+            .await?;
+        Ok(response.map(|response| self.namespace.strip(response)))
     }
 }
+
+/// Opens a keep-alive stream and waits for the first response of the server.
+/// Its request stream ends on shutdown.
+async fn open_keep_alive(
+    mut inner: GrpcLeaseClient<GrpcChannel>,
+    lease_id: i64,
+    lifecycle: Arc<Lifecycle>,
+) -> Result<DefaultKeepAliveHandler, Error> {
+    let (tx, rx) = tokio::sync::mpsc::channel::<LeaseKeepAliveRequest>(8);
+
+    // 先尝试发送第一条（如果失败，直接返回错误）
+    tx.send(LeaseKeepAliveRequest { id: lease_id })
+        .await
+        .map_err(|e| Error::KeepAliveError(e.to_string()))?;
+
+    // The request side ends on shutdown, even while the handler keeps its sender.
+    let request_stream = lifecycle.until_closed(ReceiverStream::new(rx));
+    let response = inner
+        .lease_keep_alive(Request::new(request_stream))
+        .await?;
+
+    let mut streaming = response.into_inner();
+
+    let id = match streaming.message().await? {
+        None => {
+            return Err(Error::KeepAliveError(
+                "Failed to create keep-alive stream: no response received".to_string(),
+            ));
+        }
+        Some(resp) => {
+            if resp.id != lease_id {
+                return Err(Error::KeepAliveError(
+                    "Failed to create keep-alive stream: lease ID mismatch".to_string(),
+                ));
+            }
+            resp.id
+        }
+    };
+
+    Ok(DefaultKeepAliveHandler::new(
+        id,
+        tx,
+        Response::new(streaming),
+        lifecycle,
+    ))
+}
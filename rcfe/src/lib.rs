@@ -1,9 +1,16 @@
 mod prelude;
+pub mod blocking;
+mod auth;
+mod channel;
 mod client;
+mod context;
+mod endpoint;
 mod kv;
 mod factory;
 mod txn;
 mod lease;
+mod lifecycle;
+mod namespace;
 mod watch;
 
 pub use prelude::*;
\ No newline at end of file
@@ -0,0 +1,170 @@
+use crate::{Error, WatchRequest, WatchRequestType};
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{Notify, mpsc::Sender};
+use tonic::codegen::tokio_stream::Stream;
+
+/// Tracks what has to be torn down when a client is shut down: in-flight requests,
+/// open watch and keep-alive streams and the leases granted through the client.
+#[derive(Debug, Default)]
+pub(crate) struct Lifecycle {
+    closed: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    streams_closed: AtomicBool,
+    streams: Notify,
+    watchers: Mutex<Vec<ActiveWatch>>,
+    leases: Mutex<HashSet<i64>>,
+}
+
+/// A watch created on a stream, cancelled through its request sender on shutdown.
+#[derive(Debug)]
+struct ActiveWatch {
+    id: i64,
+    sender: Sender<WatchRequest>,
+}
+
+/// Request stream of a watch or keep-alive, ending once the streams of the client are closed.
+/// Requests queued before are still sent, so that the cancellations of the watches arrive.
+pub(crate) struct UntilClosed<S> {
+    inner: S,
+    closed: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+impl<S: Stream + Unpin> Stream for UntilClosed<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        if let Poll::Ready(item) = Pin::new(&mut self.inner).poll_next(cx) {
+            return Poll::Ready(item);
+        }
+        self.closed.as_mut().poll(cx).map(|()| None)
+    }
+}
+
+/// Counts a request as in flight until dropped.
+pub(crate) struct CallGuard {
+    lifecycle: Arc<Lifecycle>,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Returns an error if the client was shut down.
+    pub(crate) fn check_open(&self) -> Result<(), Error> {
+        match self.closed.load(Ordering::Acquire) {
+            true => Err(Error::ClientClosed),
+            false => Ok(()),
+        }
+    }
+
+    /// Marks a request as in flight, failing if the client was shut down.
+    pub(crate) fn begin_call(self: &Arc<Self>) -> Result<CallGuard, Error> {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        let guard = CallGuard {
+            lifecycle: self.clone(),
+        };
+        // Checked after counting, so that `wait_idle` cannot miss a request racing with `close`.
+        self.check_open()?;
+        Ok(guard)
+    }
+
+    /// Registers a watch so that it is cancelled on shutdown.
+    /// Watches whose stream already ended are dropped from the registry.
+    pub(crate) fn register_watch(&self, id: i64, sender: Sender<WatchRequest>) {
+        let mut watchers = self.watchers.lock().unwrap();
+        watchers.retain(|watch| !watch.sender.is_closed());
+        watchers.push(ActiveWatch { id, sender });
+    }
+
+    /// Records a lease granted through the client.
+    pub(crate) fn lease_granted(&self, lease_id: i64) {
+        self.leases.lock().unwrap().insert(lease_id);
+    }
+
+    /// Forgets a lease that was revoked.
+    pub(crate) fn lease_revoked(&self, lease_id: i64) {
+        self.leases.lock().unwrap().remove(&lease_id);
+    }
+
+    /// Marks the client as shut down, returning false if it already was.
+    pub(crate) fn close(&self) -> bool {
+        !self.closed.swap(true, Ordering::AcqRel)
+    }
+
+    /// Cancels every registered watch, then closes the request side of every watch and
+    /// keep-alive stream and ends their responses for the watchers and handlers.
+    pub(crate) fn close_streams(&self) {
+        let watchers = std::mem::take(&mut *self.watchers.lock().unwrap());
+        for watch in watchers {
+            // A full or closed stream is closed below without the cancellation.
+            let _ = watch
+                .sender
+                .try_send(WatchRequestType::Cancel(watch.id).to_request());
+        }
+        self.streams_closed.store(true, Ordering::Release);
+        self.streams.notify_waiters();
+    }
+
+    /// Waits until the streams of the client are closed by [`close_streams`](Self::close_streams).
+    pub(crate) async fn streams_closed(&self) {
+        loop {
+            let notified = self.streams.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.streams_closed.load(Ordering::Acquire) {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Wraps a request stream so that it ends once the streams of the client are closed.
+    pub(crate) fn until_closed<S>(self: &Arc<Self>, stream: S) -> UntilClosed<S> {
+        let lifecycle = self.clone();
+        UntilClosed {
+            inner: stream,
+            closed: Box::pin(async move { lifecycle.streams_closed().await }),
+        }
+    }
+
+    /// Returns the leases granted through the client that were not revoked.
+    pub(crate) fn take_leases(&self) -> Vec<i64> {
+        self.leases.lock().unwrap().drain().collect()
+    }
+
+    /// Waits until no request is in flight.
+    /// # Errors
+    /// Returns an `Error::Timeout` if requests are still in flight after `timeout`.
+    pub(crate) async fn wait_idle(&self, timeout: Duration) -> Result<(), Error> {
+        let idle = async {
+            loop {
+                let notified = self.idle.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if self.in_flight.load(Ordering::Acquire) == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+
+        tokio::time::timeout(timeout, idle)
+            .await
+            .map_err(|_| Error::Timeout(timeout))
+    }
+}
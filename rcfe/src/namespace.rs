@@ -0,0 +1,88 @@
+use crate::{Namespace, PrefixNamespace, StripNamespace};
+use prost::Message;
+use tonic::{
+    Status,
+    codec::{BufferSettings, Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+};
+use tonic_prost::{ProstCodec, ProstDecoder, ProstEncoder};
+
+/// Protobuf codec applying a namespace to the messages of a stream.
+/// Requests are prefixed as they are encoded and responses are stripped as they are decoded,
+/// so the namespace also applies to the raw `Streaming` handed out to the caller.
+pub(crate) struct NamespaceCodec<T, U> {
+    namespace: Namespace,
+    inner: ProstCodec<T, U>,
+}
+
+impl<T, U> NamespaceCodec<T, U> {
+    pub(crate) fn new(namespace: Namespace) -> Self {
+        NamespaceCodec {
+            namespace,
+            inner: ProstCodec::new(),
+        }
+    }
+}
+
+impl<T, U> Codec for NamespaceCodec<T, U>
+where
+    T: Message + PrefixNamespace + Send + 'static,
+    U: Message + StripNamespace + Default + Send + 'static,
+{
+    type Encode = T;
+    type Decode = U;
+    type Encoder = NamespaceEncoder<T>;
+    type Decoder = NamespaceDecoder<U>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        NamespaceEncoder {
+            namespace: self.namespace.clone(),
+            inner: self.inner.encoder(),
+        }
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        NamespaceDecoder {
+            namespace: self.namespace.clone(),
+            inner: self.inner.decoder(),
+        }
+    }
+}
+
+pub(crate) struct NamespaceEncoder<T> {
+    namespace: Namespace,
+    inner: ProstEncoder<T>,
+}
+
+impl<T: Message + PrefixNamespace> Encoder for NamespaceEncoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn encode(&mut self, item: T, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        self.inner.encode(self.namespace.prefix(item), dst)
+    }
+
+    fn buffer_settings(&self) -> BufferSettings {
+        self.inner.buffer_settings()
+    }
+}
+
+pub(crate) struct NamespaceDecoder<U> {
+    namespace: Namespace,
+    inner: ProstDecoder<U>,
+}
+
+impl<U: Message + StripNamespace + Default> Decoder for NamespaceDecoder<U> {
+    type Item = U;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<U>, Status> {
+        Ok(self
+            .inner
+            .decode(src)?
+            .map(|item| self.namespace.strip(item)))
+    }
+
+    fn buffer_settings(&self) -> BufferSettings {
+        self.inner.buffer_settings()
+    }
+}
@@ -1,5 +1,13 @@
-use crate::{Compare, Error, GrpcKVClient, RequestOp, Txn, TxnRequest, TxnResponse};
-use tonic::{Response, async_trait, transport::Channel};
+use crate::{
+    Compare, Error, GrpcKVClient, Namespace, RequestOp, Txn, TxnRequest, TxnResponse,
+    context::{ClientContext, GrpcChannel, Idempotency},
+    etcdserverpb::{
+        Compare as PbCompare, RequestOp as PbRequestOp,
+        compare::{CompareResult, TargetUnion},
+        request_op::Request,
+    },
+};
+use tonic::{Response, async_trait};
 
 pub struct DefaultTxn {
     /// The list of comparisons to evaluate. like Txn.is
@@ -13,18 +21,28 @@ pub struct DefaultTxn {
 
     seen_then: bool,
     seen_otherwise: bool,
-    kv_client: GrpcKVClient<Channel>,
+    retries_disabled: bool,
+    kv_client: GrpcKVClient<GrpcChannel>,
+    namespace: Namespace,
+    context: ClientContext,
 }
 
 impl DefaultTxn {
-    pub fn new(kv_client: GrpcKVClient<Channel>) -> Self {
+    pub(crate) fn new(
+        kv_client: GrpcKVClient<GrpcChannel>,
+        namespace: Namespace,
+        context: ClientContext,
+    ) -> Self {
         DefaultTxn {
             kv_client,
+            namespace,
+            context,
             when_compares: Vec::new(),
             then_ops: Vec::new(),
             otherwise_ops: Vec::new(),
             seen_then: false,
             seen_otherwise: false,
+            retries_disabled: false,
         }
     }
 }
@@ -83,6 +101,11 @@ impl Txn for DefaultTxn {
         Ok(self)
     }
 
+    fn disable_retries(&mut self) -> &mut Self {
+        self.retries_disabled = true;
+        self
+    }
+
     async fn commit(&mut self) -> Result<Response<TxnResponse>, Error> {
         // Here you would typically send the transaction to the etcd server
         // and return the response. This is a placeholder implementation.
@@ -110,7 +133,89 @@ impl Txn for DefaultTxn {
                 .collect();
         }
 
+        // Read-only txns are always safe to send again. A txn that writes is only safe if
+        // the compares of every written key fail once the first attempt was applied.
+        let safe = is_read_only(&txn_request) || is_guarded(&txn_request);
+        let idempotency = if safe && !self.retries_disabled {
+            Idempotency::Idempotent
+        } else {
+            Idempotency::NonIdempotent
+        };
+
         // Send txn_request to etcd server and get response
-        Ok(self.kv_client.txn(txn_request).await?)
+        let txn_request = self.namespace.prefix(txn_request);
+        self.context.check_encoded_len(&txn_request)?;
+        let response = self
+            .context
+            .call(idempotency, || {
+                let mut kv_client = self.kv_client.clone();
+                let txn_request = txn_request.clone();
+                async move { kv_client.txn(txn_request).await }
+            })
+            .await?;
+        Ok(response.map(|response| self.namespace.strip(response)))
     }
 }
+
+/// Returns true if the txn only contains range requests, including nested txns.
+fn is_read_only(txn_request: &TxnRequest) -> bool {
+    ops_read_only(&txn_request.success) && ops_read_only(&txn_request.failure)
+}
+
+fn ops_read_only(ops: &[PbRequestOp]) -> bool {
+    ops.iter().all(|op| match &op.request {
+        Some(Request::RequestRange(_)) | None => true,
+        Some(Request::RequestTxn(txn)) => is_read_only(txn),
+        Some(_) => false,
+    })
+}
+
+/// Returns true if applying the txn twice has the same effect as applying it once:
+/// the failure branch only reads, and every key written on success is compared in a way
+/// that no longer holds after the write.
+fn is_guarded(txn_request: &TxnRequest) -> bool {
+    ops_read_only(&txn_request.failure)
+        && txn_request.success.iter().all(|op| match &op.request {
+            Some(Request::RequestRange(_)) | None => true,
+            Some(Request::RequestPut(put)) => txn_request
+                .compare
+                .iter()
+                .any(|compare| guards_put(compare, &put.key)),
+            Some(Request::RequestDeleteRange(delete)) => {
+                delete.range_end.is_empty()
+                    && txn_request
+                        .compare
+                        .iter()
+                        .any(|compare| guards_delete(compare, &delete.key))
+            }
+            Some(Request::RequestTxn(txn)) => is_read_only(txn),
+        })
+}
+
+/// A put changes the mod revision and the version of the key, and its create revision
+/// only if the key did not exist.
+fn guards_put(compare: &PbCompare, key: &[u8]) -> bool {
+    compares_key(compare, key)
+        && match compare.target_union {
+            Some(TargetUnion::ModRevision(_)) | Some(TargetUnion::Version(_)) => true,
+            Some(TargetUnion::CreateRevision(revision)) => revision == 0,
+            _ => false,
+        }
+}
+
+/// A delete resets the revisions and the version of the key to 0, or is a no-op if
+/// the key did not exist.
+fn guards_delete(compare: &PbCompare, key: &[u8]) -> bool {
+    compares_key(compare, key)
+        && matches!(
+            compare.target_union,
+            Some(TargetUnion::ModRevision(_))
+                | Some(TargetUnion::Version(_))
+                | Some(TargetUnion::CreateRevision(_))
+        )
+}
+
+/// Returns true if the compare checks the single key for equality.
+fn compares_key(compare: &PbCompare, key: &[u8]) -> bool {
+    compare.key == key && compare.range_end.is_empty() && compare.result == CompareResult::Equal as i32
+}
@@ -1,14 +1,23 @@
 use crate::{
-    Error, GrpcWatchClient, WatchClient, WatchClientOptions, WatchRequest,
-    WatchRequestType, WatchResponse, Watcher,
+    ByteSequence, Error, Namespace, Namespaceable, WatchClient, WatchClientOptions,
+    WatchCreateOptions, WatchRequest, WatchRequestType, WatchResponse, Watcher,
+    context::{ClientContext, GrpcChannel, Idempotency, set_require_leader},
+    lifecycle::Lifecycle,
+    namespace::NamespaceCodec,
+};
+use std::sync::Arc;
+use tonic::{
+    GrpcMethod, Request, Response, Status, Streaming, async_trait,
+    client::Grpc,
+    codegen::{http::uri::PathAndQuery, tokio_stream::wrappers::ReceiverStream},
 };
-use tonic::{async_trait, codegen::tokio_stream::wrappers::ReceiverStream, transport::Channel, Response, Streaming};
 
 pub struct DefaultWatcher {
     id: i64,
     request: WatchRequestType,
     response: Response<Streaming<WatchResponse>>,
     sender: tokio::sync::mpsc::Sender<WatchRequest>,
+    lifecycle: Arc<Lifecycle>,
 }
 
 impl DefaultWatcher {
@@ -17,12 +26,24 @@ impl DefaultWatcher {
         request: WatchRequestType,
         response: Response<Streaming<WatchResponse>>,
         sender: tokio::sync::mpsc::Sender<WatchRequest>,
+        lifecycle: Arc<Lifecycle>,
     ) -> Self {
         DefaultWatcher {
             id,
             request,
             response,
             sender,
+            lifecycle,
+        }
+    }
+
+    /// Receives the next response of the watch stream, `None` once the stream is closed
+    /// or the client was shut down.
+    pub(crate) async fn message(&mut self) -> Result<Option<WatchResponse>, Error> {
+        tokio::select! {
+            biased;
+            _ = self.lifecycle.streams_closed() => Ok(None),
+            message = self.response.get_mut().message() => Ok(message?),
         }
     }
 }
@@ -34,6 +55,7 @@ impl Watcher for DefaultWatcher {
     }
 
     async fn watch(&mut self) -> Result<(), Error> {
+        self.lifecycle.check_open()?;
         self.sender
             .send(self.request.to_request())
             .await
@@ -49,6 +71,7 @@ impl Watcher for DefaultWatcher {
     }
 
     async fn progress(&mut self) -> Result<(), Error> {
+        self.lifecycle.check_open()?;
         let request = WatchRequestType::Progress.to_request();
         self.sender
             .send(request)
@@ -57,6 +80,7 @@ impl Watcher for DefaultWatcher {
     }
 
     async fn cancel(&mut self) -> Result<(), Error> {
+        self.lifecycle.check_open()?;
         let request = WatchRequestType::Cancel(self.id).to_request();
         self.sender
             .send(request)
@@ -68,15 +92,42 @@ impl Watcher for DefaultWatcher {
 #[derive(Clone, Debug)]
 pub struct DefaultWatchClient {
     options: WatchClientOptions,
-    inner: GrpcWatchClient<Channel>,
+    context: ClientContext,
+    namespace: Namespace,
+    inner: Grpc<GrpcChannel>,
 }
 
 impl DefaultWatchClient {
-    pub fn new(options: WatchClientOptions) -> Self {
+    pub(crate) fn new(options: WatchClientOptions, context: ClientContext) -> Self {
         let channel = options.clone().channel();
         DefaultWatchClient {
+            namespace: Namespace::from(options.namespace()),
             options,
-            inner: GrpcWatchClient::new(channel),
+            inner: context.configure(Grpc::new(context.channel(channel))),
+            context,
+        }
+    }
+
+    /// Opens a watch stream for the request.
+    pub(crate) async fn watcher(&self, request: WatchRequestType) -> Result<DefaultWatcher, Error> {
+        self.context
+            .call(Idempotency::Idempotent, || {
+                open_watch(
+                    self.inner.clone(),
+                    self.namespace.clone(),
+                    request.clone(),
+                    self.context.lifecycle().clone(),
+                )
+            })
+            .await
+    }
+
+    /// Derives a client using the namespace, sharing the channel of this client.
+    pub(crate) fn with_namespace(&self, namespace: Option<ByteSequence>) -> Self {
+        DefaultWatchClient {
+            namespace: Namespace::from(namespace.clone()),
+            options: self.options.with_namespace(namespace),
+            ..self.clone()
         }
     }
 }
@@ -84,37 +135,73 @@ impl DefaultWatchClient {
 #[async_trait]
 impl WatchClient for DefaultWatchClient {
     async fn watch(&mut self, request: WatchRequestType) -> Result<impl Watcher, Error> {
-        let watch_request: WatchRequest = request.to_request();
-
-        let (tx, rx) = tokio::sync::mpsc::channel::<WatchRequest>(8);
-
-        tx.send(watch_request)
-            .await
-            .map_err(|e| Error::WatchError(e.to_string()))?;
-
-        let request_stream = ReceiverStream::new(rx);
-
-        let response = self.inner.watch(request_stream).await?;
-
-        let mut streaming = response.into_inner();
-        let watch_id = match streaming.message().await? {
-            Some(msg) => msg.watch_id,
-            None => {
-                return Err(Error::WatchError(
-                    "Failed to receive watch ID from server".to_string(),
-                ));
-            }
-        };
-
-        Ok(DefaultWatcher::new(
-            watch_id,
-            request,
-            Response::new(streaming),
-            tx,
-        ))
+        self.watcher(request).await
     }
 
     fn options(&self) -> &WatchClientOptions {
         &self.options
     }
 }
+
+/// Opens a watch stream and waits for the server to acknowledge the watch.
+/// The watch is registered with the lifecycle, so that it is cancelled and its request
+/// stream ends on shutdown.
+async fn open_watch(
+    mut inner: Grpc<GrpcChannel>,
+    namespace: Namespace,
+    request: WatchRequestType,
+    lifecycle: Arc<Lifecycle>,
+) -> Result<DefaultWatcher, Error> {
+    let watch_request: WatchRequest = request.to_request();
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<WatchRequest>(8);
+
+    tx.send(watch_request)
+        .await
+        .map_err(|e| Error::WatchError(e.to_string()))?;
+
+    let mut request_stream = Request::new(lifecycle.until_closed(ReceiverStream::new(rx)));
+    if let WatchRequestType::Create(WatchCreateOptions {
+        require_leader: Some(require_leader),
+        ..
+    }) = &request
+    {
+        set_require_leader(&mut request_stream, *require_leader);
+    }
+
+    inner
+        .ready()
+        .await
+        .map_err(|e| Status::unknown(format!("Service was not ready: {e}")))?;
+    request_stream
+        .extensions_mut()
+        .insert(GrpcMethod::new("etcdserverpb.Watch", "Watch"));
+    // The watch is called with a codec applying the namespace, instead of the generated client.
+    let response = inner
+        .streaming(
+            request_stream,
+            PathAndQuery::from_static("/etcdserverpb.Watch/Watch"),
+            NamespaceCodec::<WatchRequest, WatchResponse>::new(namespace),
+        )
+        .await?;
+
+    let mut streaming = response.into_inner();
+    let watch_id = match streaming.message().await? {
+        Some(msg) => msg.watch_id,
+        None => {
+            return Err(Error::WatchError(
+                "Failed to receive watch ID from server".to_string(),
+            ));
+        }
+    };
+
+    lifecycle.register_watch(watch_id, tx.clone());
+
+    Ok(DefaultWatcher::new(
+        watch_id,
+        request,
+        Response::new(streaming),
+        tx,
+        lifecycle,
+    ))
+}